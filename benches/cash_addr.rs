@@ -0,0 +1,27 @@
+//! cash_addr codec benchmarks.
+//! Run with `cargo bench --bench cash_addr`.
+
+mod util;
+
+use std::hint::black_box;
+
+use bch_addr::cash_addr::{self, AddressType, MAX_BODY_LEN};
+use util::bench;
+
+fn main() {
+    for &len in &[20, 32] {
+        let hash: Vec<u8> = (0..len as u8).map(|i| i.wrapping_mul(37)).collect();
+        let address = cash_addr::encode("bitcoincash", AddressType::P2PKH, &hash).unwrap();
+        let mut buf = [0; 12 + MAX_BODY_LEN];
+
+        bench(&format!("encode/{}", len), || {
+            black_box(cash_addr::encode(black_box("bitcoincash"), AddressType::P2PKH, black_box(&hash)).unwrap());
+        });
+        bench(&format!("encode_into/{}", len), || {
+            black_box(cash_addr::encode_into(black_box("bitcoincash"), AddressType::P2PKH, black_box(&hash), &mut buf).unwrap());
+        });
+        bench(&format!("decode/{}", len), || {
+            black_box(cash_addr::decode(black_box(&address)).unwrap());
+        });
+    }
+}
@@ -0,0 +1,68 @@
+//! Converter benchmarks.
+//! Run with `cargo bench --bench converter`.
+
+mod util;
+
+use std::hint::black_box;
+
+use bch_addr::{Converter, ConversionCtx, Network};
+use util::bench;
+
+const LEGACY: &str = "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR";
+const CASH_ADDR: &str = "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk";
+const CASH_ADDR_NO_PREFIX: &str = "qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk";
+const SLP_ADDR_NO_PREFIX: &str = "qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg";
+
+fn main() {
+    let converter = Converter::new().add_prefixes(
+        &[("simpleledger", Network::Mainnet), ("slptest", Network::Testnet)],
+        "SLPAddr",
+    );
+    let mut ctx = ConversionCtx::new(&converter);
+
+    bench("to_cash_addr/legacy", || {
+        black_box(converter.to_cash_addr(black_box(LEGACY)).unwrap());
+    });
+    bench("to_cash_addr/cash_addr", || {
+        black_box(converter.to_cash_addr(black_box(CASH_ADDR)).unwrap());
+    });
+    bench("to_legacy_addr/cash_addr", || {
+        black_box(converter.to_legacy_addr(black_box(CASH_ADDR)).unwrap());
+    });
+    bench("to_legacy_addr/no_prefix", || {
+        black_box(converter.to_legacy_addr(black_box(CASH_ADDR_NO_PREFIX)).unwrap());
+    });
+    bench("ctx.to_cash_addr/legacy", || {
+        black_box(ctx.to_cash_addr(black_box(LEGACY)).unwrap().len());
+    });
+    bench("ctx.to_legacy_addr/cash_addr", || {
+        black_box(ctx.to_legacy_addr(black_box(CASH_ADDR)).unwrap().len());
+    });
+    bench("parse/legacy", || {
+        black_box(converter.parse(black_box(LEGACY)).unwrap());
+    });
+    bench("parse/cash_addr", || {
+        black_box(converter.parse(black_box(CASH_ADDR)).unwrap());
+    });
+    bench("parse/no_prefix", || {
+        black_box(converter.parse(black_box(CASH_ADDR_NO_PREFIX)).unwrap());
+    });
+    bench("parse/no_prefix_other_format", || {
+        black_box(converter.parse(black_box(SLP_ADDR_NO_PREFIX)).unwrap());
+    });
+    bench("is_mainnet_addr/legacy", || {
+        black_box(converter.is_mainnet_addr(black_box(LEGACY)));
+    });
+    bench("is_mainnet_addr/cash_addr", || {
+        black_box(converter.is_mainnet_addr(black_box(CASH_ADDR)));
+    });
+    bench("is_p2pkh_addr/no_prefix", || {
+        black_box(converter.is_p2pkh_addr(black_box(CASH_ADDR_NO_PREFIX)));
+    });
+    bench("is_cash_addr/legacy", || {
+        black_box(converter.is_cash_addr(black_box(LEGACY)));
+    });
+    bench("is_legacy_addr/cash_addr", || {
+        black_box(converter.is_legacy_addr(black_box(CASH_ADDR)));
+    });
+}
@@ -0,0 +1,20 @@
+//! Minimal timing harness shared by the benchmarks.
+
+use std::time::Instant;
+
+const ITERATIONS: u32 = 200_000;
+
+/// Run `f` for a warm-up round, then report the mean time per call.
+pub fn bench<F: FnMut()>(name: &str, mut f: F) {
+    for _ in 0..ITERATIONS / 10 {
+        f();
+    }
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let ns = start.elapsed().as_secs_f64() * 1e9 / f64::from(ITERATIONS);
+
+    println!("{:<32} {:>8.1} ns/iter", name, ns);
+}
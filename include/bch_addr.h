@@ -0,0 +1,156 @@
+/* bch_addr C API. Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#ifndef BCH_ADDR_H
+#define BCH_ADDR_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+// Success.
+#define BCHADDR_OK 0
+
+// A required pointer argument was NULL.
+#define BCHADDR_ERR_NULL_POINTER 1
+
+// A string argument was not valid UTF-8.
+#define BCHADDR_ERR_INVALID_UTF8 2
+
+// A network argument was out of range.
+#define BCHADDR_ERR_INVALID_ARGUMENT 3
+
+// The address is not valid in any registered format (bad checksum, characters, length, ...).
+#define BCHADDR_ERR_INVALID_ADDRESS 4
+
+// The address has a cash_addr prefix that is not registered.
+#define BCHADDR_ERR_UNKNOWN_PREFIX 5
+
+// No prefix is registered for the requested format and network.
+#define BCHADDR_ERR_UNKNOWN_FORMAT 6
+
+// `Network::Mainnet`.
+#define BCHADDR_NETWORK_MAINNET 0
+
+// `Network::Testnet`.
+#define BCHADDR_NETWORK_TESTNET 1
+
+// `Network::Regtest`.
+#define BCHADDR_NETWORK_REGTEST 2
+
+// Keep the network of the input address.
+#define BCHADDR_NETWORK_UNCHANGED -1
+
+// `AddressFormat::Legacy`.
+#define BCHADDR_FORMAT_LEGACY 0
+
+// `AddressFormat::CashAddr`.
+#define BCHADDR_FORMAT_CASH_ADDR 1
+
+// `AddressFormat::Other`, a format registered with `bchaddr_converter_add_prefix`.
+#define BCHADDR_FORMAT_OTHER 2
+
+// `AddressType::P2PKH`.
+#define BCHADDR_TYPE_P2PKH 0
+
+// `AddressType::P2SH`.
+#define BCHADDR_TYPE_P2SH 1
+
+// `AddressType::Other`, a type field registered with `bchaddr_converter_add_type_bits`;
+// `BchAddrParsed::type_bits` holds which one.
+#define BCHADDR_TYPE_OTHER 2
+
+// Opaque converter handle.
+typedef struct BchAddrConverter BchAddrConverter;
+
+// Parsed address filled in by `bchaddr_parse`.
+typedef struct BchAddrParsed {
+  // One of the `BCHADDR_FORMAT_*` values.
+  int format;
+  // One of the `BCHADDR_NETWORK_*` values.
+  int network;
+  // One of the `BCHADDR_TYPE_*` values.
+  int addr_type;
+  // Hash bytes; only the first `hash_len` are meaningful.
+  uint8_t hash[64];
+  // Length of the hash.
+  size_t hash_len;
+  // Type field of the version byte: 0 for P2PKH, 1 for P2SH, 2 to 15 for `BCHADDR_TYPE_OTHER`.
+  uint8_t type_bits;
+} BchAddrParsed;
+
+
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Create a converter with the built-in prefixes.
+// Release it with `bchaddr_converter_free`.
+struct BchAddrConverter *bchaddr_converter_new(void);
+
+// Release a converter created by `bchaddr_converter_new`.
+// # Safety
+// `converter` must be NULL or a pointer returned by `bchaddr_converter_new` that has not been freed.
+void bchaddr_converter_free(struct BchAddrConverter *converter);
+
+// Register `prefix` for `network` under the user-defined format `format_name`.
+// # Safety
+// `converter` must come from `bchaddr_converter_new`; the strings must be NUL-terminated.
+int bchaddr_converter_add_prefix(struct BchAddrConverter *converter,
+                                 const char *prefix,
+                                 int network,
+                                 const char *format_name);
+
+// Accept the unassigned type fields `type_bits[0..len]` (2 to 15, others are ignored) in addresses
+// of the user-defined format `format_name` (cash_addr when NULL).
+// # Safety
+// `converter` must come from `bchaddr_converter_new`; `format_name` must be NULL or NUL-terminated;
+// `type_bits` must be valid for reads of `len` bytes.
+int bchaddr_converter_add_type_bits(struct BchAddrConverter *converter,
+                                    const char *format_name,
+                                    const uint8_t *type_bits,
+                                    size_t len);
+
+// Convert `addr` to cash_addr format and store the result in `*out`.
+// # Safety
+// `converter` must come from `bchaddr_converter_new`; `addr` must be NUL-terminated;
+// `out` must be valid for writes.
+int bchaddr_to_cash(const struct BchAddrConverter *converter, const char *addr, char **out);
+
+// Convert `addr` to the cash_addr-like format `format_name` (cash_addr when NULL)
+// on `network` (unchanged when `BCHADDR_NETWORK_UNCHANGED`) and store the result in `*out`.
+// # Safety
+// Same as `bchaddr_to_cash`; `format_name` must be NULL or NUL-terminated.
+int bchaddr_to_cash_with_options(const struct BchAddrConverter *converter,
+                                 const char *addr,
+                                 const char *format_name,
+                                 int network,
+                                 char **out);
+
+// Convert `addr` to legacy format and store the result in `*out`.
+// # Safety
+// Same as `bchaddr_to_cash`.
+int bchaddr_to_legacy(const struct BchAddrConverter *converter, const char *addr, char **out);
+
+// Parse `addr` into `*out`.
+// # Safety
+// `converter` must come from `bchaddr_converter_new`; `addr` must be NUL-terminated;
+// `out` must be valid for writes.
+int bchaddr_parse(const struct BchAddrConverter *converter,
+                  const char *addr,
+                  struct BchAddrParsed *out);
+
+// Release a string returned by this library.
+// # Safety
+// `s` must be NULL or a string returned through an `out` pointer of this library that has not been freed.
+void bchaddr_free_string(char *s);
+
+// Message describing the most recent failure on the calling thread, or NULL if nothing has failed yet.
+// The string is owned by the library and stays valid until the next failing call on the same thread;
+// do not pass it to `bchaddr_free_string`.
+const char *bchaddr_last_error_message(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* BCH_ADDR_H */
@@ -0,0 +1,47 @@
+import { Converter } from "./pkg/bch_addr.js";
+import type { AddressFormat, AddressType, Network as NetworkName } from "./pkg/bch_addr.js";
+
+export { Converter };
+export type { AddressFormat, AddressType, NetworkName };
+
+export declare const Format: Readonly<{ Legacy: "legacy"; Cashaddr: "cashaddr" }>;
+export declare const Network: Readonly<{ Mainnet: "mainnet"; Testnet: "testnet"; Regtest: "regtest" }>;
+export declare const Type: Readonly<{ P2PKH: "p2pkh"; P2SH: "p2sh"; Other: "other" }>;
+
+export declare class BchAddr {
+  constructor(converter?: Converter);
+  readonly converter: Converter;
+
+  addPrefix(prefix: string, network: NetworkName, formatName: string): this;
+  addTypeBits(format: AddressFormat, typeBits: number[]): this;
+  toCashAddress(address: string, format?: AddressFormat, network?: NetworkName): string;
+  toLegacyAddress(address: string): string;
+  detectAddressFormat(address: string): AddressFormat;
+  detectAddressNetwork(address: string): NetworkName;
+  detectAddressType(address: string): AddressType;
+  detectAddressTypeBits(address: string): number;
+  isValidAddress(address: string): boolean;
+  isLegacyAddress(address: string): boolean;
+  isCashAddress(address: string): boolean;
+  isMainnetAddress(address: string): boolean;
+  isTestnetAddress(address: string): boolean;
+  isRegtestAddress(address: string): boolean;
+  isP2PKHAddress(address: string): boolean;
+  isP2SHAddress(address: string): boolean;
+  free(): void;
+}
+
+export declare function toCashAddress(address: string): string;
+export declare function toLegacyAddress(address: string): string;
+export declare function detectAddressFormat(address: string): AddressFormat;
+export declare function detectAddressNetwork(address: string): NetworkName;
+export declare function detectAddressType(address: string): AddressType;
+export declare function detectAddressTypeBits(address: string): number;
+export declare function isValidAddress(address: string): boolean;
+export declare function isLegacyAddress(address: string): boolean;
+export declare function isCashAddress(address: string): boolean;
+export declare function isMainnetAddress(address: string): boolean;
+export declare function isTestnetAddress(address: string): boolean;
+export declare function isRegtestAddress(address: string): boolean;
+export declare function isP2PKHAddress(address: string): boolean;
+export declare function isP2SHAddress(address: string): boolean;
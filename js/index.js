@@ -0,0 +1,101 @@
+// Typed, bchaddrjs-shaped wrapper around the wasm-pack output in ./pkg.
+import { Converter } from "./pkg/bch_addr.js";
+
+export { Converter };
+
+export const Format = Object.freeze({ Legacy: "legacy", Cashaddr: "cashaddr" });
+export const Network = Object.freeze({ Mainnet: "mainnet", Testnet: "testnet", Regtest: "regtest" });
+export const Type = Object.freeze({ P2PKH: "p2pkh", P2SH: "p2sh", Other: "other" });
+
+export class BchAddr {
+  constructor(converter = new Converter()) {
+    this.converter = converter;
+  }
+
+  addPrefix(prefix, network, formatName) {
+    this.converter.addPrefix(prefix, network, formatName);
+    return this;
+  }
+
+  addTypeBits(format, typeBits) {
+    this.converter.addTypeBits(format, Uint8Array.from(typeBits));
+    return this;
+  }
+
+  toCashAddress(address, format, network) {
+    return this.converter.toCashAddress(address, format, network);
+  }
+
+  toLegacyAddress(address) {
+    return this.converter.toLegacyAddress(address);
+  }
+
+  detectAddressFormat(address) {
+    return this.converter.detectAddressFormat(address);
+  }
+
+  detectAddressNetwork(address) {
+    return this.converter.detectAddressNetwork(address);
+  }
+
+  detectAddressType(address) {
+    return this.converter.detectAddressType(address);
+  }
+
+  detectAddressTypeBits(address) {
+    return this.converter.detectAddressTypeBits(address);
+  }
+
+  isValidAddress(address) {
+    return this.converter.isValidAddress(address);
+  }
+
+  isLegacyAddress(address) {
+    return this.converter.isLegacyAddress(address);
+  }
+
+  isCashAddress(address) {
+    return this.converter.isCashAddress(address);
+  }
+
+  isMainnetAddress(address) {
+    return this.converter.isMainnetAddress(address);
+  }
+
+  isTestnetAddress(address) {
+    return this.converter.isTestnetAddress(address);
+  }
+
+  isRegtestAddress(address) {
+    return this.converter.isRegtestAddress(address);
+  }
+
+  isP2PKHAddress(address) {
+    return this.converter.isP2PKHAddress(address);
+  }
+
+  isP2SHAddress(address) {
+    return this.converter.isP2SHAddress(address);
+  }
+
+  free() {
+    this.converter.free();
+  }
+}
+
+const defaultInstance = new BchAddr();
+
+export const toCashAddress = (address) => defaultInstance.toCashAddress(address);
+export const toLegacyAddress = (address) => defaultInstance.toLegacyAddress(address);
+export const detectAddressFormat = (address) => defaultInstance.detectAddressFormat(address);
+export const detectAddressNetwork = (address) => defaultInstance.detectAddressNetwork(address);
+export const detectAddressType = (address) => defaultInstance.detectAddressType(address);
+export const detectAddressTypeBits = (address) => defaultInstance.detectAddressTypeBits(address);
+export const isValidAddress = (address) => defaultInstance.isValidAddress(address);
+export const isLegacyAddress = (address) => defaultInstance.isLegacyAddress(address);
+export const isCashAddress = (address) => defaultInstance.isCashAddress(address);
+export const isMainnetAddress = (address) => defaultInstance.isMainnetAddress(address);
+export const isTestnetAddress = (address) => defaultInstance.isTestnetAddress(address);
+export const isRegtestAddress = (address) => defaultInstance.isRegtestAddress(address);
+export const isP2PKHAddress = (address) => defaultInstance.isP2PKHAddress(address);
+export const isP2SHAddress = (address) => defaultInstance.isP2SHAddress(address);
@@ -0,0 +1,116 @@
+#![warn(missing_docs)]
+#![warn(missing_debug_implementations)]
+#![warn(unused)]
+#![warn(nonstandard_style)]
+#![warn(rust_2018_idioms)]
+
+//! `address!`: Bitcoin Cash address literals validated by `bch_addr` at compile time.
+//!
+//! A typo'd hard-coded address fails the build instead of shipping.
+//! # Example
+//! ```
+//! use bch_addr_macros::address;
+//!
+//! const DONATION: &str = address!("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+//! const DONATION_LEGACY: &str = address!("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", legacy);
+//! const DONATION_CASH: &str = address!("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", cashaddr);
+//!
+//! assert_eq!(DONATION, DONATION_CASH);
+//! assert_eq!(DONATION_LEGACY, "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR");
+//! ```
+//! ```compile_fail
+//! const TYPO: &str = bch_addr_macros::address!("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwj");
+//! ```
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use bch_addr::{AddressFormat, Converter};
+
+/// Validate an address literal, optionally re-encoding it.
+///
+/// * `address!("...")` expands to the literal itself.
+/// * `address!("...", cashaddr)` expands to the canonical cash_addr form (lowercase, with prefix).
+/// * `address!("...", legacy)` expands to the legacy form.
+///
+/// Only the built-in prefixes (`bitcoincash`, `bchtest`, `bchreg`) are known.
+#[proc_macro]
+pub fn address(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(tokens)           => tokens,
+        Err((message, span)) => compile_error(&message, span),
+    }
+}
+
+/// Format requested by the second argument.
+enum Target {
+    CashAddr,
+    Legacy,
+}
+
+type Result<T> = std::result::Result<T, (String, Span)>;
+
+fn expand(input: TokenStream) -> Result<TokenStream> {
+    let mut tokens = input.into_iter();
+
+    let literal = match tokens.next() {
+        Some(TokenTree::Literal(literal)) => literal,
+        other => return Err(("expected an address string literal".to_string(), span_of(other.as_ref()))),
+    };
+    let addr = string_value(&literal)?;
+
+    let target = match (tokens.next(), tokens.next(), tokens.next()) {
+        (None, _, _) => None,
+        (Some(TokenTree::Punct(ref comma)), Some(TokenTree::Ident(ref ident)), None) if comma.as_char() == ',' => {
+            match ident.to_string().as_str() {
+                "cashaddr" => Some(Target::CashAddr),
+                "legacy"   => Some(Target::Legacy),
+                _          => return Err(("expected `cashaddr` or `legacy`".to_string(), ident.span())),
+            }
+        },
+        (other, _, _) => {
+            return Err(("expected `, cashaddr` or `, legacy` after the address".to_string(), span_of(other.as_ref())));
+        },
+    };
+
+    let converter = Converter::new();
+    let invalid = |err: bch_addr::Error| (err.to_string(), literal.span());
+    let (_, network, addr_type, hash) = converter.parse(&addr).map_err(invalid)?;
+    let output = match target {
+        None                   => addr,
+        Some(Target::CashAddr) => converter.encode(&AddressFormat::CashAddr, network, addr_type, &hash).map_err(invalid)?,
+        Some(Target::Legacy)   => converter.encode(&AddressFormat::Legacy, network, addr_type, &hash).map_err(invalid)?,
+    };
+
+    let mut output = Literal::string(&output);
+    output.set_span(literal.span());
+    Ok(TokenTree::from(output).into())
+}
+
+/// Contents of a plain string literal. Addresses never need escapes, so none are accepted.
+fn string_value(literal: &Literal) -> Result<String> {
+    let repr = literal.to_string();
+    if repr.len() < 2 || !repr.starts_with('"') || !repr.ends_with('"') || repr.contains('\\') {
+        return Err(("expected a plain string literal".to_string(), literal.span()));
+    }
+    Ok(repr[1..repr.len() - 1].to_string())
+}
+
+fn span_of(token: Option<&TokenTree>) -> Span {
+    token.map_or_else(Span::call_site, TokenTree::span)
+}
+
+/// `compile_error!(message)` pointing at `span`.
+fn compile_error(message: &str, span: Span) -> TokenStream {
+    let mut message = Literal::string(message);
+    message.set_span(span);
+    let mut bang = Punct::new('!', Spacing::Alone);
+    bang.set_span(span);
+    let mut args = Group::new(Delimiter::Parenthesis, TokenTree::from(message).into());
+    args.set_span(span);
+
+    vec![
+        TokenTree::from(Ident::new("compile_error", span)),
+        TokenTree::from(bang),
+        TokenTree::from(args),
+    ].into_iter().collect()
+}
@@ -0,0 +1,118 @@
+use std::collections::HashSet;
+
+use super::{Converter, AddressType, Network};
+use super::error::Result;
+
+/// Network, type and hash; what every spelling of an address has in common.
+type Key = (Network, AddressType, Box<[u8]>);
+
+/// Set of addresses compared by what they pay to rather than by spelling.
+///
+/// An address inserted in one format is found in any other (legacy, cash_addr with or without prefix,
+/// upper case, user-defined formats), e.g. to check outputs against the addresses of a watch-only wallet.
+/// # Example
+/// ```
+/// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+/// # {
+/// # use bch_addr::{AddressSet, Converter};
+/// let converter = Converter::new();
+/// let mut watched = AddressSet::new(&converter);
+/// watched.insert("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap();
+///
+/// assert!(watched.contains("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk"));
+/// assert!(watched.contains("QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK"));
+/// assert!(!watched.contains("bchtest:qph5kuz78czq00e3t85ugpgd7xmer5kr7csm740kf2"));
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AddressSet<'a> {
+    converter: &'a Converter,
+    keys: HashSet<Key>,
+}
+
+impl<'a> AddressSet<'a> {
+    /// Construct an empty set.
+    /// # Arguments
+    /// * `converter` - Converter providing the prefix configuration.
+    /// # Returns
+    /// * Address set.
+    pub fn new(converter: &'a Converter) -> AddressSet<'a> {
+        AddressSet {
+            converter,
+            keys: HashSet::new(),
+        }
+    }
+
+    fn key(&self, addr: &str) -> Result<Key> {
+        let (_, network, addr_type, hash) = self.converter.parse(addr)?;
+        Ok((network, addr_type, hash.into_boxed_slice()))
+    }
+
+    /// Add an address.
+    /// # Arguments
+    /// * `addr` - Address in any format.
+    /// # Returns
+    /// * `true` if no spelling of the address was in the set.
+    pub fn insert(&mut self, addr: &str) -> Result<bool> {
+        let key = self.key(addr)?;
+        Ok(self.keys.insert(key))
+    }
+
+    /// Check for an address in any spelling.
+    /// # Arguments
+    /// * `addr` - Address in any format.
+    /// # Returns
+    /// * `true` if the address is in the set, `false` if not or invalid.
+    pub fn contains(&self, addr: &str) -> bool {
+        self.key(addr).is_ok_and(|key| self.keys.contains(&key))
+    }
+
+    /// Remove an address in any spelling.
+    /// # Arguments
+    /// * `addr` - Address in any format.
+    /// # Returns
+    /// * `true` if the address was in the set.
+    pub fn remove(&mut self, addr: &str) -> bool {
+        match self.key(addr) {
+            Ok(key) => self.keys.remove(&key),
+            Err(_)  => false,
+        }
+    }
+
+    /// Number of distinct addresses.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// `true` if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+#[cfg(all(test, feature = "legacy", feature = "cashaddr"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn address_set_test() {
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+        let mut set = AddressSet::new(&converter);
+
+        assert!(set.insert("simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg").unwrap());
+        assert!(!set.insert("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap());
+        assert!(set.insert("bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu").unwrap());
+        assert!(set.insert("bitcoincash:bad").is_err());
+        assert_eq!(set.len(), 2);
+
+        assert!(set.contains("qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk"));
+        assert!(set.contains("2N44ThNe8NXHyv4bsX8AoVCXquBRW94Ls7W"));
+        assert!(!set.contains("3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9"));
+        assert!(!set.contains("bitcoincash:bad"));
+
+        assert!(set.remove("BITCOINCASH:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK"));
+        assert!(!set.remove("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR"));
+        assert_eq!(set.len(), 1);
+        assert!(!set.is_empty());
+    }
+}
@@ -0,0 +1,203 @@
+//! Address book import and export, e.g. for wallet migrations.
+//!
+//! Reads `(label, address)` lists from CSV (with a `label,address` header) or JSON (an array of
+//! `{"label": ..., "address": ...}` objects), normalizes every address to one format and writes the list back.
+//! # Example
+//! ```
+//! # use bch_addr::{Converter, AddressFormat};
+//! use bch_addr::addressbook;
+//!
+//! let input = "label,address\nalice,1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR\nbob,nope\n";
+//! let entries = addressbook::read_csv(input.as_bytes()).unwrap();
+//! let report = addressbook::normalize(&Converter::new(), entries, &AddressFormat::CashAddr);
+//! assert_eq!(report.errors.len(), 1);
+//! assert_eq!(report.errors[0].row, 2);
+//!
+//! let mut output = Vec::new();
+//! addressbook::write_json(&mut output, &report.entries).unwrap();
+//! assert_eq!(String::from_utf8(output).unwrap(), concat!(
+//!     r#"[{"label":"alice","address":"bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk"},"#,
+//!     r#"{"label":"bob","address":"nope"}]"#,
+//! ));
+//! ```
+
+// `failure_derive` expands its impls inside an anonymous const.
+#![allow(non_local_definitions)]
+
+use std::io::{Read, Write};
+use std::result;
+
+use failure::Fail;
+use serde::{Deserialize, Serialize};
+
+use super::{Converter, ParsedAddress, AddressFormat};
+
+/// Alias of `Result` used by the readers and writers.
+pub type Result<T> = result::Result<T, Error>;
+
+/// Errors reading or writing an address book.
+#[derive(Debug, Fail)]
+pub enum Error {
+    /// CSV error, including I/O errors of CSV files.
+    /// # Arguments
+    /// * Error.
+    #[fail(display = "csv error: {}", 0)]
+    Csv(csv::Error),
+
+    /// JSON error, including I/O errors of JSON files.
+    /// # Arguments
+    /// * Error.
+    #[fail(display = "json error: {}", 0)]
+    Json(serde_json::Error),
+}
+
+impl From<csv::Error> for Error {
+    fn from(err: csv::Error) -> Error {
+        Error::Csv(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Error {
+        Error::Json(err)
+    }
+}
+
+/// Address book entry.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Entry {
+    /// Label.
+    pub label: String,
+    /// Address.
+    pub address: String,
+}
+
+/// Entry whose address could not be normalized.
+#[derive(Debug)]
+pub struct RowError {
+    /// Position of the entry, starting at 1 (the row after the CSV header).
+    pub row: usize,
+    /// Label of the entry.
+    pub label: String,
+    /// Reason.
+    pub error: super::Error,
+}
+
+/// Result of `normalize`.
+#[derive(Debug)]
+pub struct Report {
+    /// All entries in input order; normalized, or as given if they failed.
+    pub entries: Vec<Entry>,
+    /// Failed entries, in input order.
+    pub errors: Vec<RowError>,
+}
+
+/// Normalize the address of each entry to `format`, keeping its network.
+/// Addresses are rewritten even when already in `format`, so the result is lowercase and prefixed.
+/// # Arguments
+/// * `converter` - Converter providing the prefix configuration.
+/// * `entries` - Entries with addresses in any format.
+/// * `format` - Target format.
+/// # Returns
+/// * Entries and per-row errors.
+pub fn normalize(converter: &Converter, entries: Vec<Entry>, format: &AddressFormat) -> Report {
+    let mut parsed = ParsedAddress::default();
+    let mut errors = Vec::new();
+
+    let entries = entries.into_iter().enumerate()
+        .map(|(i, mut entry)| {
+            let normalized = converter.parse_into(&entry.address, &mut parsed)
+                .and_then(|()| converter.encode(format, parsed.network, parsed.addr_type, &parsed.hash));
+            match normalized {
+                Ok(address) => entry.address = address,
+                Err(error)  => errors.push(RowError { row: i + 1, label: entry.label.clone(), error }),
+            }
+            entry
+        })
+        .collect();
+
+    Report { entries, errors }
+}
+
+/// Read entries from CSV with `label` and `address` columns; other columns are ignored.
+/// # Arguments
+/// * `reader` - CSV input.
+/// # Returns
+/// * Entries.
+pub fn read_csv<R: Read>(reader: R) -> Result<Vec<Entry>> {
+    let mut reader = csv::Reader::from_reader(reader);
+    let entries = reader.deserialize().collect::<result::Result<_, _>>()?;
+    Ok(entries)
+}
+
+/// Write entries as CSV with a `label,address` header.
+/// # Arguments
+/// * `writer` - CSV output.
+/// * `entries` - Entries.
+pub fn write_csv<W: Write>(writer: W, entries: &[Entry]) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    for entry in entries {
+        writer.serialize(entry)?;
+    }
+    writer.flush().map_err(csv::Error::from)?;
+    Ok(())
+}
+
+/// Read entries from a JSON array of objects with `label` and `address` keys.
+/// # Arguments
+/// * `reader` - JSON input.
+/// # Returns
+/// * Entries.
+pub fn read_json<R: Read>(reader: R) -> Result<Vec<Entry>> {
+    Ok(serde_json::from_reader(reader)?)
+}
+
+/// Write entries as a JSON array.
+/// # Arguments
+/// * `writer` - JSON output.
+/// * `entries` - Entries.
+pub fn write_json<W: Write>(writer: W, entries: &[Entry]) -> Result<()> {
+    Ok(serde_json::to_writer(writer, entries)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Network;
+
+    #[test]
+    fn addressbook_test() {
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+        let input = "label,address,note\n\
+                     alice,QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK,x\n\
+                     \"bob, jr\",mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi,\n\
+                     carol,bitcoincash:bad,\n";
+
+        let entries = read_csv(input.as_bytes()).unwrap();
+        assert_eq!(entries[1], Entry { label: "bob, jr".to_string(), address: "mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi".to_string() });
+
+        let report = normalize(&converter, entries.clone(), &AddressFormat::CashAddr);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!((report.errors[0].row, report.errors[0].label.as_str()), (3, "carol"));
+
+        let mut output = Vec::new();
+        write_csv(&mut output, &report.entries).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "label,address\n\
+            alice,bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk\n\
+            \"bob, jr\",bchtest:qph5kuz78czq00e3t85ugpgd7xmer5kr7csm740kf2\n\
+            carol,bitcoincash:bad\n");
+
+        let report = normalize(&converter, entries, &AddressFormat::Other("SLPAddr".to_string()));
+        let rows: Vec<_> = report.errors.iter().map(|err| err.row).collect();
+        assert_eq!(rows, [2, 3]);
+
+        let mut output = Vec::new();
+        write_json(&mut output, &report.entries).unwrap();
+        let entries = read_json(&output[..]).unwrap();
+        assert_eq!(entries, report.entries);
+        assert_eq!(entries[0].address, "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg");
+
+        assert!(matches!(read_csv("name,address\nalice,x\n".as_bytes()), Err(Error::Csv(_))));
+        assert!(matches!(read_json("{}".as_bytes()), Err(Error::Json(_))));
+    }
+}
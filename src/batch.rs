@@ -0,0 +1,575 @@
+//! Helpers for lists of addresses of mixed formats, e.g. from intake pipelines.
+//!
+//! Each function parses every address once, reusing one hash buffer, and keeps the inputs as given.
+
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+
+use super::{Converter, ParsedAddress, AddressFormat, AddressType, Network};
+
+/// Addresses split by validity.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Partition<T> {
+    /// Valid addresses, in input order.
+    pub valid: Vec<T>,
+    /// Invalid addresses, in input order.
+    pub invalid: Vec<T>,
+}
+
+/// Valid addresses grouped by a detected property, and the invalid ones.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Groups<K: Eq + Hash, T> {
+    /// Valid addresses by key, each group in input order.
+    pub groups: HashMap<K, Vec<T>>,
+    /// Invalid addresses, in input order.
+    pub invalid: Vec<T>,
+}
+
+/// Split addresses into valid and invalid ones.
+/// # Arguments
+/// * `converter` - Converter providing the prefix configuration.
+/// * `addrs` - Addresses in any format.
+/// # Returns
+/// * Valid and invalid addresses.
+/// # Example
+/// ```
+/// # #[cfg(feature = "legacy")]
+/// # {
+/// # use bch_addr::Converter;
+/// use bch_addr::batch::partition_valid_invalid;
+///
+/// let partition = partition_valid_invalid(&Converter::new(), vec!["1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "nope"]);
+/// assert_eq!(partition.valid, ["1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR"]);
+/// assert_eq!(partition.invalid, ["nope"]);
+/// # }
+/// ```
+pub fn partition_valid_invalid<I, T>(converter: &Converter, addrs: I) -> Partition<T>
+    where I: IntoIterator<Item = T>, T: AsRef<str>
+{
+    let Groups { mut groups, invalid } = group_by(converter, addrs, |_| ());
+    Partition {
+        valid: groups.remove(&()).unwrap_or_default(),
+        invalid,
+    }
+}
+
+/// Group addresses by network.
+/// # Arguments
+/// * `converter` - Converter providing the prefix configuration.
+/// * `addrs` - Addresses in any format.
+/// # Returns
+/// * Addresses by network, and the invalid ones.
+/// # Example
+/// ```
+/// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+/// # {
+/// # use bch_addr::{Converter, Network};
+/// use bch_addr::batch::group_by_network;
+///
+/// let groups = group_by_network(&Converter::new(), vec![
+///     "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR",
+///     "bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu",
+///     "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk",
+/// ]);
+/// assert_eq!(groups.groups[&Network::Mainnet].len(), 2);
+/// assert_eq!(groups.groups[&Network::Testnet].len(), 1);
+/// # }
+/// ```
+pub fn group_by_network<I, T>(converter: &Converter, addrs: I) -> Groups<Network, T>
+    where I: IntoIterator<Item = T>, T: AsRef<str>
+{
+    group_by(converter, addrs, |parsed| parsed.network)
+}
+
+/// Group addresses by format.
+/// # Arguments
+/// * `converter` - Converter providing the prefix configuration.
+/// * `addrs` - Addresses in any format.
+/// # Returns
+/// * Addresses by format, and the invalid ones.
+pub fn group_by_format<I, T>(converter: &Converter, addrs: I) -> Groups<AddressFormat, T>
+    where I: IntoIterator<Item = T>, T: AsRef<str>
+{
+    group_by(converter, addrs, |parsed| parsed.format.clone())
+}
+
+/// Group addresses by type.
+/// # Arguments
+/// * `converter` - Converter providing the prefix configuration.
+/// * `addrs` - Addresses in any format.
+/// # Returns
+/// * Addresses by type, and the invalid ones.
+pub fn group_by_type<I, T>(converter: &Converter, addrs: I) -> Groups<AddressType, T>
+    where I: IntoIterator<Item = T>, T: AsRef<str>
+{
+    group_by(converter, addrs, |parsed| parsed.addr_type)
+}
+
+/// Group addresses by any key computed from the parsed address.
+/// # Arguments
+/// * `converter` - Converter providing the prefix configuration.
+/// * `addrs` - Addresses in any format.
+/// * `key` - Group key of a parsed address.
+/// # Returns
+/// * Addresses by key, and the invalid ones.
+/// # Example
+/// ```
+/// # #[cfg(feature = "legacy")]
+/// # {
+/// # use bch_addr::Converter;
+/// use bch_addr::batch::group_by;
+///
+/// let groups = group_by(&Converter::new(), vec!["1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9"], |parsed| {
+///     (parsed.format.clone(), parsed.network, parsed.addr_type)
+/// });
+/// assert_eq!(groups.groups.len(), 2);
+/// # }
+/// ```
+pub fn group_by<I, T, K, F>(converter: &Converter, addrs: I, mut key: F) -> Groups<K, T>
+    where I: IntoIterator<Item = T>, T: AsRef<str>, K: Eq + Hash, F: FnMut(&ParsedAddress) -> K
+{
+    let mut parsed = ParsedAddress::default();
+    let mut groups = Groups {
+        groups: HashMap::new(),
+        invalid: Vec::new(),
+    };
+
+    for addr in addrs {
+        match converter.parse_into(addr.as_ref(), &mut parsed) {
+            Ok(()) => groups.groups.entry(key(&parsed)).or_insert_with(Vec::new).push(addr),
+            Err(_) => groups.invalid.push(addr),
+        }
+    }
+    groups
+}
+
+/// Counts over a list of addresses, returned by `summarize`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    /// Number of entries.
+    pub total: usize,
+    /// Valid addresses by format.
+    pub formats: HashMap<AddressFormat, usize>,
+    /// Valid addresses by network.
+    pub networks: HashMap<Network, usize>,
+    /// Valid addresses by type.
+    pub types: HashMap<AddressType, usize>,
+    /// Positions of the invalid entries, in input order.
+    pub invalid: Vec<usize>,
+}
+
+impl Summary {
+    /// Number of valid addresses.
+    pub fn valid(&self) -> usize {
+        self.total - self.invalid.len()
+    }
+}
+
+/// Count addresses per format, network and type in one pass, e.g. over a deposit export.
+/// # Arguments
+/// * `converter` - Converter providing the prefix configuration.
+/// * `addrs` - Addresses in any format.
+/// # Returns
+/// * Counts, and the positions of the invalid entries.
+/// # Example
+/// ```
+/// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+/// # {
+/// # use bch_addr::{Converter, AddressFormat, Network};
+/// use bch_addr::batch::summarize;
+///
+/// let summary = summarize(&Converter::new(), &[
+///     "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR",
+///     "nope",
+///     "bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu",
+/// ]);
+/// assert_eq!(summary.valid(), 2);
+/// assert_eq!(summary.formats[&AddressFormat::Legacy], 1);
+/// assert_eq!(summary.networks[&Network::Testnet], 1);
+/// assert_eq!(summary.invalid, [1]);
+/// # }
+/// ```
+pub fn summarize<I, T>(converter: &Converter, addrs: I) -> Summary
+    where I: IntoIterator<Item = T>, T: AsRef<str>
+{
+    let mut parsed = ParsedAddress::default();
+    let mut summary = Summary::default();
+
+    for (index, addr) in addrs.into_iter().enumerate() {
+        summary.total += 1;
+        if converter.parse_into(addr.as_ref(), &mut parsed).is_err() {
+            summary.invalid.push(index);
+            continue;
+        }
+
+        // look up first so the format is only cloned once per format
+        match summary.formats.get_mut(&parsed.format) {
+            Some(count) => *count += 1,
+            None        => { summary.formats.insert(parsed.format.clone(), 1); },
+        }
+        *summary.networks.entry(parsed.network).or_insert(0) += 1;
+        *summary.types.entry(parsed.addr_type).or_insert(0) += 1;
+    }
+    summary
+}
+
+/// Differences between two lists of addresses, returned by `diff_lists`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ListDiff<T> {
+    /// Entries of `b` for addresses missing from `a`, in input order.
+    pub added: Vec<T>,
+    /// Entries of `a` for addresses missing from `b`, in input order.
+    pub removed: Vec<T>,
+    /// Entries of `a` and `b` for the same address spelled differently, e.g. legacy and cash_addr,
+    /// in the order of `a`.
+    pub changed: Vec<(T, T)>,
+    /// Number of entries of `a` spelled the same in `b`.
+    pub unchanged: usize,
+    /// Invalid entries of `a`, in input order.
+    pub invalid_a: Vec<T>,
+    /// Invalid entries of `b`, in input order.
+    pub invalid_b: Vec<T>,
+}
+
+/// Compare two lists of addresses by what they pay to, e.g. the exports of an old and a new system.
+/// Each entry of `a` is compared with the first entry of `b` for the same address.
+/// # Arguments
+/// * `converter` - Converter providing the prefix configuration.
+/// * `a` - Old list, addresses in any format.
+/// * `b` - New list, addresses in any format.
+/// # Returns
+/// * Added, removed and respelled addresses, and the invalid entries of both lists.
+/// # Example
+/// ```
+/// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+/// # {
+/// # use bch_addr::Converter;
+/// use bch_addr::batch::diff_lists;
+///
+/// let diff = diff_lists(&Converter::new(),
+///     vec!["1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9"],
+///     vec!["bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", "bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu"],
+/// );
+/// assert_eq!(diff.changed, [("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk")]);
+/// assert_eq!(diff.removed, ["3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9"]);
+/// assert_eq!(diff.added, ["bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu"]);
+/// # }
+/// ```
+pub fn diff_lists<I, J, T>(converter: &Converter, a: I, b: J) -> ListDiff<T>
+    where I: IntoIterator<Item = T>, J: IntoIterator<Item = T>, T: AsRef<str> + Clone
+{
+    let mut parsed = ParsedAddress::default();
+    let a = keyed(converter, a, &mut parsed);
+    let b = keyed(converter, b, &mut parsed);
+
+    let mut first_in_b = HashMap::new();
+    for (key, addr) in &b {
+        if let Some(key) = key {
+            first_in_b.entry(key).or_insert(addr);
+        }
+    }
+    let in_a: HashSet<_> = a.iter().filter_map(|(key, _)| key.as_ref()).collect();
+
+    let mut diff = ListDiff {
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+        unchanged: 0,
+        invalid_a: Vec::new(),
+        invalid_b: Vec::new(),
+    };
+    for (key, addr) in &a {
+        match key.as_ref().map(|key| first_in_b.get(key)) {
+            None                                                 => diff.invalid_a.push(addr.clone()),
+            Some(None)                                           => diff.removed.push(addr.clone()),
+            Some(Some(other)) if other.as_ref() == addr.as_ref() => diff.unchanged += 1,
+            Some(Some(other))                                    => diff.changed.push((addr.clone(), (*other).clone())),
+        }
+    }
+    for (key, addr) in &b {
+        match key {
+            None                             => diff.invalid_b.push(addr.clone()),
+            Some(key) if !in_a.contains(key) => diff.added.push(addr.clone()),
+            Some(_)                          => {},
+        }
+    }
+    diff
+}
+
+/// Network, type and hash; what every spelling of an address has in common.
+type Key = (Network, AddressType, Vec<u8>);
+
+/// Addresses with their keys, `None` for invalid ones.
+fn keyed<I, T>(converter: &Converter, addrs: I, parsed: &mut ParsedAddress) -> Vec<(Option<Key>, T)>
+    where I: IntoIterator<Item = T>, T: AsRef<str>
+{
+    addrs.into_iter()
+        .map(|addr| match converter.parse_into(addr.as_ref(), parsed) {
+            Ok(()) => (Some((parsed.network, parsed.addr_type, parsed.hash.clone())), addr),
+            Err(_) => (None, addr),
+        })
+        .collect()
+}
+
+/// What `normalize_batch` accepts and how it writes accepted addresses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Policy {
+    /// Format of the accepted addresses. `AddressFormat::CashAddr` by default.
+    pub format: AddressFormat,
+    /// Network accepted addresses must be on; any network if `None`, the default.
+    pub network: Option<Network>,
+    /// Type accepted addresses must have; any type if `None`, the default.
+    pub addr_type: Option<AddressType>,
+    /// Whether legacy inputs are accepted. `true` by default.
+    pub accept_legacy: bool,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Policy {
+            format: AddressFormat::CashAddr,
+            network: None,
+            addr_type: None,
+            accept_legacy: true,
+        }
+    }
+}
+
+/// Reason `normalize_batch` rejected an input.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RejectReason {
+    /// Not an address.
+    Invalid,
+    /// Legacy address while the policy does not accept them.
+    Legacy,
+    /// Address on another network than the policy's.
+    WrongNetwork(Network),
+    /// Address of another type than the policy's.
+    WrongType(AddressType),
+    /// Address the policy's format has no encoding for, e.g. no prefix for its network.
+    Unencodable,
+    /// Same address as the input at this index, in any spelling.
+    Duplicate(usize),
+}
+
+/// Input rejected by `normalize_batch`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rejection<T> {
+    /// Position of the input.
+    pub index: usize,
+    /// Input as given.
+    pub input: T,
+    /// Reason of the rejection.
+    pub reason: RejectReason,
+}
+
+/// Result of `normalize_batch`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Normalized<T> {
+    /// Accepted addresses in the policy's format, in input order, without duplicates.
+    pub accepted: Vec<String>,
+    /// Rejected inputs, in input order.
+    pub rejected: Vec<Rejection<T>>,
+}
+
+/// Convert addresses to one format, drop duplicates and check them against a policy,
+/// e.g. for deposit address reconciliation.
+/// # Arguments
+/// * `converter` - Converter providing the prefix configuration.
+/// * `addrs` - Addresses in any format.
+/// * `policy` - Acceptance policy.
+/// # Returns
+/// * Accepted addresses, and the rejected inputs with reasons.
+/// # Example
+/// ```
+/// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+/// # {
+/// # use bch_addr::{Converter, Network};
+/// use bch_addr::batch::{normalize_batch, Policy, RejectReason};
+///
+/// let policy = Policy { network: Some(Network::Mainnet), ..Policy::default() };
+/// let normalized = normalize_batch(&Converter::new(), vec![
+///     "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR",
+///     "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk",
+///     "bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu",
+/// ], &policy);
+/// assert_eq!(normalized.accepted, ["bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk"]);
+/// assert_eq!(normalized.rejected[0].reason, RejectReason::Duplicate(0));
+/// assert_eq!(normalized.rejected[1].reason, RejectReason::WrongNetwork(Network::Testnet));
+/// # }
+/// ```
+pub fn normalize_batch<I, T>(converter: &Converter, addrs: I, policy: &Policy) -> Normalized<T>
+    where I: IntoIterator<Item = T>, T: AsRef<str>
+{
+    let mut parsed = ParsedAddress::default();
+    let mut first_index = HashMap::new();
+    let mut normalized = Normalized {
+        accepted: Vec::new(),
+        rejected: Vec::new(),
+    };
+
+    for (index, input) in addrs.into_iter().enumerate() {
+        let reason = match check(converter, input.as_ref(), policy, &mut parsed) {
+            Ok(canonical) => match first_index.entry(canonical) {
+                Entry::Occupied(first) => RejectReason::Duplicate(*first.get()),
+                Entry::Vacant(entry)   => {
+                    normalized.accepted.push(entry.key().clone());
+                    entry.insert(index);
+                    continue;
+                },
+            },
+            Err(reason) => reason,
+        };
+        normalized.rejected.push(Rejection { index, input, reason });
+    }
+    normalized
+}
+
+/// Address in the policy's format, if accepted.
+fn check(converter: &Converter, addr: &str, policy: &Policy, parsed: &mut ParsedAddress) -> Result<String, RejectReason> {
+    converter.parse_into(addr, parsed).map_err(|_| RejectReason::Invalid)?;
+
+    if parsed.format == AddressFormat::Legacy && !policy.accept_legacy {
+        return Err(RejectReason::Legacy);
+    }
+    if policy.network.is_some_and(|network| network != parsed.network) {
+        return Err(RejectReason::WrongNetwork(parsed.network));
+    }
+    if policy.addr_type.is_some_and(|addr_type| addr_type != parsed.addr_type) {
+        return Err(RejectReason::WrongType(parsed.addr_type));
+    }
+    converter.encode(&policy.format, parsed.network, parsed.addr_type, &parsed.hash).map_err(|_| RejectReason::Unencodable)
+}
+
+#[cfg(all(test, feature = "legacy", feature = "cashaddr"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_test() {
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+        let addrs = vec![
+            "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR".to_string(),
+            "bitcoincash:bad".to_string(),
+            "bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu".to_string(),
+            "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg".to_string(),
+            "3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9".to_string(),
+            "".to_string(),
+        ];
+
+        let partition = partition_valid_invalid(&converter, &addrs);
+        assert_eq!(partition.valid, [&addrs[0], &addrs[2], &addrs[3], &addrs[4]]);
+        assert_eq!(partition.invalid, [&addrs[1], &addrs[5]]);
+
+        let groups = group_by_format(&converter, addrs.iter().map(String::as_str));
+        assert_eq!(groups.groups[&AddressFormat::Legacy], [&addrs[0], &addrs[4]]);
+        assert_eq!(groups.groups[&AddressFormat::CashAddr], [&addrs[2]]);
+        assert_eq!(groups.groups[&AddressFormat::Other("SLPAddr".to_string())], [&addrs[3]]);
+        assert_eq!(groups.invalid, ["bitcoincash:bad", ""]);
+
+        let groups = group_by_type(&converter, addrs.clone());
+        assert_eq!(groups.groups[&AddressType::P2PKH], [addrs[0].clone(), addrs[3].clone()]);
+        assert_eq!(groups.groups[&AddressType::P2SH], [addrs[2].clone(), addrs[4].clone()]);
+
+        let groups = group_by_network(&converter, Vec::<String>::new());
+        assert!(groups.groups.is_empty() && groups.invalid.is_empty());
+    }
+
+    #[test]
+    fn summarize_test() {
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+        let summary = summarize(&converter, vec![
+            "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR",
+            "bitcoincash:bad",
+            "bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu",
+            "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg",
+            "3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9",
+            "",
+        ]);
+
+        assert_eq!((summary.total, summary.valid()), (6, 4));
+        assert_eq!(summary.formats, [
+            (AddressFormat::Legacy, 2),
+            (AddressFormat::CashAddr, 1),
+            (AddressFormat::Other("SLPAddr".to_string()), 1),
+        ].iter().cloned().collect::<HashMap<_, _>>());
+        assert_eq!(summary.networks, [(Network::Mainnet, 3), (Network::Testnet, 1)].iter().cloned().collect::<HashMap<_, _>>());
+        assert_eq!(summary.types, [(AddressType::P2PKH, 2), (AddressType::P2SH, 2)].iter().cloned().collect::<HashMap<_, _>>());
+        assert_eq!(summary.invalid, [1, 5]);
+
+        assert_eq!(summarize(&converter, Vec::<String>::new()), Summary::default());
+    }
+
+    #[test]
+    fn diff_lists_test() {
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+        let a = vec![
+            "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR".to_string(),
+            "bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu".to_string(),
+            "nope".to_string(),
+            "3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9".to_string(),
+        ];
+        let b = vec![
+            "bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu".to_string(),
+            "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg".to_string(),
+            "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR".to_string(),
+            "mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi".to_string(),
+            "".to_string(),
+        ];
+
+        let diff = diff_lists(&converter, &a, &b);
+        assert_eq!(diff.changed, [(&a[0], &b[1])]);
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!(diff.removed, [&a[3]]);
+        assert_eq!(diff.added, [&b[3]]);
+        assert_eq!((diff.invalid_a, diff.invalid_b), (vec![&a[2]], vec![&b[4]]));
+
+        let diff = diff_lists(&converter, &a, &a);
+        assert_eq!(diff.unchanged, 3);
+        assert!(diff.changed.is_empty() && diff.added.is_empty() && diff.removed.is_empty());
+    }
+
+    #[test]
+    fn normalize_batch_test() {
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+        let addrs = [
+            "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg",
+            "bitcoincash:bad",
+            "bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu",
+            "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR",
+            "3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9",
+            "QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK",
+        ];
+
+        let normalized = normalize_batch(&converter, &addrs, &Policy::default());
+        assert_eq!(normalized.accepted, [
+            "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk",
+            "bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu",
+            "bitcoincash:pph5kuz78czq00e3t85ugpgd7xmer5kr7crv8a2z4t",
+        ]);
+        let reasons: Vec<_> = normalized.rejected.iter().map(|r| (r.index, *r.input, r.reason.clone())).collect();
+        assert_eq!(reasons, [
+            (1, addrs[1], RejectReason::Invalid),
+            (3, addrs[3], RejectReason::Duplicate(0)),
+            (5, addrs[5], RejectReason::Duplicate(0)),
+        ]);
+
+        let policy = Policy {
+            format: AddressFormat::Other("SLPAddr".to_string()),
+            addr_type: Some(AddressType::P2PKH),
+            accept_legacy: false,
+            ..Policy::default()
+        };
+        let normalized = normalize_batch(&converter, addrs.iter().copied(), &policy);
+        assert_eq!(normalized.accepted, ["simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg"]);
+        let reasons: Vec<_> = normalized.rejected.into_iter().map(|r| r.reason).collect();
+        assert_eq!(reasons, [
+            RejectReason::Invalid,
+            RejectReason::WrongType(AddressType::P2SH),
+            RejectReason::Legacy,
+            RejectReason::Legacy,
+            RejectReason::Duplicate(0),
+        ]);
+    }
+}
@@ -0,0 +1,141 @@
+//! Argument types shared by the subcommands.
+
+use std::str::FromStr;
+
+use bch_addr::{AddressFormat, Network};
+
+/// `--network` value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum NetworkArg {
+    Mainnet,
+    Testnet,
+    Regtest,
+}
+
+impl From<NetworkArg> for Network {
+    fn from(network: NetworkArg) -> Network {
+        match network {
+            NetworkArg::Mainnet => Network::Mainnet,
+            NetworkArg::Testnet => Network::Testnet,
+            NetworkArg::Regtest => Network::Regtest,
+        }
+    }
+}
+
+/// `--to` value: `legacy`, `cashaddr` or the name of a format registered with `--prefix`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormatArg(pub AddressFormat);
+
+impl FromStr for FormatArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(FormatArg(match s {
+            "legacy"   => AddressFormat::Legacy,
+            "cashaddr" => AddressFormat::CashAddr,
+            name       => AddressFormat::Other(name.to_string()),
+        }))
+    }
+}
+
+/// Hex-encoded bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HexArg(pub Vec<u8>);
+
+impl FromStr for HexArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim_start_matches("0x");
+        if !s.is_ascii() {
+            return Err(format!("invalid hex: `{}`", s));
+        }
+        if !s.len().is_multiple_of(2) {
+            return Err("odd number of hex digits".to_string());
+        }
+
+        (0..s.len()).step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).map_err(|_| format!("invalid hex: `{}`", &s[i..i + 2])))
+            .collect::<Result<_, _>>()
+            .map(HexArg)
+    }
+}
+
+/// `--prefix` value: `PREFIX:NETWORK:FORMAT`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrefixArg {
+    pub prefix: String,
+    pub network: Network,
+    pub format: String,
+}
+
+impl FromStr for PrefixArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut pieces = s.split(':');
+        match (pieces.next(), pieces.next(), pieces.next(), pieces.next()) {
+            (Some(prefix), Some(network), Some(format), None) if !prefix.is_empty() && !format.is_empty() => {
+                let network = <NetworkArg as clap::ValueEnum>::from_str(network, true)?;
+                Ok(PrefixArg { prefix: prefix.to_string(), network: network.into(), format: format.to_string() })
+            },
+            _ => Err(format!("expected PREFIX:NETWORK:FORMAT, got `{}`", s)),
+        }
+    }
+}
+
+/// `--type-bits` value: `FORMAT:BITS`, with a format as in `--to`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeBitsArg {
+    pub format: AddressFormat,
+    pub type_bits: u8,
+}
+
+impl FromStr for TypeBitsArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.rsplit_once(':') {
+            Some((format, bits)) if !format.is_empty() => {
+                let type_bits = bits.parse().ok().filter(|bits| (2..=15).contains(bits))
+                    .ok_or_else(|| format!("type bits must be 2 to 15, got `{}`", bits))?;
+                Ok(TypeBitsArg { format: format.parse::<FormatArg>()?.0, type_bits })
+            },
+            _ => Err(format!("expected FORMAT:BITS, got `{}`", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_arg_test() {
+        let prefix: PrefixArg = "simpleledger:mainnet:SLPAddr".parse().unwrap();
+        assert_eq!(prefix, PrefixArg { prefix: "simpleledger".to_string(), network: Network::Mainnet, format: "SLPAddr".to_string() });
+
+        assert!("simpleledger:mainnet".parse::<PrefixArg>().is_err());
+        assert!("simpleledger:livenet:SLPAddr".parse::<PrefixArg>().is_err());
+        assert_eq!("legacy".parse::<FormatArg>().unwrap(), FormatArg(AddressFormat::Legacy));
+    }
+
+    #[test]
+    fn type_bits_arg_test() {
+        assert_eq!("cashaddr:2".parse::<TypeBitsArg>().unwrap(), TypeBitsArg { format: AddressFormat::CashAddr, type_bits: 2 });
+        assert_eq!("SLPAddr:15".parse::<TypeBitsArg>().unwrap().format, AddressFormat::Other("SLPAddr".to_string()));
+        assert!("cashaddr:1".parse::<TypeBitsArg>().is_err());
+        assert!("cashaddr:16".parse::<TypeBitsArg>().is_err());
+        assert!(":2".parse::<TypeBitsArg>().is_err());
+    }
+
+    #[test]
+    fn hex_arg_test() {
+        assert_eq!("0x02ff".parse::<HexArg>().unwrap(), HexArg(vec![0x02, 0xff]));
+        assert_eq!("".parse::<HexArg>().unwrap(), HexArg(vec![]));
+        assert!("abc".parse::<HexArg>().is_err());
+        assert!("zz".parse::<HexArg>().is_err());
+        assert!("é1".parse::<HexArg>().is_err());
+        assert!("aé1".parse::<HexArg>().is_err());
+    }
+}
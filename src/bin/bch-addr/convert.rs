@@ -0,0 +1,302 @@
+//! `convert` subcommand.
+
+use std::fs::File;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use bch_addr::{Converter, AddressFormat, Network, MAX_ADDR_LEN};
+
+use crate::args::{FormatArg, NetworkArg};
+use crate::error::{CliError, Result};
+use crate::output::{self, OutputFormat, Record};
+
+#[derive(Debug, clap::Args)]
+pub struct ConvertArgs {
+    /// Address to convert.
+    #[arg(required_unless_present_any = ["stdin", "csv"], conflicts_with_all = ["stdin", "csv"])]
+    addr: Option<String>,
+
+    /// Convert one address per line read from stdin.
+    #[arg(long, conflicts_with = "csv")]
+    stdin: bool,
+
+    /// Convert a column of a CSV file, keeping the other columns as they are.
+    /// `--output tsv` writes the file tab-separated and `--output json` writes one object per record.
+    #[arg(long, value_name = "FILE")]
+    csv: Option<PathBuf>,
+
+    /// CSV column holding the addresses.
+    #[arg(long, value_name = "NAME", default_value = "address")]
+    column: String,
+
+    /// Output file for `--csv`. Defaults to stdout.
+    #[arg(long, value_name = "FILE", requires = "csv")]
+    out: Option<PathBuf>,
+
+    /// Target format: `cashaddr`, `legacy` or a format registered with `--prefix`.
+    #[arg(long, value_name = "FORMAT", default_value = "cashaddr")]
+    to: FormatArg,
+
+    /// Target network. Defaults to the network of the input address.
+    #[arg(long, value_enum)]
+    network: Option<NetworkArg>,
+}
+
+pub fn run(converter: &Converter, args: &ConvertArgs, format: OutputFormat) -> Result<()> {
+    let target = Target { format: &args.to.0, network: args.network.map(Network::from) };
+
+    if args.stdin {
+        let stdin = io::stdin();
+        let stdout = io::stdout();
+        return convert_lines(converter, &target, format, stdin.lock(), io::BufWriter::new(stdout.lock()), io::stderr());
+    }
+
+    if let Some(path) = &args.csv {
+        let input = File::open(path)?;
+        return match &args.out {
+            Some(out) => convert_csv(converter, &target, &args.column, format, input, File::create(out)?, io::stderr()),
+            None      => convert_csv(converter, &target, &args.column, format, input, io::stdout(), io::stderr()),
+        };
+    }
+
+    let addr = args.addr.as_ref().expect("clap requires an address without --stdin or --csv");
+    let converted = Converted { input: addr, output: convert(converter, addr, target.format, target.network)? };
+    output::print_record(format, &converted)?;
+    Ok(())
+}
+
+/// A converted address. TSV columns: input, output.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Converted<'a> {
+    input: &'a str,
+    output: String,
+}
+
+impl Record for Converted<'_> {
+    fn write_text<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        writeln!(output, "{}", self.output)
+    }
+
+    fn tsv_fields(&self) -> Vec<String> {
+        vec![self.input.to_string(), self.output.clone()]
+    }
+}
+
+/// Conversion target shared by the rows of a batch.
+pub struct Target<'a> {
+    pub format: &'a AddressFormat,
+    pub network: Option<Network>,
+}
+
+/// Convert each non-empty line of `input` into a line of `output`.
+/// Failed lines are reported to `errors` and skipped, and the batch then fails as a whole.
+pub fn convert_lines<R, W, E>(converter: &Converter, target: &Target<'_>, format: OutputFormat, input: R, mut output: W, mut errors: E) -> Result<()>
+    where R: BufRead, W: Write, E: Write
+{
+    let (mut failed, mut total) = (0, 0);
+    for (i, line) in input.lines().enumerate() {
+        let line = line?;
+        let addr = line.trim();
+        if addr.is_empty() {
+            continue;
+        }
+
+        total += 1;
+        match convert(converter, addr, target.format, target.network) {
+            Ok(converted) => output::write_record(&mut output, format, &Converted { input: addr, output: converted })?,
+            Err(err) => {
+                failed += 1;
+                writeln!(errors, "line {}: {}", i + 1, err)?;
+            },
+        }
+    }
+    output.flush()?;
+
+    batch_result(failed, total)
+}
+
+/// Convert the `column` of each CSV record, copying the record to `output`.
+/// Records that fail keep their original value and are reported to `errors`, and the batch then fails as a whole.
+pub fn convert_csv<R, W, E>(converter: &Converter, target: &Target<'_>, column: &str, format: OutputFormat, input: R, output: W, mut errors: E) -> Result<()>
+    where R: io::Read, W: Write, E: Write
+{
+    let mut reader = csv::Reader::from_reader(input);
+
+    let headers = reader.headers()?.clone();
+    let index = headers.iter().position(|header| header == column)
+        .ok_or_else(|| CliError::MissingColumn(column.to_string()))?;
+    let mut writer = RecordWriter::new(output, format, headers)?;
+
+    let (mut failed, mut total) = (0, 0);
+    let mut record = csv::StringRecord::new();
+    while reader.read_record(&mut record)? {
+        total += 1;
+        match convert(converter, &record[index], target.format, target.network) {
+            Ok(converted) => {
+                let fields = record.iter().enumerate().map(|(i, field)| if i == index { converted.as_str() } else { field });
+                writer.write_record(fields)?;
+            },
+            Err(err) => {
+                failed += 1;
+                let line = record.position().map_or(total + 1, |position| position.line() as usize);
+                writeln!(errors, "line {}: {}", line, err)?;
+                writer.write_record(&record)?;
+            },
+        }
+    }
+    writer.flush()?;
+
+    batch_result(failed, total)
+}
+
+/// Writer of `convert --csv` records in the `--output` format.
+enum RecordWriter<W: Write> {
+    /// CSV, or TSV for `--output tsv`, with the header row.
+    Delimited(Box<csv::Writer<W>>),
+    /// One JSON object per line, keyed by the headers.
+    Json(W, csv::StringRecord),
+}
+
+impl<W: Write> RecordWriter<W> {
+    fn new(output: W, format: OutputFormat, headers: csv::StringRecord) -> Result<RecordWriter<W>> {
+        let delimiter = match format {
+            OutputFormat::Json => return Ok(RecordWriter::Json(output, headers)),
+            OutputFormat::Text => b',',
+            OutputFormat::Tsv  => b'\t',
+        };
+
+        let mut writer = csv::WriterBuilder::new().delimiter(delimiter).from_writer(output);
+        writer.write_record(&headers)?;
+        Ok(RecordWriter::Delimited(Box::new(writer)))
+    }
+
+    fn write_record<'a, I>(&mut self, fields: I) -> Result<()>
+        where I: IntoIterator<Item = &'a str>
+    {
+        match self {
+            RecordWriter::Delimited(writer) => writer.write_record(fields)?,
+            RecordWriter::Json(output, headers) => {
+                let object: Vec<(&str, &str)> = headers.iter().zip(fields).collect();
+                serde_json::to_writer(&mut *output, &JsonObject(&object)).map_err(io::Error::from)?;
+                writeln!(output)?;
+            },
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            RecordWriter::Delimited(writer) => writer.flush(),
+            RecordWriter::Json(output, _)   => output.flush(),
+        }
+    }
+}
+
+/// Key-value pairs serialized as a JSON object in their original order.
+struct JsonObject<'a>(&'a [(&'a str, &'a str)]);
+
+impl Serialize for JsonObject<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().copied())
+    }
+}
+
+fn batch_result(failed: usize, total: usize) -> Result<()> {
+    if failed > 0 {
+        return Err(CliError::Batch(failed, total));
+    }
+    Ok(())
+}
+
+/// Convert `addr` to `format` on `network` (unchanged when `None`).
+pub fn convert(converter: &Converter, addr: &str, format: &AddressFormat, network: Option<Network>) -> bch_addr::Result<String> {
+    match (format, network) {
+        (AddressFormat::Legacy, None)          => converter.to_legacy_addr(addr),
+        (AddressFormat::Legacy, Some(network)) => {
+            let (_, _, addr_type, hash) = converter.parse(addr)?;
+            let mut buf = [0; MAX_ADDR_LEN];
+            let len = converter.encode_into(format, network, addr_type, &hash, &mut buf)?;
+            Ok(String::from_utf8_lossy(&buf[..len]).into_owned())
+        },
+        (format, network) => converter.to_cash_addr_with_options(addr, Some(format.clone()), network),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_test() {
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+        let legacy = "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR";
+        let cash_addr = "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk";
+
+        assert_eq!(convert(&converter, legacy, &AddressFormat::CashAddr, None).unwrap(), cash_addr);
+        assert_eq!(convert(&converter, cash_addr, &AddressFormat::Legacy, None).unwrap(), legacy);
+        assert_eq!(
+            convert(&converter, cash_addr, &AddressFormat::Other("SLPAddr".to_string()), None).unwrap(),
+            "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg",
+        );
+        assert_eq!(
+            convert(&converter, cash_addr, &AddressFormat::Legacy, Some(Network::Testnet)).unwrap(),
+            "mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi",
+        );
+        assert!(convert(&converter, "bitcoincash:bad", &AddressFormat::Legacy, None).is_err());
+    }
+
+    #[test]
+    fn convert_lines_test() {
+        let converter = Converter::new();
+        let target = Target { format: &AddressFormat::Legacy, network: None };
+        let input = "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk\n\nnope\nbitcoincash:ppm2qsznhks23z7629mms6s4cwef74vcwvn0h829pq\n";
+
+        let (mut output, mut errors) = (Vec::new(), Vec::new());
+        let ret = convert_lines(&converter, &target, OutputFormat::Text, input.as_bytes(), &mut output, &mut errors);
+        assert!(matches!(ret, Err(CliError::Batch(1, 3))));
+        assert_eq!(String::from_utf8(output).unwrap(), "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR\n3CWFddi6m4ndiGyKqzYvsFYagqDLPVMTzC\n");
+        assert_eq!(String::from_utf8(errors).unwrap(), "line 3: invalid address: nope\n");
+    }
+
+    #[test]
+    fn convert_csv_test() {
+        let converter = Converter::new();
+        let target = Target { format: &AddressFormat::CashAddr, network: None };
+        let input = "id,address\n1,1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR\n2,nope\n";
+
+        let (mut output, mut errors) = (Vec::new(), Vec::new());
+        let ret = convert_csv(&converter, &target, "address", OutputFormat::Text, input.as_bytes(), &mut output, &mut errors);
+        assert!(matches!(ret, Err(CliError::Batch(1, 2))));
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "id,address\n1,bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk\n2,nope\n",
+        );
+        assert_eq!(String::from_utf8(errors).unwrap(), "line 3: invalid address: nope\n");
+
+        let ret = convert_csv(&converter, &target, "addr", OutputFormat::Text, input.as_bytes(), Vec::new(), Vec::new());
+        assert!(matches!(ret, Err(CliError::MissingColumn(_))));
+    }
+
+    #[test]
+    fn output_format_test() {
+        let converter = Converter::new();
+        let target = Target { format: &AddressFormat::CashAddr, network: None };
+        let legacy = "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR";
+        let cash_addr = "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk";
+
+        let mut output = Vec::new();
+        convert_lines(&converter, &target, OutputFormat::Tsv, legacy.as_bytes(), &mut output, Vec::new()).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), format!("{}\t{}\n", legacy, cash_addr));
+
+        let input = format!("id,address\n1,{}\n", legacy);
+        let mut output = Vec::new();
+        convert_csv(&converter, &target, "address", OutputFormat::Tsv, input.as_bytes(), &mut output, Vec::new()).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), format!("id\taddress\n1\t{}\n", cash_addr));
+
+        let mut output = Vec::new();
+        convert_csv(&converter, &target, "address", OutputFormat::Json, input.as_bytes(), &mut output, Vec::new()).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), format!("{{\"id\":\"1\",\"address\":\"{}\"}}\n", cash_addr));
+    }
+}
@@ -0,0 +1,55 @@
+//! `from-pubkey` and `from-script` subcommands.
+
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use bch_addr::{Converter, Network};
+
+use crate::args::{FormatArg, HexArg, NetworkArg};
+use crate::error::Result;
+use crate::output::{self, OutputFormat, Record};
+
+#[derive(Debug, clap::Args)]
+pub struct DeriveArgs {
+    /// Hex-encoded public key (`from-pubkey`) or redeem script (`from-script`).
+    data: HexArg,
+
+    /// Address format: `cashaddr`, `legacy` or a format registered with `--prefix`.
+    #[arg(long, value_name = "FORMAT", default_value = "cashaddr")]
+    format: FormatArg,
+
+    /// Address network.
+    #[arg(long, value_enum, default_value = "mainnet")]
+    network: NetworkArg,
+}
+
+/// A derived address. TSV column: address.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Derived {
+    address: String,
+}
+
+impl Record for Derived {
+    fn write_text<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        writeln!(output, "{}", self.address)
+    }
+
+    fn tsv_fields(&self) -> Vec<String> {
+        vec![self.address.clone()]
+    }
+}
+
+/// Print the P2PKH address of a public key.
+pub fn run_pubkey(converter: &Converter, args: &DeriveArgs, format: OutputFormat) -> Result<()> {
+    let address = converter.from_pubkey(&args.data.0, &args.format.0, Network::from(args.network))?;
+    output::print_record(format, &Derived { address })?;
+    Ok(())
+}
+
+/// Print the P2SH address of a redeem script.
+pub fn run_script(converter: &Converter, args: &DeriveArgs, format: OutputFormat) -> Result<()> {
+    let address = converter.from_redeem_script(&args.data.0, &args.format.0, Network::from(args.network))?;
+    output::print_record(format, &Derived { address })?;
+    Ok(())
+}
@@ -0,0 +1,58 @@
+// `failure_derive` expands its impls inside an anonymous const.
+#![allow(non_local_definitions)]
+
+use std::io;
+
+use failure::Fail;
+
+/// Errors reported by the command line tool.
+#[derive(Debug, Fail)]
+pub enum CliError {
+    /// Address error from the library.
+    #[fail(display = "{}", 0)]
+    Address(bch_addr::Error),
+
+    /// I/O error.
+    #[fail(display = "{}", 0)]
+    Io(io::Error),
+
+    /// CSV error.
+    #[fail(display = "{}", 0)]
+    Csv(csv::Error),
+
+    /// The CSV input has no column with this name.
+    #[fail(display = "no column named `{}`", 0)]
+    MissingColumn(String),
+
+    /// The data does not fit in a QR code.
+    #[fail(display = "{}", 0)]
+    Qr(qrcode::types::QrError),
+
+    /// PNG output error.
+    #[fail(display = "{}", 0)]
+    Image(image::ImageError),
+
+    /// Some rows of a batch failed; each was reported as it was read.
+    #[fail(display = "{} of {} addresses failed", 0, 1)]
+    Batch(usize, usize),
+}
+
+pub type Result<T> = std::result::Result<T, CliError>;
+
+impl From<bch_addr::Error> for CliError {
+    fn from(err: bch_addr::Error) -> CliError {
+        CliError::Address(err)
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(err: io::Error) -> CliError {
+        CliError::Io(err)
+    }
+}
+
+impl From<csv::Error> for CliError {
+    fn from(err: csv::Error) -> CliError {
+        CliError::Csv(err)
+    }
+}
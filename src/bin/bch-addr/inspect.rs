@@ -0,0 +1,134 @@
+//! `inspect` subcommand.
+
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use bch_addr::{Converter, AddressDetails, AddressFormat, AddressType, Network};
+
+use crate::error::Result;
+use crate::output::{self, OutputFormat, Record};
+
+#[derive(Debug, clap::Args)]
+pub struct InspectArgs {
+    /// Address to inspect.
+    addr: String,
+
+    /// Shorthand for `--output json`.
+    #[arg(long)]
+    json: bool,
+}
+
+/// `inspect` output.
+/// TSV columns: address, format, network, type, prefix (empty for legacy), version byte, payload, type bits.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Report<'a> {
+    address: &'a str,
+    format: &'a str,
+    network: &'static str,
+    #[serde(rename = "type")]
+    addr_type: &'static str,
+    prefix: Option<&'a str>,
+    version_byte: u8,
+    payload: String,
+    /// Type field: 0 for P2PKH, 1 for P2SH, the registered one for `other`.
+    type_bits: u8,
+}
+
+impl<'a> Report<'a> {
+    pub fn new(addr: &'a str, details: &'a AddressDetails) -> Report<'a> {
+        Report {
+            address: addr,
+            format: format_name(&details.format),
+            network: network_name(details.network),
+            addr_type: type_name(details.addr_type),
+            prefix: details.prefix.as_deref(),
+            version_byte: details.version_byte,
+            payload: details.hash.iter().map(|b| format!("{:02x}", b)).collect(),
+            type_bits: details.addr_type.type_bits(),
+        }
+    }
+}
+
+impl Record for Report<'_> {
+    fn write_text<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        writeln!(output, "format:       {}", self.format)?;
+        writeln!(output, "network:      {}", self.network)?;
+        writeln!(output, "type:         {}", self.addr_type)?;
+        writeln!(output, "type bits:    {}", self.type_bits)?;
+        if let Some(prefix) = self.prefix {
+            writeln!(output, "prefix:       {}", prefix)?;
+        }
+        writeln!(output, "version byte: 0x{:02x}", self.version_byte)?;
+        writeln!(output, "payload:      {}", self.payload)
+    }
+
+    fn tsv_fields(&self) -> Vec<String> {
+        vec![
+            self.address.to_string(),
+            self.format.to_string(),
+            self.network.to_string(),
+            self.addr_type.to_string(),
+            self.prefix.unwrap_or_default().to_string(),
+            self.version_byte.to_string(),
+            self.payload.clone(),
+            self.type_bits.to_string(),
+        ]
+    }
+}
+
+pub fn run(converter: &Converter, args: &InspectArgs, format: OutputFormat) -> Result<()> {
+    let details = converter.inspect(&args.addr)?;
+    let format = if args.json { OutputFormat::Json } else { format };
+
+    output::print_record(format, &Report::new(&args.addr, &details))?;
+    Ok(())
+}
+
+fn format_name(format: &AddressFormat) -> &str {
+    match format {
+        AddressFormat::Legacy      => "legacy",
+        AddressFormat::CashAddr    => "cashaddr",
+        AddressFormat::Other(name) => name,
+    }
+}
+
+fn network_name(network: Network) -> &'static str {
+    match network {
+        Network::Mainnet => "mainnet",
+        Network::Testnet => "testnet",
+        Network::Regtest => "regtest",
+    }
+}
+
+fn type_name(addr_type: AddressType) -> &'static str {
+    match addr_type {
+        AddressType::P2PKH    => "p2pkh",
+        AddressType::P2SH     => "p2sh",
+        AddressType::Other(_) => "other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_test() {
+        let converter = Converter::new();
+        let addr = "bchtest:pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t";
+        let details = converter.inspect(addr).unwrap();
+
+        let json = serde_json::to_value(Report::new(addr, &details)).unwrap();
+        assert_eq!(json, serde_json::json!({
+            "address": addr,
+            "format": "cashaddr",
+            "network": "testnet",
+            "type": "p2sh",
+            "prefix": "bchtest",
+            "version_byte": 8,
+            "payload": "f5bf48b397dae70be82b3cca4793f8eb2b6cdac9",
+            "type_bits": 1,
+        }));
+    }
+}
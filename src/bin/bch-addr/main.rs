@@ -0,0 +1,91 @@
+//! `bch-addr` command line tool.
+
+use std::{io, process};
+
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+
+use bch_addr::Converter;
+
+mod args;
+mod error;
+mod convert;
+mod derive;
+mod inspect;
+mod output;
+mod qr;
+mod scan;
+
+use args::{PrefixArg, TypeBitsArg};
+use output::OutputFormat;
+
+/// Convert and inspect Bitcoin Cash addresses.
+#[derive(Debug, Parser)]
+#[command(name = "bch-addr", version)]
+struct Cli {
+    /// Register a user-defined prefix, e.g. `simpleledger:mainnet:SLPAddr`. Can be repeated.
+    #[arg(long = "prefix", value_name = "PREFIX:NETWORK:FORMAT", global = true)]
+    prefixes: Vec<PrefixArg>,
+
+    /// Accept an unassigned type field in a cash_addr-like format, e.g. `cashaddr:2`. Can be repeated.
+    #[arg(long = "type-bits", value_name = "FORMAT:BITS", global = true)]
+    type_bits: Vec<TypeBitsArg>,
+
+    /// Output format. `json` prints one object per line and `tsv` one row per line, without a header.
+    #[arg(long, value_enum, default_value_t, global = true)]
+    output: OutputFormat,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Convert an address to another format or network.
+    Convert(convert::ConvertArgs),
+    /// Show how an address is encoded.
+    Inspect(inspect::InspectArgs),
+    /// Find addresses in free text and print their line and column.
+    Scan(scan::ScanArgs),
+    /// Render a payment URI as a QR code.
+    Qr(qr::QrArgs),
+    /// Derive the P2PKH address of a hex public key.
+    FromPubkey(derive::DeriveArgs),
+    /// Derive the P2SH address of a hex redeem script.
+    FromScript(derive::DeriveArgs),
+    /// Print a shell completion script, e.g. `bch-addr completions bash > /etc/bash_completion.d/bch-addr`.
+    Completions {
+        /// Target shell.
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let converter = cli.prefixes.iter().fold(Converter::new(), |converter, prefix| {
+        converter.add_prefixes(&[(&prefix.prefix, prefix.network)], &prefix.format)
+    });
+    let converter = cli.type_bits.iter().fold(converter, |converter, arg| {
+        converter.add_type_bits(&arg.format, &[arg.type_bits])
+    });
+
+    let result = match cli.command {
+        Command::Convert(args)         => convert::run(&converter, &args, cli.output),
+        Command::Inspect(args)         => inspect::run(&converter, &args, cli.output),
+        Command::Scan(args)            => scan::run(&converter, &args, cli.output),
+        Command::Qr(args)              => qr::run(&converter, &args, cli.output),
+        Command::FromPubkey(args)      => derive::run_pubkey(&converter, &args, cli.output),
+        Command::FromScript(args)      => derive::run_script(&converter, &args, cli.output),
+        Command::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "bch-addr", &mut io::stdout());
+            Ok(())
+        },
+    };
+
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
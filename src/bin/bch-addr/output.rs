@@ -0,0 +1,87 @@
+//! `--output` rendering shared by the subcommands.
+
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+/// Output format selected with `--output`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text.
+    #[default]
+    Text,
+    /// One JSON object per line.
+    Json,
+    /// Tab-separated fields, one record per line, without a header.
+    Tsv,
+}
+
+/// A unit of subcommand output.
+pub trait Record: Serialize {
+    /// Write the human-readable form, including trailing newlines.
+    fn write_text<W: Write>(&self, output: &mut W) -> io::Result<()>;
+
+    /// Fields of the TSV row.
+    fn tsv_fields(&self) -> Vec<String>;
+}
+
+/// Write `record` to `output` in `format`.
+pub fn write_record<W: Write, R: Record>(output: &mut W, format: OutputFormat, record: &R) -> io::Result<()> {
+    match format {
+        OutputFormat::Text => record.write_text(output),
+        OutputFormat::Json => {
+            serde_json::to_writer(&mut *output, record)?;
+            writeln!(output)
+        },
+        OutputFormat::Tsv  => {
+            // Tabs and newlines would break the row, and none of the fields should contain them.
+            let fields: Vec<String> = record.tsv_fields().into_iter()
+                .map(|field| field.replace(['\t', '\n', '\r'], " "))
+                .collect();
+            writeln!(output, "{}", fields.join("\t"))
+        },
+    }
+}
+
+/// Write `record` to stdout in `format`.
+pub fn print_record<R: Record>(format: OutputFormat, record: &R) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut output = stdout.lock();
+    write_record(&mut output, format, record)?;
+    output.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Pair {
+        input: &'static str,
+        output: &'static str,
+    }
+
+    impl Record for Pair {
+        fn write_text<W: Write>(&self, output: &mut W) -> io::Result<()> {
+            writeln!(output, "{}", self.output)
+        }
+
+        fn tsv_fields(&self) -> Vec<String> {
+            vec![self.input.to_string(), self.output.to_string()]
+        }
+    }
+
+    #[test]
+    fn write_record_test() {
+        let pair = Pair { input: "a\tb", output: "c" };
+        let render = |format| {
+            let mut output = Vec::new();
+            write_record(&mut output, format, &pair).unwrap();
+            String::from_utf8(output).unwrap()
+        };
+
+        assert_eq!(render(OutputFormat::Text), "c\n");
+        assert_eq!(render(OutputFormat::Json), "{\"input\":\"a\\tb\",\"output\":\"c\"}\n");
+        assert_eq!(render(OutputFormat::Tsv), "a b\tc\n");
+    }
+}
@@ -0,0 +1,69 @@
+//! `qr` subcommand.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use qrcode::render::{svg, unicode};
+use qrcode::QrCode;
+use serde::Serialize;
+
+use bch_addr::Converter;
+
+use crate::error::{CliError, Result};
+use crate::output::{self, OutputFormat, Record};
+
+#[derive(Debug, clap::Args)]
+pub struct QrArgs {
+    /// Address to encode, in any format.
+    addr: String,
+
+    /// Amount in BCH to request.
+    #[arg(long)]
+    amount: Option<String>,
+
+    /// Also write the QR code as SVG to this file.
+    #[arg(long, value_name = "FILE")]
+    svg: Option<PathBuf>,
+
+    /// Also write the QR code as PNG to this file.
+    #[arg(long, value_name = "FILE")]
+    png: Option<PathBuf>,
+}
+
+/// An encoded payment URI. The terminal rendering is only part of the text output.
+/// TSV column: uri.
+#[derive(Serialize)]
+pub struct Encoded {
+    uri: String,
+    #[serde(skip)]
+    code: QrCode,
+}
+
+impl Record for Encoded {
+    fn write_text<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        writeln!(output, "{}", self.code.render::<unicode::Dense1x2>().quiet_zone(true).build())?;
+        writeln!(output, "{}", self.uri)
+    }
+
+    fn tsv_fields(&self) -> Vec<String> {
+        vec![self.uri.clone()]
+    }
+}
+
+pub fn run(converter: &Converter, args: &QrArgs, format: OutputFormat) -> Result<()> {
+    let uri = converter.qr_payment_uri(&args.addr, args.amount.as_deref())?;
+    let code = QrCode::new(uri.as_bytes()).map_err(CliError::Qr)?;
+    let encoded = Encoded { uri, code };
+    output::print_record(format, &encoded)?;
+
+    if let Some(path) = &args.svg {
+        fs::write(path, encoded.code.render::<svg::Color<'_>>().min_dimensions(256, 256).build())?;
+    }
+    if let Some(path) = &args.png {
+        encoded.code.render::<image::Luma<u8>>().min_dimensions(256, 256).build()
+            .save_with_format(path, image::ImageFormat::Png)
+            .map_err(CliError::Image)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,95 @@
+//! `scan` subcommand.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use bch_addr::Converter;
+
+use crate::error::Result;
+use crate::output::{self, OutputFormat, Record};
+
+#[derive(Debug, clap::Args)]
+pub struct ScanArgs {
+    /// File to scan, or `-` for stdin.
+    input: PathBuf,
+}
+
+pub fn run(converter: &Converter, args: &ScanArgs, format: OutputFormat) -> Result<()> {
+    let stdout = io::stdout();
+    let output = io::BufWriter::new(stdout.lock());
+
+    if args.input.as_os_str() == "-" {
+        let stdin = io::stdin();
+        scan(converter, format, stdin.lock(), output)
+    } else {
+        scan(converter, format, BufReader::new(File::open(&args.input)?), output)
+    }
+}
+
+/// An address found by `scan`, with its 1-based line and column.
+/// TSV columns: line, column, address.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Found<'a> {
+    line: usize,
+    column: usize,
+    address: &'a str,
+}
+
+impl Record for Found<'_> {
+    fn write_text<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        writeln!(output, "{}:{}: {}", self.line, self.column, self.address)
+    }
+
+    fn tsv_fields(&self) -> Vec<String> {
+        vec![self.line.to_string(), self.column.to_string(), self.address.to_string()]
+    }
+}
+
+/// Print every valid address in `input`; as `LINE:COLUMN: ADDRESS` in text.
+pub fn scan<R: BufRead, W: Write>(converter: &Converter, format: OutputFormat, input: R, mut output: W) -> Result<()> {
+    for (i, line) in input.lines().enumerate() {
+        let line = line?;
+        for (column, address) in scan_line(converter, &line) {
+            output::write_record(&mut output, format, &Found { line: i + 1, column, address })?;
+        }
+    }
+    output.flush()?;
+    Ok(())
+}
+
+/// Valid addresses in `line` with their 1-based character columns.
+fn scan_line<'a>(converter: &Converter, line: &'a str) -> Vec<(usize, &'a str)> {
+    converter.scan_text(line).into_iter()
+        .map(|found| (line[..found.range.start].chars().count() + 1, found.text))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_test() {
+        let converter = Converter::new();
+        let input = "send to 1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR, not 1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzS\n\
+                     uri: bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk?amount=1\n\
+                     “ünïcode” addr:3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9\n";
+
+        let mut output = Vec::new();
+        scan(&converter, OutputFormat::Text, input.as_bytes(), &mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "\
+            1:9: 1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR\n\
+            2:6: bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk\n\
+            3:16: 3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9\n");
+
+        let mut output = Vec::new();
+        scan(&converter, OutputFormat::Json, "x 3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9".as_bytes(), &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "{\"line\":1,\"column\":3,\"address\":\"3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9\"}\n",
+        );
+    }
+}
@@ -0,0 +1,5 @@
+//! Generates the Kotlin/Swift bindings for the `uniffi` feature.
+
+fn main() {
+    uniffi::uniffi_bindgen_main()
+}
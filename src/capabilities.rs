@@ -0,0 +1,118 @@
+//! What this build of the crate supports, for checks at startup.
+
+use super::AddressType;
+
+/// Revision of the cashaddr spec implemented by the codec.
+/// Ordered, so a minimum can be asserted with `>=`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum SpecRevision {
+    /// The original spec: P2PKH and P2SH type bits, 160 to 512-bit hashes.
+    /// Token-aware types of the CashTokens upgrade are not part of it.
+    Original,
+}
+
+/// Features of the crate, in the order reported by `Capabilities::features`.
+const FEATURES: &[(&str, bool)] = &[
+    ("legacy", cfg!(feature = "legacy")),
+    ("cashaddr", cfg!(feature = "cashaddr")),
+    ("hash160", cfg!(feature = "hash160")),
+    ("subtle", cfg!(feature = "subtle")),
+    ("serde", cfg!(feature = "serde")),
+    ("borsh", cfg!(feature = "borsh")),
+    ("rkyv", cfg!(feature = "rkyv")),
+    ("arbitrary", cfg!(feature = "arbitrary")),
+    ("sqlx", cfg!(feature = "sqlx")),
+    ("diesel", cfg!(feature = "diesel")),
+    ("bitcoincash-addr", cfg!(feature = "bitcoincash-addr")),
+    ("tracing", cfg!(feature = "tracing")),
+    ("clap", cfg!(feature = "clap")),
+    ("wasm", cfg!(feature = "wasm")),
+    ("ffi", cfg!(feature = "ffi")),
+    ("uniffi", cfg!(feature = "uniffi")),
+    ("axum", cfg!(feature = "axum")),
+    ("actix", cfg!(feature = "actix")),
+    ("addressbook", cfg!(feature = "addressbook")),
+    ("parallel", cfg!(feature = "parallel")),
+    ("report", cfg!(feature = "report")),
+    ("async", cfg!(feature = "async")),
+    ("test-util", cfg!(feature = "test-util")),
+    ("spec-vectors", cfg!(feature = "spec-vectors")),
+    ("cli", cfg!(feature = "cli")),
+];
+
+/// Hash lengths in bytes the cash_addr format can carry.
+const CASH_ADDR_HASH_SIZES: &[usize] = &[20, 24, 28, 32, 40, 48, 56, 64];
+
+/// What the linked build supports. Returned by `capabilities()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Revision of the cashaddr spec, `None` without the `cashaddr` feature.
+    pub spec_revision: Option<SpecRevision>,
+    /// `true` if token-aware address types (CashTokens) are supported.
+    pub token_aware: bool,
+    /// Address types that can be parsed and built without registering type bits.
+    pub address_types: &'static [AddressType],
+    /// Hash lengths in bytes accepted in any supported format, ascending.
+    pub hash_sizes: &'static [usize],
+    /// Enabled cargo features.
+    pub features: Vec<&'static str>,
+}
+
+impl Capabilities {
+    /// Check if the cargo feature `name` is enabled.
+    /// # Arguments
+    /// * `name` - Feature name as in `Cargo.toml`, e.g. `"serde"`.
+    /// # Returns
+    /// * `true` if enabled.
+    pub fn has_feature(&self, name: &str) -> bool {
+        self.features.contains(&name)
+    }
+}
+
+/// Report what this build supports, so a service can refuse to start
+/// rather than fail later on addresses it cannot handle.
+/// # Returns
+/// * Capabilities of the build.
+/// # Example
+/// ```
+/// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+/// # {
+/// use bch_addr::{capabilities, SpecRevision};
+///
+/// let caps = capabilities();
+/// assert!(caps.spec_revision >= Some(SpecRevision::Original));
+/// assert!(!caps.token_aware);
+/// assert!(caps.hash_sizes.contains(&32));
+/// assert!(caps.has_feature("legacy"));
+/// # }
+/// ```
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        spec_revision: if cfg!(feature = "cashaddr") { Some(SpecRevision::Original) } else { None },
+        token_aware: false,
+        address_types: &[AddressType::P2PKH, AddressType::P2SH],
+        hash_sizes: if cfg!(feature = "cashaddr") { CASH_ADDR_HASH_SIZES } else { &CASH_ADDR_HASH_SIZES[..1] },
+        features: FEATURES.iter().filter(|(_, enabled)| *enabled).map(|(name, _)| *name).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::HASH160_LEN;
+
+    #[test]
+    fn capabilities_test() {
+        let caps = capabilities();
+        assert_eq!(caps.hash_sizes[0], HASH160_LEN);
+        assert!(caps.hash_sizes.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(caps.has_feature("cashaddr"), caps.spec_revision.is_some());
+        assert_eq!(caps.has_feature("subtle"), cfg!(feature = "subtle"));
+        assert!(!caps.has_feature("no-such-feature"));
+        #[cfg(feature = "cashaddr")]
+        assert_eq!(caps.hash_sizes.len(), 8);
+        #[cfg(not(feature = "cashaddr"))]
+        assert_eq!(caps.hash_sizes, [HASH160_LEN]);
+    }
+}
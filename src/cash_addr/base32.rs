@@ -0,0 +1,66 @@
+use super::error::{Error, Result};
+
+// https://github.com/rust-bitcoin/rust-bech32/blob/master/src/lib.rs
+pub const CHARSET: [u8; 32] = *b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+// https://github.com/rust-bitcoin/rust-bech32/blob/master/src/lib.rs
+const CHARSET_REV: [i8; 128] = [
+    -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+    -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+    -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+    15, -1, 10, 17, 21, 20, 26, 30,  7,  5, -1, -1, -1, -1, -1, -1,
+    -1, 29, -1, 24, 13, 25,  9,  8, 23, -1, 18, 22, 31, 27, 19, -1,
+    1,  0,  3, 16, 11, 28, 12, 14,  6,  4,  2, -1, -1, -1, -1, -1,
+    -1, 29, -1, 24, 13, 25,  9,  8, 23, -1, 18, 22, 31, 27, 19, -1,
+    1,  0,  3, 16, 11, 28, 12, 14,  6,  4,  2, -1, -1, -1, -1, -1
+];
+
+/// `None` for digits, otherwise whether `c` is lowercase.
+pub const fn is_lower(c: u8) -> Option<bool> {
+    if c.is_ascii_digit() { None } else { Some(c.is_ascii_lowercase()) }
+}
+
+/// 5-bit value of `c`, in either case.
+pub const fn decode_char(c: u8) -> Option<u8> {
+    if c >= 128 || CHARSET_REV[c as usize] < 0 {
+        return None;
+    }
+    Some(CHARSET_REV[c as usize] as u8)
+}
+
+/// Decode `data`, passing each 5-bit value to `f` instead of collecting them.
+pub fn decode_with<F: FnMut(u8)>(data: &str, mut f: F) -> Result<()> {
+    if data.is_empty() || !data.is_ascii() {
+        return Err(Error::InvalidAddressFormat(data.to_string()));
+    }
+
+    let lower = data.bytes().find_map(is_lower);
+
+    for c in data.bytes() {
+        if let Some(case) = is_lower(c) {
+            if case != lower.ok_or(Error::InvalidChar(char::from(c)))? {
+                return Err(Error::MixedCase);
+            }
+        }
+
+        f(decode_char(c).ok_or(Error::InvalidChar(char::from(c)))?);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let test_str = "qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2";
+
+        let mut decoded = Vec::new();
+        decode_with(test_str, |v| decoded.push(v)).unwrap();
+        let encoded: String = decoded.iter().map(|&p| char::from(CHARSET[p as usize])).collect();
+
+        assert_eq!(encoded, test_str);
+    }
+}
@@ -0,0 +1,706 @@
+use super::error::{Error, Result};
+use super::base32::{self, CHARSET};
+use super::format::{AddressType, SEPARATOR, CHECKSUM_LEN, MAX_BODY_LEN, MAX_TYPE_BITS, body_len, split, normalize_prefix, version_byte, hash_size};
+
+/// Version byte and a 512-bit hash.
+const MAX_PAYLOAD_LEN: usize = 1 + 64;
+
+/// Encode hash to cash_addr format.
+/// # Arguments
+/// * `prefix` - address prefix.
+/// * `address_type` - address type.
+/// * `hash` - hashed publickey.
+/// # Returns
+/// * cash_addr format address.
+/// # Example
+/// ```
+/// use bch_addr::cash_addr::{encode, AddressType};
+///
+/// let data = [0xF5, 0xBF, 0x48, 0xB3, 0x97, 0xDA, 0xE7, 0x0B, 0xE8, 0x2B, 0x3C, 0xCA, 0x47, 0x93, 0xF8, 0xEB, 0x2B, 0x6C, 0xDA, 0xC9];
+/// let prefix = "bitcoincash";
+/// let addr_type = AddressType::P2PKH;
+///
+/// let address = encode(prefix, addr_type, &data).unwrap();
+/// assert_eq!(address, "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2");
+/// ```
+pub fn encode(prefix: &str, address_type: AddressType, hash: &[u8]) -> Result<String> {
+    let version_byte = version_byte(address_type, hash)?;
+
+    let mut body = [0; MAX_BODY_LEN];
+    let len = encode_body(prefix, version_byte, hash, &mut body);
+
+    let mut address = String::with_capacity(prefix.len() + 1 + len);
+    address.push_str(prefix);
+    address.push(SEPARATOR);
+    address.extend(body[..len].iter().map(|&c| char::from(c)));
+
+    Ok(address)
+}
+
+/// Encode hash to cash_addr format into a caller-provided buffer, without heap allocation.
+/// # Arguments
+/// * `prefix` - address prefix.
+/// * `address_type` - address type.
+/// * `hash` - hashed publickey.
+/// * `out` - Output buffer. `prefix.len() + 1 + MAX_BODY_LEN` bytes are always enough.
+/// # Returns
+/// * Number of bytes written. `out[..len]` holds the ASCII address.
+/// # Example
+/// ```
+/// use bch_addr::cash_addr::{encode_into, AddressType, MAX_BODY_LEN};
+///
+/// let data = [0xF5, 0xBF, 0x48, 0xB3, 0x97, 0xDA, 0xE7, 0x0B, 0xE8, 0x2B, 0x3C, 0xCA, 0x47, 0x93, 0xF8, 0xEB, 0x2B, 0x6C, 0xDA, 0xC9];
+/// let mut buf = [0; 12 + MAX_BODY_LEN];
+/// let len = encode_into("bitcoincash", AddressType::P2PKH, &data, &mut buf).unwrap();
+/// assert_eq!(&buf[..len], &b"bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2"[..]);
+/// ```
+pub fn encode_into(prefix: &str, address_type: AddressType, hash: &[u8], out: &mut [u8]) -> Result<usize> {
+    let version_byte = version_byte(address_type, hash)?;
+
+    let body_start = prefix.len() + 1;
+    let required = body_start + body_len(hash.len());
+    if out.len() < required {
+        return Err(Error::BufferTooSmall(required));
+    }
+
+    out[..prefix.len()].copy_from_slice(prefix.as_bytes());
+    out[prefix.len()] = SEPARATOR as u8;
+    let len = encode_body(prefix, version_byte, hash, &mut out[body_start..]);
+
+    Ok(body_start + len)
+}
+
+/// Compute the checksum characters ending the body of an address, e.g. to assemble or repair one piecewise.
+/// The version byte is not checked against the payload length.
+/// # Arguments
+/// * `prefix` - address prefix.
+/// * `version_byte` - version byte.
+/// * `payload` - hashed publickey, up to 64 bytes.
+/// # Returns
+/// * The last 8 characters of the address, lowercase.
+/// # Example
+/// ```
+/// use bch_addr::cash_addr::compute_cash_checksum;
+///
+/// let data = [0xF5, 0xBF, 0x48, 0xB3, 0x97, 0xDA, 0xE7, 0x0B, 0xE8, 0x2B, 0x3C, 0xCA, 0x47, 0x93, 0xF8, 0xEB, 0x2B, 0x6C, 0xDA, 0xC9];
+/// let checksum = compute_cash_checksum("bitcoincash", 0, &data).unwrap();
+/// assert_eq!(&checksum, b"lep8ekg2");
+/// ```
+pub fn compute_cash_checksum(prefix: &str, version_byte: u8, payload: &[u8]) -> Result<[u8; CHECKSUM_LEN]> {
+    if payload.len() >= MAX_PAYLOAD_LEN {
+        return Err(Error::InvalidHashSize(payload.len()));
+    }
+
+    let mut body = [0; MAX_BODY_LEN];
+    let len = encode_body(prefix, version_byte, payload, &mut body);
+
+    let mut checksum = [0; CHECKSUM_LEN];
+    checksum.copy_from_slice(&body[len - CHECKSUM_LEN..len]);
+    Ok(checksum)
+}
+
+/// `out` must hold at least `body_len(hash.len())` bytes.
+fn encode_body(prefix: &str, version_byte: u8, hash: &[u8], out: &mut [u8]) -> usize {
+    let mut payload = [0; MAX_PAYLOAD_LEN];
+    payload[0] = version_byte;
+    payload[1..=hash.len()].copy_from_slice(hash);
+    let len = to_base32(&payload[..=hash.len()], out);
+
+    let mut checksum = Checksum::new(prefix);
+    out[..len].iter().for_each(|&v| checksum.update(v));
+    let checksum = checksum.finalize();
+    for (i, v) in out[len..len + CHECKSUM_LEN].iter_mut().enumerate() {
+        let shift = 5 * (CHECKSUM_LEN - 1 - i);
+        *v = ((checksum >> shift) & 31) as u8;
+    }
+
+    let len = len + CHECKSUM_LEN;
+    out[..len].iter_mut().for_each(|v| *v = CHARSET[*v as usize]);
+    len
+}
+
+/// Decode cash_addr.
+/// # Arguments
+/// * `address` - cash_addr format address
+/// # Resurns
+/// * Prefix.
+/// * Address type.
+/// * hashed publickey.
+/// # Example
+/// ```
+/// use bch_addr::cash_addr::{decode, AddressType};
+///
+/// let data = [0xF5, 0xBF, 0x48, 0xB3, 0x97, 0xDA, 0xE7, 0x0B, 0xE8, 0x2B, 0x3C, 0xCA, 0x47, 0x93, 0xF8, 0xEB, 0x2B, 0x6C, 0xDA, 0xC9];
+/// let address = "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2";
+/// let (prefix, addr_type, hash) = decode(address).unwrap();
+///
+/// assert_eq!(prefix, "bitcoincash");
+/// assert_eq!(addr_type, AddressType::P2PKH);
+/// assert_eq!(hash, data);
+/// ```
+pub fn decode(address: &str) -> Result<(String, AddressType, Vec<u8>)> {
+    let (prefix, body) = split(address)?;
+    let body = Body::decode(body)?;
+    if !body.verify(prefix) {
+        return Err(Error::InvalidChecksum(address.to_string()));
+    }
+
+    let mut hash = Vec::new();
+    let address_type = body.decode_into(&mut hash)?;
+
+    Ok((normalize_prefix(prefix).into_owned(), address_type, hash))
+}
+
+/// Decode cash_addr against the prefix the caller expects, e.g. always `bitcoincash` for a payment processor.
+/// A prefix-less address is checked against `prefix` alone, with no other prefix tried.
+/// # Arguments
+/// * `address` - cash_addr format address, with or without prefix. A prefix must be `prefix`, in any case.
+/// * `prefix` - Expected prefix.
+/// # Returns
+/// * Address type.
+/// * hashed publickey.
+/// # Example
+/// ```
+/// use bch_addr::cash_addr::{decode_with_expected_prefix, AddressType};
+///
+/// let (addr_type, hash) = decode_with_expected_prefix("qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2", "bitcoincash").unwrap();
+/// assert_eq!(addr_type, AddressType::P2PKH);
+/// assert_eq!(hash.len(), 20);
+///
+/// assert!(decode_with_expected_prefix("qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2", "bchtest").is_err());
+/// assert!(decode_with_expected_prefix("bchtest:pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t", "bitcoincash").is_err());
+/// ```
+pub fn decode_with_expected_prefix(address: &str, prefix: &str) -> Result<(AddressType, Vec<u8>)> {
+    let body = if address.contains(SEPARATOR) {
+        let (found, body) = split(address)?;
+        if !found.eq_ignore_ascii_case(prefix) {
+            return Err(Error::InvalidAddressFormat(address.to_string()));
+        }
+        body
+    } else {
+        address
+    };
+
+    let body = Body::decode(body)?;
+    if !body.verify(prefix) {
+        return Err(Error::InvalidChecksum(address.to_string()));
+    }
+
+    let mut hash = Vec::new();
+    let address_type = body.decode_into(&mut hash)?;
+    Ok((address_type, hash))
+}
+
+/// Check only the checksum of `address`, against the address's own prefix.
+/// The version byte and hash are not interpreted, so any prefix, type bits and body length are accepted.
+/// # Arguments
+/// * `address` - cash_addr format address with prefix.
+/// # Returns
+/// * `Ok(())` if the checksum matches.
+/// # Example
+/// ```
+/// use bch_addr::cash_addr::verify_checksum;
+///
+/// assert!(verify_checksum("bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2").is_ok());
+/// assert!(verify_checksum("bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg3").is_err());
+/// ```
+pub fn verify_checksum(address: &str) -> Result<()> {
+    let (prefix, body) = split(address)?;
+    if !Body::decode(body)?.verify(prefix) {
+        return Err(Error::InvalidChecksum(address.to_string()));
+    }
+    Ok(())
+}
+
+/// Check `address` without allocating, in const context too.
+/// Covers the prefix separator, the character set and case, the checksum against the address's own prefix,
+/// and that the body length and padding match the hash size in the version byte.
+/// Prefix-less addresses are rejected, since the prefix to check against is unknown.
+/// # Arguments
+/// * `address` - cash_addr format address with prefix.
+/// # Returns
+/// * `true` if `decode` would accept `address`.
+/// # Example
+/// ```
+/// use bch_addr::cash_addr::is_valid;
+///
+/// const DONATION: &str = "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk";
+/// const _: () = assert!(is_valid(DONATION));
+///
+/// static PAYOUTS: [&str; 2] = {
+///     const TABLE: [&str; 2] = [
+///         "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2",
+///         "bchtest:pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t",
+///     ];
+///     let mut i = 0;
+///     while i < TABLE.len() {
+///         assert!(is_valid(TABLE[i]));
+///         i += 1;
+///     }
+///     TABLE
+/// };
+/// assert!(!is_valid("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwj"));
+/// ```
+pub const fn is_valid(address: &str) -> bool {
+    let address = address.as_bytes();
+
+    let mut separator = None;
+    let mut i = 0;
+    while i < address.len() {
+        if address[i] == SEPARATOR as u8 {
+            if separator.is_some() {
+                return false;
+            }
+            separator = Some(i);
+        }
+        i += 1;
+    }
+    let (prefix, body) = match separator {
+        Some(i) => address.split_at(i),
+        None    => return false,
+    };
+    let (_, body) = body.split_at(1);
+    if body.len() < (CHECKSUM_LEN + 1 + 1) || body.len() > MAX_BODY_LEN {
+        return false;
+    }
+
+    let mut checksum = Checksum::with_prefix(prefix);
+    let mut lower = None;
+    let mut version_byte = 0;
+    let mut last = 0;
+    let mut i = 0;
+    while i < body.len() {
+        let c = body[i];
+        if let Some(case) = base32::is_lower(c) {
+            match lower {
+                None => lower = Some(case),
+                Some(lower) if lower != case => return false,
+                Some(_) => {},
+            }
+        }
+        let v = match base32::decode_char(c) {
+            Some(v) => v,
+            None    => return false,
+        };
+        match i {
+            0 => version_byte = v << 3,
+            1 => version_byte |= v >> 2,
+            _ => {},
+        }
+        if i + 1 == body.len() - CHECKSUM_LEN {
+            last = v;
+        }
+        checksum.update(v);
+        i += 1;
+    }
+
+    let hash_len = hash_size(version_byte);
+    let padding = body_len(hash_len) * 5 - (hash_len + 1) * 8 - CHECKSUM_LEN * 5;
+    checksum.is_valid() && version_byte >> 3 <= MAX_TYPE_BITS && body.len() == body_len(hash_len) && last & ((1 << padding) - 1) == 0
+}
+
+/// Base32 values of the part following the separator.
+/// Decoded once so that several prefixes can be checked against it without reparsing.
+pub(crate) struct Body {
+    values: [u8; MAX_BODY_LEN],
+    len: usize,
+}
+
+impl Body {
+    pub(crate) fn decode(body: &str) -> Result<Body> {
+        if body.len() < (CHECKSUM_LEN + 1 + 1) || body.len() > MAX_BODY_LEN { // checksum + version + hash
+            return Err(Error::InvalidAddressFormat(body.to_string()));
+        }
+
+        let mut values = [0; MAX_BODY_LEN];
+        let mut len = 0;
+        base32::decode_with(body, |v| {
+            values[len] = v;
+            len += 1;
+        })?;
+
+        Ok(Body { values, len })
+    }
+
+    /// `true` if the checksum matches `prefix`.
+    pub(crate) fn verify(&self, prefix: &str) -> bool {
+        self.verify_with(Checksum::new(prefix))
+    }
+
+    /// Same as `verify` but continues from a checksum already fed with the prefix.
+    pub(crate) fn verify_with(&self, mut checksum: Checksum) -> bool {
+        self.values[..self.len].iter().for_each(|&v| checksum.update(v));
+        checksum.is_valid()
+    }
+
+    /// Write the hash into `hash`, reusing its allocation. The checksum must already be verified.
+    pub(crate) fn decode_into(&self, hash: &mut Vec<u8>) -> Result<AddressType> {
+        let mut payload = [0; MAX_PAYLOAD_LEN];
+        let len = self.payload(&mut payload)?;
+
+        hash.clear();
+        hash.extend_from_slice(&payload[1..len]);
+        get_address_type(payload[0])
+    }
+
+    /// Validate like `decode_into` but only report the address type.
+    pub(crate) fn classify(&self) -> Result<AddressType> {
+        let mut payload = [0; MAX_PAYLOAD_LEN];
+        self.payload(&mut payload)?;
+
+        get_address_type(payload[0])
+    }
+
+    /// Decode version byte and hash into `payload`, returning the payload length.
+    fn payload(&self, payload: &mut [u8; MAX_PAYLOAD_LEN]) -> Result<usize> {
+        let len = from_base32(&self.values[..self.len - CHECKSUM_LEN], payload)?; // remove checksum
+        let hash_len = len - 1;
+        if get_hash_size(payload[0])? != hash_len {
+            return Err(Error::HashSizeMismatch(hash_len));
+        }
+
+        Ok(len)
+    }
+}
+
+/// Regroup 8-bit bytes into 5-bit values, padding the last group with zeros.
+/// Five bytes are unpacked into eight values at a time.
+fn to_base32(data: &[u8], out: &mut [u8]) -> usize {
+    let mut chunks = data.chunks_exact(5);
+    let mut len = 0;
+
+    for chunk in &mut chunks {
+        let acc = chunk.iter().fold(0u64, |acc, &b| (acc << 8) | u64::from(b));
+        for (i, v) in out[len..len + 8].iter_mut().enumerate() {
+            *v = ((acc >> (35 - 5 * i)) & 31) as u8;
+        }
+        len += 8;
+    }
+
+    let mut acc: u32 = 0;
+    let mut bits = 0;
+    for &b in chunks.remainder() {
+        acc = (acc << 8) | u32::from(b);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out[len] = ((acc >> bits) & 31) as u8;
+            len += 1;
+        }
+        acc &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        out[len] = ((acc << (5 - bits)) & 31) as u8;
+        len += 1;
+    }
+
+    len
+}
+
+/// Regroup 5-bit values into 8-bit bytes, rejecting non-zero or overlong padding.
+/// Eight values are packed into five bytes at a time.
+fn from_base32(data: &[u8], out: &mut [u8]) -> Result<usize> {
+    let mut chunks = data.chunks_exact(8);
+    let mut len = 0;
+
+    for chunk in &mut chunks {
+        let acc = chunk.iter().fold(0u64, |acc, &v| (acc << 5) | u64::from(v));
+        for (i, b) in out[len..len + 5].iter_mut().enumerate() {
+            *b = (acc >> (32 - 8 * i)) as u8;
+        }
+        len += 5;
+    }
+
+    let mut acc: u32 = 0;
+    let mut bits = 0;
+    for &v in chunks.remainder() {
+        acc = (acc << 5) | u32::from(v);
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out[len] = (acc >> bits) as u8;
+            len += 1;
+        }
+        acc &= (1 << bits) - 1;
+    }
+    if bits >= 5 || acc != 0 {
+        return Err(Error::InvalidPadding);
+    }
+
+    Ok(len)
+}
+
+fn get_hash_size(version_byte: u8) -> Result<usize> {
+    Ok(hash_size(version_byte))
+}
+
+/// Any type field is decoded; only the reserved bit 7 is rejected.
+fn get_address_type(version_byte: u8) -> Result<AddressType> {
+    let type_bits = version_byte >> 3;
+    if type_bits > MAX_TYPE_BITS {
+        return Err(Error::InvalidAddressTypeBit(type_bits));
+    }
+    Ok(AddressType::from_type_bits(type_bits))
+}
+
+/// Running BCH checksum over the prefix and payload (the spec's `PolyMod`).
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Checksum(u64);
+
+const GENERATOR: [u64; 5] = [
+    0x0098_f2bc_8e61,
+    0x0079_b76d_99e2,
+    0x00f3_3e5f_b3c4,
+    0x00ae_2eab_e2a8,
+    0x001e_4f43_e470
+];
+
+/// XOR of the `GENERATOR` entries selected by each 5-bit value shifted out of the state.
+const GENERATOR_TABLE: [u64; 32] = generator_table();
+
+const fn generator_table() -> [u64; 32] {
+    let mut table = [0; 32];
+    let mut c0 = 0;
+    while c0 < 32 {
+        let mut i = 0;
+        while i < GENERATOR.len() {
+            if (c0 & (1 << i)) != 0 {
+                table[c0] ^= GENERATOR[i];
+            }
+            i += 1;
+        }
+        c0 += 1;
+    }
+    table
+}
+
+impl Checksum {
+    pub(crate) const fn new(prefix: &str) -> Checksum {
+        Checksum::with_prefix(prefix.as_bytes())
+    }
+
+    const fn with_prefix(prefix: &[u8]) -> Checksum {
+        let mut checksum = Checksum(1);
+        let mut i = 0;
+        while i < prefix.len() {
+            checksum.update(prefix[i] & 31);
+            i += 1;
+        }
+        checksum.update(0);
+        checksum
+    }
+
+    const fn update(&mut self, d: u8) {
+        let c0 = (self.0 >> 35) as usize;
+        self.0 = ((self.0 & 0x0007_ffff_ffff) << 5) ^ d as u64 ^ GENERATOR_TABLE[c0];
+    }
+
+    /// Checksum value to append to the payload.
+    const fn finalize(mut self) -> u64 {
+        let mut i = 0;
+        while i < CHECKSUM_LEN {
+            self.update(0);
+            i += 1;
+        }
+        self.0 ^ 1
+    }
+
+    /// `true` if the data fed so far ends with a matching checksum.
+    const fn is_valid(&self) -> bool {
+        self.0 == 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::*;
+
+    #[test]
+    fn encode_test() {
+        let data = [0xF5, 0xBF, 0x48, 0xB3, 0x97, 0xDA, 0xE7, 0x0B, 0xE8, 0x2B, 0x3C, 0xCA, 0x47, 0x93, 0xF8, 0xEB, 0x2B, 0x6C, 0xDA, 0xC9];
+
+        let prefix = "bitcoincash";
+        let addr_type = AddressType::P2PKH;
+        let address = encode(prefix, addr_type, &data).unwrap();
+        assert_eq!(address, "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2");
+
+        let prefix = "bchtest";
+        let addr_type = AddressType::P2SH;
+        let address = encode(prefix, addr_type, &data).unwrap();
+        assert_eq!(address, "bchtest:pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t");
+
+        // sizes are reported in bytes, like the hash argument
+        assert!(matches!(encode(prefix, addr_type, &[0; 100]), Err(Error::InvalidHashSize(100))));
+    }
+
+    #[test]
+    fn compute_cash_checksum_test() {
+        for hash_len in &[20, 24, 32, 64] {
+            let hash = vec![0xa5; *hash_len];
+            let address = encode("bchtest", AddressType::P2SH, &hash).unwrap();
+            let version_byte = version_byte(AddressType::P2SH, &hash).unwrap();
+
+            let checksum = compute_cash_checksum("bchtest", version_byte, &hash).unwrap();
+            assert_eq!(&checksum[..], &address.as_bytes()[address.len() - CHECKSUM_LEN..]);
+            assert_eq!(compute_cash_checksum("BCHTEST", version_byte, &hash).unwrap(), checksum);
+        }
+        assert!(matches!(compute_cash_checksum("bchtest", 0, &[0; 65]), Err(Error::InvalidHashSize(65))));
+    }
+
+    #[test]
+    fn encode_into_test() {
+        let data = [0xF5, 0xBF, 0x48, 0xB3, 0x97, 0xDA, 0xE7, 0x0B, 0xE8, 0x2B, 0x3C, 0xCA, 0x47, 0x93, 0xF8, 0xEB, 0x2B, 0x6C, 0xDA, 0xC9];
+        let expected = "bchtest:pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t";
+
+        let mut buf = [0; 8 + MAX_BODY_LEN];
+        let len = encode_into("bchtest", AddressType::P2SH, &data, &mut buf).unwrap();
+        assert_eq!(&buf[..len], expected.as_bytes());
+
+        let mut buf = [0; 49];
+        match encode_into("bchtest", AddressType::P2SH, &data, &mut buf) {
+            Err(Error::BufferTooSmall(len)) => assert_eq!(len, expected.len()),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let data = [0xAB; 64];
+        let address = encode("bitcoincash", AddressType::P2SH, &data).unwrap();
+        assert_eq!(address.len(), 12 + MAX_BODY_LEN);
+        assert_eq!(decode(&address).unwrap().2, &data[..]);
+    }
+
+    #[test]
+    fn hash_size_test() {
+        for &len in &[20, 24, 28, 32, 40, 48, 56, 64] {
+            let data: Vec<u8> = (0..len as u8).map(|i| i.wrapping_mul(151)).collect();
+
+            for &addr_type in &[AddressType::P2PKH, AddressType::P2SH] {
+                let address = encode("bitcoincash", addr_type, &data).unwrap();
+                assert_eq!(address.len(), 12 + body_len(len));
+                assert_eq!(decode(&address).unwrap(), ("bitcoincash".to_string(), addr_type, data.clone()));
+            }
+        }
+    }
+
+    #[test]
+    fn body_test() {
+        let address = "bchtest:pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t";
+        let (prefix, body) = split(address).unwrap();
+        assert_eq!(prefix, "bchtest");
+
+        let body = Body::decode(body).unwrap();
+        assert!(body.verify("bchtest"));
+        assert!(!body.verify("bitcoincash"));
+        assert_eq!(body.classify().unwrap(), AddressType::P2SH);
+
+        let mut hash = vec![0; 3];
+        assert_eq!(body.decode_into(&mut hash).unwrap(), AddressType::P2SH);
+        assert_eq!(hash.len(), 20);
+
+        assert!(Body::decode("pr6m7j9n").is_err());
+        assert!(split("bchtest:pr6m7j9n:").is_err());
+    }
+
+    #[test]
+    fn is_valid_test() {
+        let data = [0xF5, 0xBF, 0x48, 0xB3, 0x97, 0xDA, 0xE7, 0x0B, 0xE8, 0x2B, 0x3C, 0xCA, 0x47, 0x93, 0xF8, 0xEB, 0x2B, 0x6C, 0xDA, 0xC9];
+        let mut addresses = vec![
+            "BITCOINCASH:QR6M7J9NJLDWWZLG9V7V53UNLR4JKMX6EYLEP8EKG2".to_string(),
+            "bitcoincash:QR6M7J9NJLDWWZLG9V7V53UNLR4JKMX6EYLEP8EKG2".to_string(),
+            "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2".to_string(),
+            "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg3".to_string(),
+            "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eYlep8ekg2".to_string(),
+            "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2:".to_string(),
+            "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekb2".to_string(),
+            "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ékg2".to_string(),
+            "bchtest:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2".to_string(),
+            "qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2".to_string(),
+            "bitcoincash:qpzry9x8".to_string(),
+        ];
+        for &len in &[20, 24, 28, 32, 40, 48, 56, 64] {
+            addresses.push(encode("bchreg", AddressType::P2SH, &data.repeat(4)[..len]).unwrap());
+        }
+        // Valid checksums over a hash one byte short, over the reserved bit 7, and over non-zero padding.
+        let mut body = [0; MAX_BODY_LEN];
+        let len = encode_body("bitcoincash", 0, &data[..19], &mut body);
+        addresses.push(format!("bitcoincash:{}", String::from_utf8_lossy(&body[..len])));
+        let len = encode_body("bitcoincash", 0x80, &data, &mut body);
+        addresses.push(format!("bitcoincash:{}", String::from_utf8_lossy(&body[..len])));
+        addresses.push("bitcoincash:sqg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zygnrg5xjv".to_string());
+
+        let mut values = [0; MAX_BODY_LEN];
+        let len = to_base32(&[&[0][..], &data[..]].concat(), &mut values);
+        values[len - 1] |= 1;
+        let mut checksum = Checksum::new("bitcoincash");
+        values[..len].iter().for_each(|&v| checksum.update(v));
+        let checksum = checksum.finalize();
+        let body: String = values[..len].iter().map(|&v| v as u64)
+            .chain((0..CHECKSUM_LEN).rev().map(|i| (checksum >> (5 * i)) & 31))
+            .map(|v| char::from(CHARSET[v as usize]))
+            .collect();
+        addresses.push(format!("bitcoincash:{}", body));
+
+        for address in &addresses {
+            assert_eq!(is_valid(address), decode(address).is_ok(), "{}", address);
+        }
+    }
+
+    #[test]
+    fn normalize_prefix_test() {
+        assert!(matches!(normalize_prefix("bitcoincash"), Cow::Borrowed("bitcoincash")));
+        assert!(matches!(normalize_prefix("bchreg"), Cow::Borrowed(_)));
+        assert_eq!(normalize_prefix("BITCOINCASH"), Cow::<str>::Owned("bitcoincash".to_string()));
+    }
+
+    #[test]
+    fn decode_test() {
+        let data = [0xF5, 0xBF, 0x48, 0xB3, 0x97, 0xDA, 0xE7, 0x0B, 0xE8, 0x2B, 0x3C, 0xCA, 0x47, 0x93, 0xF8, 0xEB, 0x2B, 0x6C, 0xDA, 0xC9];
+
+        let address = "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2";
+        let (prefix, addr_type, hash) = decode(address).unwrap();
+        assert_eq!(prefix, "bitcoincash");
+        assert_eq!(addr_type, AddressType::P2PKH);
+        assert_eq!(hash, data);
+
+        let address = "bchtest:pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t";
+        let (prefix, addr_type, hash) = decode(address).unwrap();
+        assert_eq!(prefix, "bchtest");
+        assert_eq!(addr_type, AddressType::P2SH);
+        assert_eq!(hash, data);
+
+        let address = "BITCOINCASH:QR6M7J9NJLDWWZLG9V7V53UNLR4JKMX6EYLEP8EKG2";
+        let (prefix, addr_type, hash) = decode(address).unwrap();
+        assert_eq!(prefix, "bitcoincash");
+        assert_eq!(addr_type, AddressType::P2PKH);
+        assert_eq!(hash, data);
+    }
+
+    #[test]
+    fn decode_with_expected_prefix_test() {
+        let data = [0xF5, 0xBF, 0x48, 0xB3, 0x97, 0xDA, 0xE7, 0x0B, 0xE8, 0x2B, 0x3C, 0xCA, 0x47, 0x93, 0xF8, 0xEB, 0x2B, 0x6C, 0xDA, 0xC9];
+
+        for &(address, prefix, expected_type) in &[
+            ("qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2", "bitcoincash", AddressType::P2PKH),
+            ("QR6M7J9NJLDWWZLG9V7V53UNLR4JKMX6EYLEP8EKG2", "bitcoincash", AddressType::P2PKH),
+            ("bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2", "bitcoincash", AddressType::P2PKH),
+            ("BITCOINCASH:QR6M7J9NJLDWWZLG9V7V53UNLR4JKMX6EYLEP8EKG2", "bitcoincash", AddressType::P2PKH),
+            ("bchtest:pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t", "BCHTEST", AddressType::P2SH),
+            ("pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t", "bchtest", AddressType::P2SH),
+        ] {
+            let (addr_type, hash) = decode_with_expected_prefix(address, prefix).unwrap();
+            assert_eq!(addr_type, expected_type);
+            assert_eq!(hash, data);
+        }
+
+        match decode_with_expected_prefix("qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2", "bchtest") {
+            Err(Error::InvalidChecksum(_)) => {},
+            result => panic!("{:?}", result),
+        }
+        match decode_with_expected_prefix("bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2", "bchtest") {
+            Err(Error::InvalidAddressFormat(_)) => {},
+            result => panic!("{:?}", result),
+        }
+        assert!(decode_with_expected_prefix("a:b:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2", "a").is_err());
+    }
+}
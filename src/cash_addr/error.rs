@@ -0,0 +1,73 @@
+// `failure_derive` expands its impls inside an anonymous const.
+#![allow(non_local_definitions)]
+
+use std::result;
+
+use failure::Fail;
+
+/// Alias of `Result` used by cash_addr.
+pub type Result<T> = result::Result<T, Error>;
+
+/// Errors
+#[derive(Debug, Fail)]
+pub enum Error {
+    /// Invalid address format.
+    /// # Arguments
+    /// * Address.
+    #[fail(display = "Invalid address format: {}", 0)]
+    InvalidAddressFormat(String),
+
+    /// Invalid bech32 char.
+    /// # Arguments
+    /// * Character.
+    #[fail(display = "Invalid bech32 char: {}", 0)]
+    InvalidChar(char),
+
+    /// Mixed case.
+    #[fail(display = "Mixed case")]
+    MixedCase,
+
+    /// Invalid hash size.
+    /// # Arguments
+    /// * Hash length in bytes.
+    #[fail(display = "Invalid hash size: {} bytes", 0)]
+    InvalidHashSize(usize),
+
+    /// Invalid hash size bits.
+    /// # Arguments
+    /// * Hash size bits.
+    #[fail(display = "Invalid hash size bits: {}", 0)]
+    InvalidHashSizeBits(u8),
+
+    /// Hash size mismatch.
+    /// # Arguments
+    /// * Hash length.
+    #[fail(display = "Hash size mismatch: {}", 0)]
+    HashSizeMismatch(usize),
+
+    /// Invalid checksum
+    /// # Arguments
+    /// * Address.
+    #[fail(display = "Invalid checksum: {}", 0)]
+    InvalidChecksum(String),
+
+    /// undefined address type bit
+    /// # Arguments
+    /// * Type bit.
+    #[fail(display = "Invalid address type bit: {}", 0)]
+    InvalidAddressTypeBit(u8),
+
+    /// Non-zero or too long padding after the payload.
+    #[fail(display = "Invalid padding")]
+    InvalidPadding,
+
+    /// Output buffer is too small for the encoded address.
+    /// # Arguments
+    /// * Required length.
+    #[fail(display = "Buffer too small: {} bytes required", 0)]
+    BufferTooSmall(usize),
+
+    /// Codec compiled out of this build (the `cashaddr` feature is disabled).
+    #[fail(display = "Not supported without the `cashaddr` feature")]
+    NotSupported,
+}
@@ -0,0 +1,139 @@
+//! Layout of cash_addr addresses, shared by the codec and by builds without it.
+
+use std::borrow::Cow;
+
+use super::error::{Error, Result};
+
+/// Address type
+/// The discriminants are the type bits as placed in the version byte; `Other` takes the reserved bit 7,
+/// which no valid version byte has, so its encodings never collide with the standard types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize), borsh(use_discriminant = true))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq), derive(Debug)))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[repr(u8)]
+pub enum AddressType {
+    /// P2PKH address.
+    P2PKH = 0,
+    /// P2SH address.
+    P2SH = 8,
+    /// Type field the spec does not assign, e.g. used by an experimental protocol.
+    /// Holds the type field of the version byte, 2 to 15. Converters accept it only for the formats
+    /// it is registered for with `Converter::add_type_bits`.
+    Other(u8) = 0x80,
+}
+
+impl AddressType {
+    /// Type field of the cash_addr version byte (its bits 3 to 6): 0 for P2PKH, 1 for P2SH.
+    /// # Returns
+    /// * Type field.
+    /// # Example
+    /// ```
+    /// use bch_addr::AddressType;
+    ///
+    /// assert_eq!(AddressType::P2SH.type_bits(), 1);
+    /// assert_eq!(AddressType::Other(15).type_bits(), 15);
+    /// ```
+    pub const fn type_bits(self) -> u8 {
+        match self {
+            AddressType::P2PKH       => 0,
+            AddressType::P2SH        => 1,
+            AddressType::Other(bits) => bits,
+        }
+    }
+
+    /// Address type of a type field; the inverse of `type_bits`.
+    /// # Arguments
+    /// * `bits` - Type field of the version byte.
+    /// # Returns
+    /// * `P2PKH` for 0, `P2SH` for 1, `Other` otherwise.
+    /// # Example
+    /// ```
+    /// use bch_addr::AddressType;
+    ///
+    /// assert_eq!(AddressType::from_type_bits(0), AddressType::P2PKH);
+    /// assert_eq!(AddressType::from_type_bits(2), AddressType::Other(2));
+    /// ```
+    pub const fn from_type_bits(bits: u8) -> AddressType {
+        match bits {
+            0 => AddressType::P2PKH,
+            1 => AddressType::P2SH,
+            _ => AddressType::Other(bits),
+        }
+    }
+
+    /// Check that the type field fits the version byte and, for `Other`, is not one of the assigned ones,
+    /// which would encode the same as P2PKH or P2SH.
+    pub(crate) const fn is_well_formed(self) -> bool {
+        match self {
+            AddressType::Other(bits) => bits > 1 && bits <= MAX_TYPE_BITS,
+            _                        => true,
+        }
+    }
+
+    /// Type field in place in the version byte.
+    pub(crate) const fn version_bits(self) -> u8 {
+        self.type_bits() << 3
+    }
+}
+
+/// Largest type field: four bits, below the reserved bit 7 of the version byte.
+pub(crate) const MAX_TYPE_BITS: u8 = 15;
+
+pub(crate) const SEPARATOR: char = ':';
+
+/// Number of checksum characters ending the body.
+pub const CHECKSUM_LEN: usize = 8;
+
+/// Maximum length of the part following the separator (version byte, 512-bit hash and checksum).
+pub const MAX_BODY_LEN: usize = body_len(64);
+
+pub(crate) const fn body_len(hash_len: usize) -> usize {
+    ((hash_len + 1) * 8).div_ceil(5) + CHECKSUM_LEN
+}
+
+/// Split `address` into prefix and body.
+pub(crate) fn split(address: &str) -> Result<(&str, &str)> {
+    let mut pieces = address.split(SEPARATOR);
+    match (pieces.next(), pieces.next(), pieces.next()) {
+        (Some(prefix), Some(body), None) => Ok((prefix, body)),
+        _ => Err(Error::InvalidAddressFormat(address.to_string())),
+    }
+}
+
+/// Lowercase `prefix`, borrowing it when it is already lowercase.
+pub(crate) fn normalize_prefix(prefix: &str) -> Cow<'_, str> {
+    if prefix.bytes().any(|c| c.is_ascii_uppercase()) {
+        Cow::Owned(prefix.to_ascii_lowercase())
+    } else {
+        Cow::Borrowed(prefix)
+    }
+}
+
+/// Version byte of the payload: address type bits plus hash size bits.
+pub(crate) fn version_byte(address_type: AddressType, hash: &[u8]) -> Result<u8> {
+    if !address_type.is_well_formed() {
+        return Err(Error::InvalidAddressTypeBit(address_type.type_bits()));
+    }
+    Ok(address_type.version_bits() + get_hash_size_bits(hash)?)
+}
+
+fn get_hash_size_bits(hash: &[u8]) -> Result<u8> {
+    match hash.len() * 8 {
+        160 => Ok(0),
+        192 => Ok(1),
+        224 => Ok(2),
+        256 => Ok(3),
+        320 => Ok(4),
+        384 => Ok(5),
+        448 => Ok(6),
+        512 => Ok(7),
+        _   => Err(Error::InvalidHashSize(hash.len()))
+    }
+}
+
+/// Hash length in bytes given by the size bits of `version_byte`.
+pub(crate) const fn hash_size(version_byte: u8) -> usize {
+    const SIZES: [usize; 8] = [20, 24, 28, 32, 40, 48, 56, 64];
+    SIZES[(version_byte & 7) as usize]
+}
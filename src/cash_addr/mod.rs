@@ -0,0 +1,47 @@
+//! cash_addr codec.
+//! Originally published as the `cash_addr` crate, vendored here so the converter
+//! can encode into caller-provided buffers.
+//! Without the `cashaddr` feature only `AddressType` and the constants remain usable;
+//! the functions fail with `Error::NotSupported` and `is_valid` is always `false`.
+//! # Example
+//! ```rust
+//! # #[cfg(feature = "cashaddr")]
+//! # {
+//! use bch_addr::cash_addr::{encode, decode, AddressType};
+//! 
+//! let data = [0xF5, 0xBF, 0x48, 0xB3, 0x97, 0xDA, 0xE7, 0x0B, 0xE8, 0x2B, 0x3C, 0xCA, 0x47, 0x93, 0xF8, 0xEB, 0x2B, 0x6C, 0xDA, 0xC9];
+//! let prefix = "bitcoincash";
+//! let addr_type = AddressType::P2PKH;
+//! 
+//! let address = encode(prefix, addr_type, &data).unwrap();
+//! assert_eq!(address, "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2");
+//! 
+//! let (prefix, addr_type, hash) = decode(&address).unwrap();
+//! assert_eq!(prefix, "bitcoincash");
+//! assert_eq!(addr_type, AddressType::P2PKH);
+//! assert_eq!(hash, data);
+//! # }
+//! ```
+
+mod error;
+mod format;
+#[cfg(feature = "cashaddr")]
+mod converter;
+#[cfg(feature = "cashaddr")]
+mod base32;
+#[cfg(not(feature = "cashaddr"))]
+mod unsupported;
+
+pub use self::error::{Error, Result};
+pub use self::format::{AddressType, CHECKSUM_LEN, MAX_BODY_LEN};
+pub(crate) use self::format::{split, normalize_prefix, version_byte, hash_size, body_len};
+#[cfg(feature = "cashaddr")]
+pub use self::converter::{encode, encode_into, decode, decode_with_expected_prefix, is_valid, verify_checksum, compute_cash_checksum};
+#[cfg(feature = "cashaddr")]
+pub(crate) use self::base32::decode_char;
+#[cfg(feature = "cashaddr")]
+pub(crate) use self::converter::{Body, Checksum};
+#[cfg(not(feature = "cashaddr"))]
+pub use self::unsupported::{encode, encode_into, decode, decode_with_expected_prefix, is_valid, verify_checksum, compute_cash_checksum};
+#[cfg(not(feature = "cashaddr"))]
+pub(crate) use self::unsupported::{decode_char, Body, Checksum};
@@ -0,0 +1,78 @@
+//! Stand-ins for the codec in builds without the `cashaddr` feature, so callers compile unchanged.
+
+use super::error::{Error, Result};
+use super::format::{AddressType, CHECKSUM_LEN};
+
+/// Always `Err(Error::NotSupported)`.
+pub fn encode(_prefix: &str, _address_type: AddressType, _hash: &[u8]) -> Result<String> {
+    Err(Error::NotSupported)
+}
+
+/// Always `Err(Error::NotSupported)`.
+pub fn encode_into(_prefix: &str, _address_type: AddressType, _hash: &[u8], _out: &mut [u8]) -> Result<usize> {
+    Err(Error::NotSupported)
+}
+
+/// Always `Err(Error::NotSupported)`.
+pub fn compute_cash_checksum(_prefix: &str, _version_byte: u8, _payload: &[u8]) -> Result<[u8; CHECKSUM_LEN]> {
+    Err(Error::NotSupported)
+}
+
+/// Always `Err(Error::NotSupported)`.
+pub fn decode(_address: &str) -> Result<(String, AddressType, Vec<u8>)> {
+    Err(Error::NotSupported)
+}
+
+/// Always `Err(Error::NotSupported)`.
+pub fn decode_with_expected_prefix(_address: &str, _prefix: &str) -> Result<(AddressType, Vec<u8>)> {
+    Err(Error::NotSupported)
+}
+
+/// Always `Err(Error::NotSupported)`.
+pub fn verify_checksum(_address: &str) -> Result<()> {
+    Err(Error::NotSupported)
+}
+
+/// Always `false`.
+pub const fn is_valid(_address: &str) -> bool {
+    false
+}
+
+/// No character decodes, so prefix-less bodies and vanity patterns are never cash_addr.
+pub(crate) const fn decode_char(_c: u8) -> Option<u8> {
+    None
+}
+
+/// Never constructed: `Body::decode` always fails.
+pub(crate) enum Body {}
+
+impl Body {
+    pub(crate) fn decode(_body: &str) -> Result<Body> {
+        Err(Error::NotSupported)
+    }
+
+    pub(crate) fn verify(&self, _prefix: &str) -> bool {
+        match *self {}
+    }
+
+    pub(crate) fn verify_with(&self, _checksum: Checksum) -> bool {
+        match *self {}
+    }
+
+    pub(crate) fn decode_into(&self, _hash: &mut Vec<u8>) -> Result<AddressType> {
+        match *self {}
+    }
+
+    pub(crate) fn classify(&self) -> Result<AddressType> {
+        match *self {}
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Checksum;
+
+impl Checksum {
+    pub(crate) const fn new(_prefix: &str) -> Checksum {
+        Checksum
+    }
+}
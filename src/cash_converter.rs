@@ -1,91 +1,282 @@
-use std::collections::HashMap;
-
-use cash_addr;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 
+use super::cash_addr;
 use super::AddressType;
 use super::AddressFormat;
 use super::Network;
 use super::error::{Error, Result};
+use super::trace::{self, Decoder};
+
+/// Index into `CashConverter::formats`, resolved once when a format is registered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct FormatId(usize);
+
+const CASH_ADDR_FORMAT_ID: FormatId = FormatId(0);
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 struct PrefixDetails {
-    format: AddressFormat,
+    format: FormatId,
     network: Network,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct CashConverter {
-    prefix_list: HashMap<String, PrefixDetails>,
-    prefix_inv_list: HashMap<PrefixDetails, String>,
+    formats: Vec<AddressFormat>,
+    format_ids: HashMap<String, FormatId>,
+    prefix_list: HashMap<Cow<'static, str>, PrefixDetails>,
+    prefix_inv_list: HashMap<PrefixDetails, Cow<'static, str>>,
+    /// Prefixes tried against prefix-less addresses, in registration order,
+    /// with the checksum state after feeding each prefix.
+    prefix_checksums: Vec<(Cow<'static, str>, PrefixDetails, cash_addr::Checksum)>,
+    /// Prefixes accepted in place of a registered one, never used to build addresses.
+    aliases: HashMap<String, String>,
+    /// Type fields accepted as `AddressType::Other` by each format.
+    type_bits: HashSet<(FormatId, u8)>,
 }
 
 const SEPARATOR: char = ':';
 
 impl CashConverter {
     pub fn new() -> CashConverter {
-        let prefix_list = [
-            ("bitcoincash".to_string(), PrefixDetails {
-                format: AddressFormat::CashAddr,
-                network: Network::Mainnet,
-            }),
-            ("bchtest".to_string(), PrefixDetails {
-                format: AddressFormat::CashAddr,
-                network: Network::Testnet,
-            }),
-            ("bchreg".to_string(), PrefixDetails {
-                format: AddressFormat::CashAddr,
-                network: Network::Regtest,
-            }),
-        ].iter().cloned().collect::<HashMap<String, PrefixDetails>>();
-
-        CashConverter {
-            prefix_inv_list: prefix_list.iter().map(|el| (el.1.clone(), el.0.clone())).collect(),
-            prefix_list,
+        let mut converter = CashConverter {
+            formats: vec![AddressFormat::CashAddr],
+            format_ids: HashMap::new(),
+            prefix_list: HashMap::new(),
+            prefix_inv_list: HashMap::new(),
+            prefix_checksums: Vec::new(),
+            aliases: HashMap::new(),
+            type_bits: HashSet::new(),
+        };
+
+        for &(prefix, network) in &[
+            ("bitcoincash", Network::Mainnet),
+            ("bchtest", Network::Testnet),
+            ("bchreg", Network::Regtest),
+        ] {
+            converter.insert_prefix(Cow::Borrowed(prefix), PrefixDetails {
+                format: CASH_ADDR_FORMAT_ID,
+                network,
+            });
         }
+        converter
     }
 
     pub fn add_prefixes(mut self, prefixes: &[(&str, Network)], format_name: &str) -> CashConverter {
-        self.prefix_list.extend(prefixes.iter().map(|p| {
-            (p.0.to_string(), PrefixDetails {
-                format: AddressFormat::Other(format_name.to_string()),
-                network: p.1,
-            })
-        }));
-        self.prefix_inv_list.extend(prefixes.iter().map(|p| {
-            (PrefixDetails {
-                format: AddressFormat::Other(format_name.to_string()),
-                network: p.1,
-            }, p.0.to_string())
-        }));
+        let format = self.register_format(format_name);
+
+        for &(prefix, network) in prefixes {
+            self.insert_prefix(Cow::Owned(cash_addr::normalize_prefix(prefix).into_owned()), PrefixDetails { format, network });
+        }
+        self
+    }
+
+    /// Accept `alias` in place of `prefix`; the checksum is still computed with `prefix`.
+    /// `prefix` is looked up when parsing, so it can be registered later.
+    pub fn add_alias(mut self, alias: &str, prefix: &str) -> CashConverter {
+        self.aliases.insert(cash_addr::normalize_prefix(alias).into_owned(), cash_addr::normalize_prefix(prefix).into_owned());
         self
     }
 
+    /// Accept `AddressType::Other` with the type fields `type_bits` in `format`.
+    /// A format given by name is registered if it is not yet. Legacy has no type field and is left unchanged.
+    /// Type fields other than 2 to 15 are ignored: 0 and 1 are P2PKH and P2SH, larger ones do not fit the version byte.
+    pub fn add_type_bits(mut self, format: &AddressFormat, type_bits: &[u8]) -> CashConverter {
+        let format = match format {
+            AddressFormat::CashAddr    => CASH_ADDR_FORMAT_ID,
+            AddressFormat::Other(name) => self.register_format(name),
+            AddressFormat::Legacy      => return self,
+        };
+        self.type_bits.extend(type_bits.iter()
+            .filter(|&&bits| AddressType::Other(bits).is_well_formed())
+            .map(|&bits| (format, bits)));
+        self
+    }
+
+    /// Check that `format` accepts `addr_type`; P2PKH and P2SH always are.
+    fn check_type(&self, format: FormatId, addr_type: AddressType) -> Result<()> {
+        match addr_type {
+            AddressType::Other(bits) if !self.type_bits.contains(&(format, bits)) => Err(Error::UnsupportedAddressType(addr_type)),
+            _ => Ok(()),
+        }
+    }
+
+    /// `true` if addresses of `format`, or of any format if `None`, can have the type field `bits`.
+    pub fn accepts_type_bits(&self, format: Option<&AddressFormat>, bits: u8) -> bool {
+        let addr_type = AddressType::from_type_bits(bits);
+        match format {
+            Some(format) => self.format_id(format).is_some_and(|id| self.check_type(id, addr_type).is_ok()),
+            None         => (0..self.formats.len()).any(|id| self.check_type(FormatId(id), addr_type).is_ok()),
+        }
+    }
+
+    fn insert_prefix(&mut self, prefix: Cow<'static, str>, details: PrefixDetails) {
+        let checksum = cash_addr::Checksum::new(&prefix);
+        match self.prefix_checksums.iter_mut().find(|(p, _, _)| *p == prefix) {
+            Some(entry) => *entry = (prefix.clone(), details, checksum),
+            None        => self.prefix_checksums.push((prefix.clone(), details, checksum)),
+        }
+
+        self.prefix_list.insert(prefix.clone(), details);
+        self.prefix_inv_list.insert(details, prefix);
+    }
+
+    fn register_format(&mut self, format_name: &str) -> FormatId {
+        if let Some(&id) = self.format_ids.get(format_name) {
+            return id;
+        }
+
+        let id = FormatId(self.formats.len());
+        self.formats.push(AddressFormat::Other(format_name.to_string()));
+        self.format_ids.insert(format_name.to_string(), id);
+        id
+    }
+
+    fn format_id(&self, format: &AddressFormat) -> Option<FormatId> {
+        match format {
+            AddressFormat::CashAddr    => Some(CASH_ADDR_FORMAT_ID),
+            AddressFormat::Other(name) => self.format_ids.get(name).cloned(),
+            AddressFormat::Legacy      => None,
+        }
+    }
+
+    /// cash_addr and every registered user-defined format, in registration order.
+    pub fn formats(&self) -> &[AddressFormat] {
+        &self.formats
+    }
+
+    /// Registered prefixes with their formats and networks, in registration order.
+    pub fn prefixes(&self) -> impl Iterator<Item = (&str, &AddressFormat, Network)> {
+        self.prefix_checksums.iter().map(move |(prefix, details, _)| (prefix.as_ref(), &self.formats[details.format.0], details.network))
+    }
+
+    /// Aliases and the prefixes they stand for.
+    pub fn aliases(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.aliases.iter().map(|(alias, prefix)| (alias.as_str(), prefix.as_str()))
+    }
+
+    /// Format and network of a registered prefix or alias, matched case-insensitively.
+    pub fn prefix_details(&self, prefix: &str) -> Option<(&AddressFormat, Network)> {
+        self.lookup(prefix).map(|(_, details)| (&self.formats[details.format.0], details.network))
+    }
+
+    /// `true` if the prefix of `addr` is an alias rather than a registered prefix.
+    pub fn has_alias(&self, addr: &str) -> bool {
+        match cash_addr::split(addr) {
+            Ok((prefix, _)) => self.lookup(prefix).is_some_and(|(registered, _)| !registered.eq_ignore_ascii_case(prefix)),
+            Err(_)          => false,
+        }
+    }
+
+    /// Registered prefix `prefix` stands for, itself or the target of an alias, and its details.
+    /// Registered prefixes take precedence over aliases.
+    fn lookup(&self, prefix: &str) -> Option<(&str, PrefixDetails)> {
+        let prefix = cash_addr::normalize_prefix(prefix);
+        let registered = match self.prefix_list.get_key_value(prefix.as_ref()) {
+            Some(found) => Some(found),
+            None        => self.aliases.get(prefix.as_ref()).and_then(|target| self.prefix_list.get_key_value(target.as_str())),
+        };
+        registered.map(|(prefix, details)| (prefix.as_ref(), *details))
+    }
+
     pub fn parse(&self, addr: &str) -> Result<(AddressFormat, Network, AddressType, Vec<u8>)> {
+        let mut hash = Vec::new();
+        let (format, network, addr_type) = self.parse_into(addr, &mut hash)?;
+
+        Ok((format.clone(), network, addr_type, hash))
+    }
+
+    /// Same as `parse` but writes the hash into `hash`, reusing its allocation.
+    pub fn parse_into(&self, addr: &str, hash: &mut Vec<u8>) -> Result<(&AddressFormat, Network, AddressType)> {
+        let (prefix_details, body) = self.verify(addr)?;
+        let addr_type = body.decode_into(hash)?;
+        self.check_type(prefix_details.format, addr_type)?;
+
+        Ok((&self.formats[prefix_details.format.0], prefix_details.network, addr_type))
+    }
+
+    /// Same as `parse` but skips collecting the hash.
+    pub fn classify(&self, addr: &str) -> Result<(&AddressFormat, Network, AddressType)> {
+        let (prefix_details, body) = self.verify(addr)?;
+        let addr_type = body.classify()?;
+        self.check_type(prefix_details.format, addr_type)?;
+
+        Ok((&self.formats[prefix_details.format.0], prefix_details.network, addr_type))
+    }
+
+    /// Check the checksum of `addr` against its own prefix, registered or not (the prefix it stands for if it is an alias),
+    /// or against the registered prefixes if it has none.
+    pub fn verify_checksum(&self, addr: &str) -> Result<()> {
+        if self.has_alias(addr) {
+            return self.verify(addr).map(|_| ());
+        }
         if addr.contains(SEPARATOR) {
-            return Ok(self.parse_with_prefix(addr)?)
+            return Ok(cash_addr::verify_checksum(addr)?);
         }
+        self.verify(addr).map(|_| ())
+    }
 
-        for prefix in self.prefix_list.keys() {
-            let addr = format!("{}{}{}", prefix, SEPARATOR, addr);
-            match self.parse_with_prefix(&addr) {
-                Ok(ret) => return Ok(ret),
-                Err(_)  => continue,
+    /// Decode the body of `addr` and find the registered prefix its checksum matches.
+    /// Without a prefix in `addr`, every registered prefix is tried against the same decoded body,
+    /// in registration order so the default prefixes are tried first.
+    fn verify(&self, addr: &str) -> Result<(PrefixDetails, cash_addr::Body)> {
+        if addr.contains(SEPARATOR) {
+            let (prefix, body) = cash_addr::split(addr)?;
+            let (registered, prefix_details) = self.lookup(prefix)
+                .ok_or_else(|| Error::UnknownCashPrefix(prefix.to_string()))?;
+            let body = cash_addr::Body::decode(body)?;
+            if !body.verify(registered) {
+                return Err(cash_addr::Error::InvalidChecksum(addr.to_string()).into());
             }
-        }
 
-        Err(Error::InvalidAddress(addr.to_string()))
-    }
+            trace::decoder_matched(Decoder::CashAddr);
+            return Ok((prefix_details, body))
+        }
 
-    fn parse_with_prefix(&self, addr: &str) -> Result<(AddressFormat, Network, AddressType, Vec<u8>)> {
-        let (prefix, addr_type, hash) = cash_addr::decode(addr)?;
-        let prefix_details = self.prefix_list.get(&prefix).ok_or_else(|| Error::UnknownCashPrefix(prefix))?;
+        let body = cash_addr::Body::decode(addr).map_err(|_| Error::InvalidAddress(addr.to_string()))?;
+        let (_, prefix_details, _) = self.prefix_checksums.iter()
+            .find(|(prefix, _, checksum)| {
+                let matched = body.verify_with(*checksum);
+                trace::prefix_trial(prefix, matched);
+                matched
+            })
+            .ok_or_else(|| Error::InvalidAddress(addr.to_string()))?;
 
-        Ok((prefix_details.format.clone(), prefix_details.network, addr_type, hash))
+        trace::decoder_matched(Decoder::CashAddr);
+        Ok((*prefix_details, body))
     }
 
     pub fn build(&self, format: &AddressFormat, network: Network, addr_type: AddressType, hash: &[u8]) -> Result<String> {
-        let prefix = self.prefix_inv_list.get(&PrefixDetails{format: format.clone(), network})
-            .ok_or_else(|| Error::UnknownCashFormat(format.clone(), network))?;
+        let prefix = self.prefix_for_type(format, network, addr_type)?;
         Ok(cash_addr::encode(prefix, addr_type, hash)?)
     }
+
+    pub fn build_into(&self, format: &AddressFormat, network: Network, addr_type: AddressType, hash: &[u8], out: &mut [u8]) -> Result<usize> {
+        let prefix = self.prefix_for_type(format, network, addr_type)?;
+        Ok(cash_addr::encode_into(prefix, addr_type, hash, out)?)
+    }
+
+    /// Same as `prefix` but also checks that `format` accepts `addr_type`.
+    fn prefix_for_type(&self, format: &AddressFormat, network: Network, addr_type: AddressType) -> Result<&str> {
+        let prefix = self.prefix(format, network)?;
+        if let Some(format) = self.format_id(format) {
+            self.check_type(format, addr_type)?;
+        }
+        Ok(prefix)
+    }
+
+    /// Registered prefix of `format` on `network`, the last one registered if there are several.
+    pub fn find_prefix(&self, format: &AddressFormat, network: Network) -> Option<&str> {
+        self.format_id(format)
+            .and_then(|format| self.prefix_inv_list.get(&PrefixDetails { format, network }))
+            .map(|prefix| prefix.as_ref())
+    }
+
+    pub fn prefix(&self, format: &AddressFormat, network: Network) -> Result<&str> {
+        self.find_prefix(format, network)
+            .ok_or_else(|| {
+                trace::unknown_format(format, network);
+                Error::UnknownCashFormat(format.clone(), network)
+            })
+    }
 }
@@ -0,0 +1,137 @@
+//! `clap` value parser validating address arguments.
+//! Enabled by the `clap` feature.
+
+use std::ffi::OsStr;
+use std::sync::Arc;
+
+use ::clap::builder::TypedValueParser;
+use ::clap::error::ErrorKind;
+use ::clap::{Arg, Command, Error};
+
+use super::{Converter, AddressFormat, Network};
+
+/// Value parser accepting valid addresses, optionally of a given network and format only.
+/// The argument's value is the address as given.
+/// # Example
+/// ```
+/// use bch_addr::{AddressFormat, Network};
+/// use bch_addr::clap::AddressParser;
+/// use clap::{Arg, Command};
+///
+/// let cmd = Command::new("pay").arg(
+///     Arg::new("to").value_parser(AddressParser::new().network(Network::Mainnet).format(AddressFormat::CashAddr)),
+/// );
+///
+/// let matches = cmd.clone().try_get_matches_from(["pay", "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk"]).unwrap();
+/// assert_eq!(matches.get_one::<String>("to").unwrap(), "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+///
+/// let err = cmd.try_get_matches_from(["pay", "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR"]).unwrap_err();
+/// assert!(err.to_string().contains("expected a CashAddr address"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct AddressParser {
+    converter: Arc<Converter>,
+    network: Option<Network>,
+    format: Option<AddressFormat>,
+}
+
+impl Default for AddressParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AddressParser {
+    /// Accept any address in a built-in format.
+    /// # Returns
+    /// * Value parser.
+    pub fn new() -> AddressParser {
+        AddressParser::with_converter(Arc::new(Converter::new()))
+    }
+
+    /// Accept any address `converter` can parse, e.g. with user-defined prefixes.
+    /// # Arguments
+    /// * `converter` - Converter providing the prefix configuration.
+    /// # Returns
+    /// * Value parser.
+    pub fn with_converter(converter: Arc<Converter>) -> AddressParser {
+        AddressParser {
+            converter,
+            network: None,
+            format: None,
+        }
+    }
+
+    /// Only accept addresses of `network`.
+    /// # Arguments
+    /// * `network` - Address network.
+    /// # Returns
+    /// * Value parser.
+    pub fn network(mut self, network: Network) -> AddressParser {
+        self.network = Some(network);
+        self
+    }
+
+    /// Only accept addresses in `format`.
+    /// # Arguments
+    /// * `format` - Address format.
+    /// # Returns
+    /// * Value parser.
+    pub fn format(mut self, format: AddressFormat) -> AddressParser {
+        self.format = Some(format);
+        self
+    }
+
+    fn check(&self, addr: &str) -> Result<(), String> {
+        let (format, network, ..) = self.converter.parse(addr).map_err(|err| err.to_string())?;
+        match (&self.network, &self.format) {
+            (Some(expected), _) if *expected != network => Err(format!("expected a {:?} address, got {:?}", expected, network)),
+            (_, Some(expected)) if *expected != format  => Err(format!("expected a {:?} address, got {:?}", expected, format)),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl TypedValueParser for AddressParser {
+    type Value = String;
+
+    fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<String, Error> {
+        let addr = value.to_str().ok_or_else(|| Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd))?;
+        self.check(addr).map_err(|reason| {
+            let arg = arg.map_or_else(|| "...".to_string(), ToString::to_string);
+            Error::raw(ErrorKind::ValueValidation, format!("invalid value '{}' for '{}': {}\n", addr, arg, reason)).with_cmd(cmd)
+        })?;
+        Ok(addr.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn address_parser_test() {
+        let converter = Arc::new(Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr"));
+        let slp = AddressFormat::Other("SLPAddr".to_string());
+        let cmd = Command::new("test")
+            .arg(Arg::new("any").long("any").value_parser(AddressParser::with_converter(converter.clone())))
+            .arg(Arg::new("testnet").long("testnet").value_parser(AddressParser::new().network(Network::Testnet)))
+            .arg(Arg::new("slp").long("slp").value_parser(AddressParser::with_converter(converter).format(slp)));
+        let parse = |args: &[&str]| cmd.clone().try_get_matches_from([&["test"], args].concat()).map_err(|err| err.to_string());
+
+        let matches = parse(&[
+            "--any", "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg",
+            "--testnet", "bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu",
+            "--slp", "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg",
+        ]).unwrap();
+        assert_eq!(matches.get_one::<String>("testnet").unwrap(), "bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu");
+
+        assert_eq!(
+            parse(&["--testnet", "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR"]).unwrap_err(),
+            "error: invalid value '1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR' for '--testnet <testnet>': expected a Testnet address, got Mainnet\n",
+        );
+        assert!(parse(&["--slp", "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk"]).unwrap_err()
+            .contains("expected a Other(\"SLPAddr\") address, got CashAddr"));
+        assert!(parse(&["--any", "bitcoincash:bad"]).unwrap_err().contains("invalid address: bitcoincash:bad"));
+    }
+}
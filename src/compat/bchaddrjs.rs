@@ -0,0 +1,258 @@
+//! Free functions with the names and semantics of [bchaddrjs](https://github.com/bitcoincashjs/bchaddrjs).
+//!
+//! Unlike `Converter`, this follows bchaddrjs exactly:
+//! * Bitpay addresses (legacy with version bytes 28 and 40, `C...` and `H...`) are accepted and can be produced.
+//!   Testnet Bitpay addresses are the same as legacy ones and are detected as legacy.
+//! * Only the `bitcoincash`, `bchtest` and `bchreg` prefixes are known, and `bchreg` addresses are testnet addresses.
+//!   Cash addresses are always written with the `bitcoincash` or `bchtest` prefix.
+//! * The `is_*` functions other than `is_valid_address` fail on invalid addresses, as they throw in bchaddrjs.
+//!
+//! Every failure is `Error::InvalidAddress`, bchaddrjs's `InvalidAddressError`.
+//! # Example
+//! ```
+//! use bch_addr::compat::bchaddrjs::{self, Format};
+//!
+//! assert_eq!(bchaddrjs::to_cash_address("CScMwvXjdooDnGevHgfHjGWFi9cjk75Aaj").unwrap(), "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+//! assert_eq!(bchaddrjs::to_bitpay_address("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap(), "CScMwvXjdooDnGevHgfHjGWFi9cjk75Aaj");
+//! assert_eq!(bchaddrjs::detect_address_format("qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap(), Format::Cashaddr);
+//! assert!(!bchaddrjs::is_valid_address("nope"));
+//! assert!(bchaddrjs::is_legacy_address("nope").is_err());
+//! ```
+
+use crate::{AddressType, Network};
+use crate::{cash_addr, legacy};
+use crate::error::{Error, Result};
+use crate::hash::HASH160_LEN;
+
+/// Prefixes bchaddrjs tries, in order, for cash addresses without one.
+const PREFIXES: [(&str, Network); 3] = [
+    ("bitcoincash", Network::Mainnet),
+    ("bchtest", Network::Testnet),
+    ("bchreg", Network::Testnet),
+];
+
+/// Address format, bchaddrjs's `Format`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// Base58Check with the node's version bytes.
+    Legacy,
+    /// Base58Check with the version bytes Bitpay used before cash_addr.
+    Bitpay,
+    /// cash_addr.
+    Cashaddr,
+}
+
+/// Address decoded by bchaddrjs's `decodeAddress`.
+struct Decoded {
+    format: Format,
+    network: Network,
+    addr_type: AddressType,
+    hash: Vec<u8>,
+}
+
+/// Detect the format of an address, `detectAddressFormat`.
+/// # Arguments
+/// * `address` - Address in any format.
+/// # Returns
+/// * Format.
+pub fn detect_address_format(address: &str) -> Result<Format> {
+    decode(address).map(|decoded| decoded.format)
+}
+
+/// Detect the network of an address, `detectAddressNetwork`.
+/// # Arguments
+/// * `address` - Address in any format.
+/// # Returns
+/// * `Network::Mainnet` or `Network::Testnet`, never `Network::Regtest`.
+pub fn detect_address_network(address: &str) -> Result<Network> {
+    decode(address).map(|decoded| decoded.network)
+}
+
+/// Detect the type of an address, `detectAddressType`.
+/// # Arguments
+/// * `address` - Address in any format.
+/// # Returns
+/// * Type.
+pub fn detect_address_type(address: &str) -> Result<AddressType> {
+    decode(address).map(|decoded| decoded.addr_type)
+}
+
+/// Convert an address to legacy format, `toLegacyAddress`.
+/// # Arguments
+/// * `address` - Address in any format.
+/// # Returns
+/// * Legacy address.
+pub fn to_legacy_address(address: &str) -> Result<String> {
+    let decoded = decode(address)?;
+    if decoded.format == Format::Legacy {
+        return Ok(address.to_string());
+    }
+    Ok(encode_base58(Format::Legacy, &decoded))
+}
+
+/// Convert an address to Bitpay format, `toBitpayAddress`.
+/// # Arguments
+/// * `address` - Address in any format.
+/// # Returns
+/// * Bitpay address.
+pub fn to_bitpay_address(address: &str) -> Result<String> {
+    let decoded = decode(address)?;
+    if decoded.format == Format::Bitpay {
+        return Ok(address.to_string());
+    }
+    Ok(encode_base58(Format::Bitpay, &decoded))
+}
+
+/// Convert an address to cash_addr format, `toCashAddress`.
+/// # Arguments
+/// * `address` - Address in any format.
+/// # Returns
+/// * Lowercase cash address with the `bitcoincash` or `bchtest` prefix.
+pub fn to_cash_address(address: &str) -> Result<String> {
+    let decoded = decode(address)?;
+    let prefix = match decoded.network {
+        Network::Mainnet => "bitcoincash",
+        _                => "bchtest",
+    };
+    cash_addr::encode(prefix, decoded.addr_type, &decoded.hash).map_err(|_| invalid(address))
+}
+
+/// `isLegacyAddress`.
+pub fn is_legacy_address(address: &str) -> Result<bool> {
+    detect_address_format(address).map(|format| format == Format::Legacy)
+}
+
+/// `isBitpayAddress`.
+pub fn is_bitpay_address(address: &str) -> Result<bool> {
+    detect_address_format(address).map(|format| format == Format::Bitpay)
+}
+
+/// `isCashAddress`.
+pub fn is_cash_address(address: &str) -> Result<bool> {
+    detect_address_format(address).map(|format| format == Format::Cashaddr)
+}
+
+/// `isMainnetAddress`.
+pub fn is_mainnet_address(address: &str) -> Result<bool> {
+    detect_address_network(address).map(|network| network == Network::Mainnet)
+}
+
+/// `isTestnetAddress`, `true` for regtest addresses too.
+pub fn is_testnet_address(address: &str) -> Result<bool> {
+    detect_address_network(address).map(|network| network == Network::Testnet)
+}
+
+/// `isP2PKHAddress`.
+pub fn is_p2pkh_address(address: &str) -> Result<bool> {
+    detect_address_type(address).map(|addr_type| addr_type == AddressType::P2PKH)
+}
+
+/// `isP2SHAddress`.
+pub fn is_p2sh_address(address: &str) -> Result<bool> {
+    detect_address_type(address).map(|addr_type| addr_type == AddressType::P2SH)
+}
+
+/// `isValidAddress`.
+pub fn is_valid_address(address: &str) -> bool {
+    decode(address).is_ok()
+}
+
+/// `decodeAddress`: Base58 first, then cash_addr.
+fn decode(address: &str) -> Result<Decoded> {
+    decode_base58(address)
+        .or_else(|| decode_cash(address))
+        .ok_or_else(|| invalid(address))
+}
+
+fn decode_base58(address: &str) -> Option<Decoded> {
+    let (version_byte, hash) = legacy::decode(address).ok()?;
+    if hash.len() != HASH160_LEN {
+        return None;
+    }
+
+    let (format, network, addr_type) = match version_byte {
+        0   => (Format::Legacy, Network::Mainnet, AddressType::P2PKH),
+        5   => (Format::Legacy, Network::Mainnet, AddressType::P2SH),
+        111 => (Format::Legacy, Network::Testnet, AddressType::P2PKH),
+        196 => (Format::Legacy, Network::Testnet, AddressType::P2SH),
+        28  => (Format::Bitpay, Network::Mainnet, AddressType::P2PKH),
+        40  => (Format::Bitpay, Network::Mainnet, AddressType::P2SH),
+        _   => return None,
+    };
+    Some(Decoded { format, network, addr_type, hash })
+}
+
+fn decode_cash(address: &str) -> Option<Decoded> {
+    if address.contains(':') {
+        return decode_cash_with_prefix(address);
+    }
+    PREFIXES.iter().find_map(|(prefix, _)| decode_cash_with_prefix(&format!("{}:{}", prefix, address)))
+}
+
+fn decode_cash_with_prefix(address: &str) -> Option<Decoded> {
+    let (prefix, addr_type, hash) = cash_addr::decode(address).ok()?;
+    if let AddressType::Other(_) = addr_type {
+        return None;
+    }
+    let &(_, network) = PREFIXES.iter().find(|(known, _)| *known == prefix)?;
+    Some(Decoded { format: Format::Cashaddr, network, addr_type, hash })
+}
+
+fn encode_base58(format: Format, decoded: &Decoded) -> String {
+    let version_byte = match (format, decoded.network, decoded.addr_type) {
+        (Format::Bitpay, Network::Mainnet, AddressType::P2PKH) => 28,
+        (Format::Bitpay, Network::Mainnet, AddressType::P2SH)  => 40,
+        (_, Network::Mainnet, AddressType::P2PKH)              => 0,
+        (_, Network::Mainnet, AddressType::P2SH)               => 5,
+        (_, _, AddressType::P2PKH)                             => 111,
+        (_, _, AddressType::P2SH)                              => 196,
+        (_, _, AddressType::Other(_))                          => unreachable!("decoders only return P2PKH and P2SH"),
+    };
+    legacy::encode(version_byte, &decoded.hash)
+}
+
+fn invalid(address: &str) -> Error {
+    Error::InvalidAddress(address.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEGACY: &str = "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR";
+    const BITPAY: &str = "CScMwvXjdooDnGevHgfHjGWFi9cjk75Aaj";
+    const CASH: &str = "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk";
+
+    #[test]
+    fn bchaddrjs_test() {
+        for addr in &[LEGACY, BITPAY, CASH, "QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK"] {
+            assert_eq!(to_legacy_address(addr).unwrap(), LEGACY);
+            assert_eq!(to_bitpay_address(addr).unwrap(), BITPAY);
+            assert_eq!(to_cash_address(addr).unwrap(), CASH);
+            assert!(is_mainnet_address(addr).unwrap() && is_p2pkh_address(addr).unwrap());
+        }
+        assert_eq!(detect_address_format(BITPAY).unwrap(), Format::Bitpay);
+        assert!(is_bitpay_address(BITPAY).unwrap() && !is_legacy_address(BITPAY).unwrap());
+
+        let p2sh = "3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9";
+        let bitpay_p2sh = to_bitpay_address(p2sh).unwrap();
+        assert!(bitpay_p2sh.starts_with('H'));
+        assert_eq!(to_legacy_address(&bitpay_p2sh).unwrap(), p2sh);
+        assert!(is_p2sh_address(&bitpay_p2sh).unwrap());
+
+        // testnet Bitpay addresses are legacy ones, regtest reads as testnet and is written as bchtest
+        let testnet = "mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi";
+        assert_eq!(to_bitpay_address(testnet).unwrap(), testnet);
+        assert!(is_legacy_address(testnet).unwrap());
+        let regtest = "bchreg:qph5kuz78czq00e3t85ugpgd7xmer5kr7c28g5v92v";
+        assert_eq!(detect_address_network(regtest).unwrap(), Network::Testnet);
+        assert!(is_testnet_address(regtest).unwrap() && is_cash_address(regtest).unwrap());
+        assert_eq!(to_cash_address(regtest).unwrap(), "bchtest:qph5kuz78czq00e3t85ugpgd7xmer5kr7csm740kf2");
+        assert_eq!(to_legacy_address(&regtest[7..]).unwrap(), testnet);
+
+        for addr in &["", "nope", "bitcoincash:bad", "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg"] {
+            assert!(!is_valid_address(addr));
+            assert!(matches!(is_cash_address(addr), Err(Error::InvalidAddress(_))));
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Conversions between `ParsedAddress` and the [bitcoincash-addr](https://crates.io/crates/bitcoincash-addr) crate's
+//! `Address`, and between the enums of both crates.
+//!
+//! `Scheme::Base58` is the legacy format. Addresses in additional formats (`AddressFormat::Other`) have no scheme
+//! and fail to convert with `Error::UnknownCashFormat`, as addresses of `AddressType::Other` do with
+//! `Error::UnsupportedAddressType`.
+//! Enabled by the `bitcoincash-addr` feature.
+//! # Example
+//! ```
+//! use std::convert::TryFrom;
+//! # use bch_addr::{Converter, ParsedAddress};
+//!
+//! let parsed = ParsedAddress::from(bitcoincash_addr::Address::decode("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap());
+//! assert_eq!(Converter::new().encode(&parsed.format, parsed.network, parsed.addr_type, &parsed.hash).unwrap(), "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR");
+//!
+//! let mut parsed = ParsedAddress::default();
+//! Converter::new().parse_into("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", &mut parsed).unwrap();
+//! let address = bitcoincash_addr::Address::try_from(parsed).unwrap();
+//! assert_eq!(address.encode().unwrap(), "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+//! ```
+
+use std::convert::TryFrom;
+
+use ::bitcoincash_addr::{Address, HashType, Scheme};
+
+use crate::{ParsedAddress, AddressFormat, AddressType, Network};
+use crate::error::{Error, Result};
+
+impl From<Address> for ParsedAddress {
+    fn from(address: Address) -> ParsedAddress {
+        ParsedAddress {
+            format: address.scheme.into(),
+            network: address.network.into(),
+            addr_type: address.hash_type.into(),
+            hash: address.body,
+        }
+    }
+}
+
+impl TryFrom<ParsedAddress> for Address {
+    type Error = Error;
+
+    fn try_from(parsed: ParsedAddress) -> Result<Address> {
+        let scheme = match Scheme::try_from(&parsed.format) {
+            Ok(scheme) => scheme,
+            Err(_)     => return Err(Error::UnknownCashFormat(parsed.format, parsed.network)),
+        };
+        Ok(Address::new(parsed.hash, scheme, HashType::try_from(parsed.addr_type)?, parsed.network.into()))
+    }
+}
+
+impl TryFrom<&ParsedAddress> for Address {
+    type Error = Error;
+
+    fn try_from(parsed: &ParsedAddress) -> Result<Address> {
+        let scheme = Scheme::try_from(&parsed.format).map_err(|_| Error::UnknownCashFormat(parsed.format.clone(), parsed.network))?;
+        Ok(Address::new(parsed.hash.clone(), scheme, HashType::try_from(parsed.addr_type)?, parsed.network.into()))
+    }
+}
+
+impl From<Scheme> for AddressFormat {
+    fn from(scheme: Scheme) -> AddressFormat {
+        match scheme {
+            Scheme::Base58   => AddressFormat::Legacy,
+            Scheme::CashAddr => AddressFormat::CashAddr,
+        }
+    }
+}
+
+impl TryFrom<&AddressFormat> for Scheme {
+    type Error = Error;
+
+    fn try_from(format: &AddressFormat) -> Result<Scheme> {
+        match format {
+            AddressFormat::Legacy   => Ok(Scheme::Base58),
+            AddressFormat::CashAddr => Ok(Scheme::CashAddr),
+            AddressFormat::Other(_) => Err(Error::UnknownCashFormat(format.clone(), Network::Mainnet)),
+        }
+    }
+}
+
+impl From<::bitcoincash_addr::Network> for Network {
+    fn from(network: ::bitcoincash_addr::Network) -> Network {
+        match network {
+            ::bitcoincash_addr::Network::Main    => Network::Mainnet,
+            ::bitcoincash_addr::Network::Test    => Network::Testnet,
+            ::bitcoincash_addr::Network::Regtest => Network::Regtest,
+        }
+    }
+}
+
+impl From<Network> for ::bitcoincash_addr::Network {
+    fn from(network: Network) -> ::bitcoincash_addr::Network {
+        match network {
+            Network::Mainnet => ::bitcoincash_addr::Network::Main,
+            Network::Testnet => ::bitcoincash_addr::Network::Test,
+            Network::Regtest => ::bitcoincash_addr::Network::Regtest,
+        }
+    }
+}
+
+impl From<HashType> for AddressType {
+    fn from(hash_type: HashType) -> AddressType {
+        match hash_type {
+            HashType::Key    => AddressType::P2PKH,
+            HashType::Script => AddressType::P2SH,
+        }
+    }
+}
+
+impl TryFrom<AddressType> for HashType {
+    type Error = Error;
+
+    fn try_from(addr_type: AddressType) -> Result<HashType> {
+        match addr_type {
+            AddressType::P2PKH    => Ok(HashType::Key),
+            AddressType::P2SH     => Ok(HashType::Script),
+            AddressType::Other(_) => Err(Error::UnsupportedAddressType(addr_type)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Converter;
+
+    #[test]
+    fn bitcoincash_addr_test() {
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+        let mut parsed = ParsedAddress::default();
+
+        for addr in &["3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9", "bchtest:qph5kuz78czq00e3t85ugpgd7xmer5kr7csm740kf2", "bchreg:pph5kuz78czq00e3t85ugpgd7xmer5kr7caz4mtx33"] {
+            converter.parse_into(addr, &mut parsed).unwrap();
+            let address = Address::try_from(&parsed).unwrap();
+            assert_eq!(address.encode().unwrap(), *addr);
+            assert_eq!(ParsedAddress::from(Address::decode(addr).unwrap()), parsed);
+            assert_eq!(ParsedAddress::from(address), parsed);
+        }
+
+        converter.parse_into("simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg", &mut parsed).unwrap();
+        assert!(matches!(Address::try_from(parsed), Err(Error::UnknownCashFormat(AddressFormat::Other(_), Network::Mainnet))));
+
+        assert_eq!(AddressFormat::from(Scheme::Base58), AddressFormat::Legacy);
+        assert_eq!(HashType::try_from(AddressType::P2SH).unwrap(), HashType::Script);
+        assert!(HashType::try_from(AddressType::Other(2)).is_err());
+        assert_eq!(::bitcoincash_addr::Network::from(Network::Regtest), ::bitcoincash_addr::Network::Regtest);
+    }
+}
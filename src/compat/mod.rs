@@ -0,0 +1,6 @@
+//! Shims reproducing the API of other address libraries, so code written against them can be ported mechanically.
+
+#[cfg(all(feature = "legacy", feature = "cashaddr"))]
+pub mod bchaddrjs;
+#[cfg(feature = "bitcoincash-addr")]
+pub mod bitcoincash_addr;
@@ -0,0 +1,215 @@
+use std::str;
+
+use super::{Converter, AddressFormat, Network, AddressType, MAX_ADDR_LEN};
+use super::error::{Error, Result};
+
+/// Conversion context owning reusable buffers.
+///
+/// Offers the conversion methods of `Converter`, but decodes into and encodes from
+/// buffers kept between calls and returns borrowed results, so tight loops do not allocate per call.
+/// # Example
+/// ```
+/// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+/// # {
+/// # use bch_addr::{Converter, ConversionCtx};
+/// let converter = Converter::new();
+/// let mut ctx = ConversionCtx::new(&converter);
+///
+/// for legacy in &["1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9"] {
+///     let cash_addr = ctx.to_cash_addr(legacy).unwrap();
+///     assert!(cash_addr.starts_with("bitcoincash:"));
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ConversionCtx<'a> {
+    converter: &'a Converter,
+    hash: Vec<u8>,
+    out: Vec<u8>,
+}
+
+impl<'a> ConversionCtx<'a> {
+    /// Construct `ConversionCtx`.
+    /// # Arguments
+    /// * `converter` - Converter providing the prefix configuration.
+    /// # Returns
+    /// * Conversion context.
+    /// # Example
+    /// ```
+    /// # use bch_addr::{Converter, ConversionCtx};
+    /// let converter = Converter::new();
+    /// let ctx = ConversionCtx::new(&converter);
+    /// ```
+    pub fn new(converter: &'a Converter) -> ConversionCtx<'a> {
+        ConversionCtx {
+            converter,
+            hash: Vec::with_capacity(64),
+            out: vec![0; MAX_ADDR_LEN],
+        }
+    }
+
+    /// Underlying converter, for the queries that need no buffers.
+    /// # Returns
+    /// * Converter.
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "legacy")]
+    /// # {
+    /// # use bch_addr::{Converter, ConversionCtx};
+    /// # let converter = Converter::new();
+    /// let ctx = ConversionCtx::new(&converter);
+    /// assert!(ctx.converter().is_p2sh_addr("3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9"));
+    /// # }
+    /// ```
+    pub fn converter(&self) -> &'a Converter {
+        self.converter
+    }
+
+    /// Convert to cash_addr format with some options.
+    /// See `Converter::to_cash_addr_with_options`.
+    /// # Arguments
+    /// * `legacy` - Address to be converted. Usually legacy format but cash_addr format is acceptable.
+    /// * `format` - (option) Address format. `AddressFormat::CashAddr` or `AddressFormat::Other("other format")` is required.
+    /// * `network` - (option) Address network.
+    /// # Returns
+    /// * Converted address, valid until the next call.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::{Converter, ConversionCtx, Network};
+    /// # let converter = Converter::new();
+    /// let mut ctx = ConversionCtx::new(&converter);
+    /// let regtest_addr = ctx.to_cash_addr_with_options(
+    ///     "mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi",
+    ///     None,
+    ///     Some(Network::Regtest)
+    /// ).unwrap();
+    /// assert_eq!(regtest_addr, "bchreg:qph5kuz78czq00e3t85ugpgd7xmer5kr7c28g5v92v");
+    /// # }
+    /// ```
+    pub fn to_cash_addr_with_options(&mut self, legacy: &str, format: Option<AddressFormat>, network: Option<Network>) -> Result<&str> {
+        let format = format.unwrap_or(AddressFormat::CashAddr);
+        let converter = self.converter;
+
+        let (current_format, current_network, addr_type) = converter.parse_hash_into(legacy, &mut self.hash)?;
+        let network = network.unwrap_or(current_network);
+        if format == AddressFormat::Legacy {
+            return Err(Error::UnknownCashFormat(format, network));
+        }
+
+        let converted = if *current_format == format && network == current_network && !converter.cash_converter.has_alias(legacy) {
+            self.copy(legacy)
+        } else {
+            self.encode(&format, network, addr_type)?
+        };
+        converter.observer.conversion(legacy, converted, &format);
+        Ok(converted)
+    }
+
+    /// Convert to cash_addr format.
+    /// See `Converter::to_cash_addr`.
+    /// # Arguments
+    /// * `legacy` - Address to be converted. Usually legacy format but cash_addr format is acceptable.
+    /// # Returns
+    /// * Converted address, valid until the next call.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::{Converter, ConversionCtx};
+    /// # let converter = Converter::new();
+    /// let mut ctx = ConversionCtx::new(&converter);
+    /// let cash_addr = ctx.to_cash_addr("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap();
+    /// assert_eq!(cash_addr, "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+    /// # }
+    /// ```
+    pub fn to_cash_addr(&mut self, legacy: &str) -> Result<&str> {
+        self.to_cash_addr_with_options(legacy, None, None)
+    }
+
+    /// Convert to legacy format.
+    /// See `Converter::to_legacy_addr`.
+    /// # Arguments
+    /// * `cash` - Address to be converted. Usually cash_addr format but legacy format is acceptable.
+    /// # Returns
+    /// * Converted address, valid until the next call.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::{Converter, ConversionCtx};
+    /// # let converter = Converter::new();
+    /// let mut ctx = ConversionCtx::new(&converter);
+    /// let legacy_addr = ctx.to_legacy_addr("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap();
+    /// assert_eq!(legacy_addr, "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR");
+    /// # }
+    /// ```
+    pub fn to_legacy_addr(&mut self, cash: &str) -> Result<&str> {
+        let converter = self.converter;
+
+        let (current_format, network, addr_type) = converter.parse_hash_into(cash, &mut self.hash)?;
+        let converted = if *current_format == AddressFormat::Legacy {
+            self.copy(cash)
+        } else {
+            self.encode(&AddressFormat::Legacy, network, addr_type)?
+        };
+        converter.observer.conversion(cash, converted, &AddressFormat::Legacy);
+        Ok(converted)
+    }
+
+    /// Parse address.
+    /// See `Converter::parse`.
+    /// # Arguments
+    /// * `addr` - Address to be parsed.
+    /// # Returns
+    /// * Address format.
+    /// * Address network.
+    /// * Address type.
+    /// * hashed pubilckey, valid until the next call.
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
+    /// # use bch_addr::{Converter, ConversionCtx, AddressFormat, Network, AddressType};
+    /// # let converter = Converter::new();
+    /// let mut ctx = ConversionCtx::new(&converter);
+    /// let (format, network, addr_type, hash) = ctx.parse("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap();
+    /// assert_eq!(*format, AddressFormat::CashAddr);
+    /// assert_eq!(network, Network::Mainnet);
+    /// assert_eq!(addr_type, AddressType::P2PKH);
+    /// assert_eq!(hash.len(), 20);
+    /// # }
+    /// ```
+    pub fn parse(&mut self, addr: &str) -> Result<(&'a AddressFormat, Network, AddressType, &[u8])> {
+        let converter = self.converter;
+
+        let (format, network, addr_type) = converter.parse_hash_into(addr, &mut self.hash)?;
+        Ok((format, network, addr_type, &self.hash))
+    }
+
+    /// Encode the hash held in `self.hash` into `self.out`.
+    fn encode(&mut self, format: &AddressFormat, network: Network, addr_type: AddressType) -> Result<&str> {
+        let len = loop {
+            match self.converter.encode_into(format, network, addr_type, &self.hash, &mut self.out) {
+                Err(Error::BufferTooSmall(len)) => self.out.resize(len, 0),
+                ret                             => break ret?,
+            }
+        };
+
+        Ok(str::from_utf8(&self.out[..len]).expect("encoded addresses are ASCII"))
+    }
+
+    /// Copy `addr` into the output buffer, lowercased if prefixed like `reused_cash_addr`.
+    fn copy(&mut self, addr: &str) -> &str {
+        if self.out.len() < addr.len() {
+            self.out.resize(addr.len(), 0);
+        }
+        self.out[..addr.len()].copy_from_slice(addr.as_bytes());
+        if super::is_prefixed(addr) {
+            self.out[..addr.len()].make_ascii_lowercase();
+        }
+
+        str::from_utf8(&self.out[..addr.len()]).expect("copied from a str")
+    }
+}
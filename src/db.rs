@@ -0,0 +1,220 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::{Converter, AddressFormat, AddressType, Network};
+use super::error::Result;
+
+/// Address kept in its canonical form, a lowercase cash_addr address with prefix, for database columns.
+///
+/// Any accepted spelling (legacy, upper case, without prefix) is normalized on construction,
+/// so equal addresses always store equal strings.
+/// With the `sqlx` or `diesel` feature, it maps to a text column and is normalized again when read back.
+/// The alternate form of `Display`, `{:#}`, leaves the prefix out, as wallet UIs usually show addresses.
+/// # Example
+/// ```
+/// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+/// # {
+/// # use bch_addr::DbAddress;
+/// let addr: DbAddress = "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR".parse().unwrap();
+/// assert_eq!(addr.as_str(), "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+/// assert_eq!(addr, "QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK".parse().unwrap());
+/// assert_eq!(format!("{:#}", addr), "qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "diesel", derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Text))]
+pub struct DbAddress(String);
+
+impl DbAddress {
+    /// Normalize an address in any built-in format.
+    /// # Arguments
+    /// * `addr` - Address.
+    /// # Returns
+    /// * Canonical address.
+    pub fn new(addr: &str) -> Result<DbAddress> {
+        DbAddress::with_converter(&Converter::new(), addr)
+    }
+
+    /// Normalize an address with the prefixes registered in `converter`.
+    /// Addresses of user-defined formats are stored as cash_addr too.
+    /// # Arguments
+    /// * `converter` - Converter providing the prefix configuration.
+    /// * `addr` - Address.
+    /// # Returns
+    /// * Canonical address.
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
+    /// # use bch_addr::{Converter, DbAddress, Network};
+    /// let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+    /// let addr = DbAddress::with_converter(&converter, "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg").unwrap();
+    /// assert_eq!(addr.as_str(), "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+    /// # }
+    /// ```
+    pub fn with_converter(converter: &Converter, addr: &str) -> Result<DbAddress> {
+        let (_, network, addr_type, hash) = converter.parse(addr)?;
+        converter.encode(&AddressFormat::CashAddr, network, addr_type, &hash).map(DbAddress)
+    }
+
+    /// Build the canonical address of a hash, without going through another spelling.
+    /// # Arguments
+    /// * `network` - Address network.
+    /// * `addr_type` - Address type.
+    /// * `hash` - hashed publickey, of any cash_addr hash size.
+    /// # Returns
+    /// * Canonical address.
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
+    /// # use bch_addr::{DbAddress, Network, AddressType};
+    /// let addr = DbAddress::from_hash(Network::Testnet, AddressType::P2SH, &[0xab; 32]).unwrap();
+    /// assert!(addr.as_str().starts_with("bchtest:p"));
+    /// # }
+    /// ```
+    pub fn from_hash(network: Network, addr_type: AddressType, hash: &[u8]) -> Result<DbAddress> {
+        Converter::new().encode(&AddressFormat::CashAddr, network, addr_type, hash).map(DbAddress)
+    }
+
+    /// Canonical address.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Canonical address.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl FromStr for DbAddress {
+    type Err = super::Error;
+
+    fn from_str(addr: &str) -> Result<DbAddress> {
+        DbAddress::new(addr)
+    }
+}
+
+impl fmt::Display for DbAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.find(':') {
+            Some(sep) if f.alternate() => f.write_str(&self.0[sep + 1..]),
+            _                          => f.write_str(&self.0),
+        }
+    }
+}
+
+impl AsRef<str> for DbAddress {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<DbAddress> for String {
+    fn from(addr: DbAddress) -> String {
+        addr.0
+    }
+}
+
+#[cfg(feature = "sqlx")]
+mod sqlx_impls {
+    use failure::Fail;
+    use sqlx::encode::IsNull;
+    use sqlx::error::BoxDynError;
+    use sqlx::{Database, Decode, Encode, Type};
+
+    use super::DbAddress;
+
+    impl<DB: Database> Type<DB> for DbAddress where String: Type<DB> {
+        fn type_info() -> DB::TypeInfo {
+            <String as Type<DB>>::type_info()
+        }
+
+        fn compatible(ty: &DB::TypeInfo) -> bool {
+            <String as Type<DB>>::compatible(ty)
+        }
+    }
+
+    impl<'q, DB: Database> Encode<'q, DB> for DbAddress where String: Encode<'q, DB> {
+        fn encode_by_ref(&self, buf: &mut DB::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+            self.0.encode_by_ref(buf)
+        }
+
+        fn size_hint(&self) -> usize {
+            self.0.size_hint()
+        }
+    }
+
+    impl<'r, DB: Database> Decode<'r, DB> for DbAddress where String: Decode<'r, DB> {
+        fn decode(value: DB::ValueRef<'r>) -> Result<DbAddress, BoxDynError> {
+            let addr = String::decode(value)?;
+            Ok(DbAddress::new(&addr).map_err(Fail::compat)?)
+        }
+    }
+}
+
+#[cfg(feature = "diesel")]
+mod diesel_impls {
+    use diesel::backend::Backend;
+    use diesel::deserialize::{self, FromSql};
+    use diesel::serialize::{self, Output, ToSql};
+    use diesel::sql_types::Text;
+    use failure::Fail;
+
+    use super::DbAddress;
+
+    impl<DB: Backend> ToSql<Text, DB> for DbAddress where str: ToSql<Text, DB> {
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+            self.0.as_str().to_sql(out)
+        }
+    }
+
+    impl<DB: Backend> FromSql<Text, DB> for DbAddress where String: FromSql<Text, DB> {
+        fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<DbAddress> {
+            let addr = String::from_sql(bytes)?;
+            Ok(DbAddress::new(&addr).map_err(Fail::compat)?)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "cashaddr"))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "legacy")]
+    #[test]
+    fn normalize_test() {
+        let canonical = "bchtest:pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t";
+        let legacy = Converter::new().to_legacy_addr(canonical).unwrap();
+        for addr in &[
+            canonical,
+            "BCHTEST:PR6M7J9NJLDWWZLG9V7V53UNLR4JKMX6EYVWC0UZ5T",
+            "pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t",
+            &legacy,
+        ] {
+            assert_eq!(DbAddress::new(addr).unwrap().as_str(), canonical);
+        }
+        assert!(DbAddress::new("bitcoincash:bad").is_err());
+
+        let converter = Converter::new().add_prefixes(&[("slptest", Network::Testnet)], "SLPAddr");
+        let slp = converter.to_cash_addr_with_options(canonical, Some(AddressFormat::Other("SLPAddr".to_string())), None).unwrap();
+        assert_eq!(DbAddress::with_converter(&converter, &slp).unwrap().as_str(), canonical);
+    }
+
+    #[test]
+    fn from_hash_test() {
+        let canonical = DbAddress::new("bchtest:pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t").unwrap();
+        let (_, network, addr_type, hash) = Converter::new().parse(canonical.as_str()).unwrap();
+        assert_eq!(DbAddress::from_hash(network, addr_type, &hash).unwrap(), canonical);
+        assert!(DbAddress::from_hash(network, addr_type, &hash[1..]).is_err());
+    }
+
+    #[test]
+    fn display_test() {
+        let addr = DbAddress::new("bchtest:pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t").unwrap();
+        assert_eq!(addr.to_string(), "bchtest:pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t");
+        assert_eq!(format!("{:#}", addr), "pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t");
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(feature = "subtle")]
+use subtle::ConstantTimeEq;
+
+use super::{Converter, ParsedAddress};
+use super::error::Result;
+
+/// How two addresses differ, returned by `Converter::diff`.
+/// Each field is `true` if the addresses differ in that property.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AddressDiff {
+    /// Address format, e.g. legacy and cash_addr.
+    pub format: bool,
+    /// Network.
+    pub network: bool,
+    /// Address type.
+    pub addr_type: bool,
+    /// Hash.
+    pub hash: bool,
+}
+
+impl AddressDiff {
+    /// `true` if both addresses pay to the same destination, in whatever format.
+    pub fn is_same_destination(&self) -> bool {
+        !(self.network || self.addr_type || self.hash)
+    }
+
+    /// `true` if the addresses only differ in format.
+    pub fn is_format_only(&self) -> bool {
+        self.format && self.is_same_destination()
+    }
+
+    /// `true` if both addresses have the same hash,
+    /// e.g. the same key or script used on another network or as another type.
+    pub fn is_same_hash(&self) -> bool {
+        !self.hash
+    }
+}
+
+pub(crate) fn diff(converter: &Converter, a: &str, b: &str) -> Result<AddressDiff> {
+    let mut parsed_a = ParsedAddress::default();
+    let mut parsed_b = ParsedAddress::default();
+    converter.parse_into(a, &mut parsed_a)?;
+    converter.parse_into(b, &mut parsed_b)?;
+
+    Ok(AddressDiff {
+        format: parsed_a.format != parsed_b.format,
+        network: parsed_a.network != parsed_b.network,
+        addr_type: parsed_a.addr_type != parsed_b.addr_type,
+        hash: parsed_a.hash != parsed_b.hash,
+    })
+}
+
+/// `diff(a, b)?.is_same_destination()` with every property compared in constant time.
+/// Only the comparison is constant-time: parsing still depends on the addresses.
+#[cfg(feature = "subtle")]
+pub(crate) fn ct_same_destination(converter: &Converter, a: &str, b: &str) -> Result<bool> {
+    let mut parsed_a = ParsedAddress::default();
+    let mut parsed_b = ParsedAddress::default();
+    converter.parse_into(a, &mut parsed_a)?;
+    converter.parse_into(b, &mut parsed_b)?;
+
+    let same = (parsed_a.network as u8).ct_eq(&(parsed_b.network as u8))
+        & parsed_a.addr_type.version_bits().ct_eq(&parsed_b.addr_type.version_bits())
+        & parsed_a.hash.ct_eq(&parsed_b.hash);
+    Ok(same.into())
+}
+
+#[cfg(all(test, feature = "legacy", feature = "cashaddr"))]
+mod tests {
+    use super::*;
+    use crate::Network;
+
+    #[test]
+    fn diff_test() {
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+
+        let same = diff(&converter, "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", "QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK").unwrap();
+        assert_eq!(same, AddressDiff::default());
+        assert!(same.is_same_destination() && !same.is_format_only());
+
+        let slp = diff(&converter, "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg").unwrap();
+        assert!(slp.is_format_only());
+
+        let testnet = diff(&converter, "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "bchtest:qph5kuz78czq00e3t85ugpgd7xmer5kr7csm740kf2").unwrap();
+        assert_eq!(testnet, AddressDiff { format: true, network: true, addr_type: false, hash: false });
+        assert!(testnet.is_same_hash() && !testnet.is_same_destination());
+
+        let p2sh = diff(&converter, "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", "bitcoincash:pph5kuz78czq00e3t85ugpgd7xmer5kr7crv8a2z4t").unwrap();
+        assert_eq!(p2sh, AddressDiff { addr_type: true, ..AddressDiff::default() });
+
+        let other = diff(&converter, "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "185K5yAfcrARrHjNVt4iAUHtkYqcogF4km").unwrap();
+        assert_eq!(other, AddressDiff { hash: true, ..AddressDiff::default() });
+
+        assert!(diff(&converter, "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "bitcoincash:bad").is_err());
+    }
+
+    #[cfg(feature = "subtle")]
+    #[test]
+    fn ct_same_destination_test() {
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+
+        assert!(ct_same_destination(&converter, "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK").unwrap());
+        assert!(ct_same_destination(&converter, "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg").unwrap());
+        assert!(!ct_same_destination(&converter, "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "bchtest:qph5kuz78czq00e3t85ugpgd7xmer5kr7csm740kf2").unwrap());
+        assert!(!ct_same_destination(&converter, "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", "bitcoincash:pph5kuz78czq00e3t85ugpgd7xmer5kr7crv8a2z4t").unwrap());
+        assert!(!ct_same_destination(&converter, "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "185K5yAfcrARrHjNVt4iAUHtkYqcogF4km").unwrap());
+        assert!(ct_same_destination(&converter, "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "bitcoincash:bad").is_err());
+
+        // hashes of other lengths never match
+        let p2sh32 = converter.encode(&crate::AddressFormat::CashAddr, Network::Mainnet, crate::AddressType::P2SH, &[0xab; 32]).unwrap();
+        let p2sh = converter.encode(&crate::AddressFormat::CashAddr, Network::Mainnet, crate::AddressType::P2SH, &[0xab; 20]).unwrap();
+        assert!(!ct_same_destination(&converter, &p2sh32, &p2sh).unwrap());
+    }
+}
@@ -1,10 +1,13 @@
+// `failure_derive` expands its impls inside an anonymous const.
+#![allow(non_local_definitions)]
+
+use std::fmt;
 use std::result;
 
-use bs58;
-use cash_addr;
 use failure::Fail;
 
-use super::{Network, AddressFormat};
+use super::{Network, AddressFormat, AddressType};
+use super::cash_addr;
 
 /// Alias of `Result` used by bch_addr.
 pub type Result<T> = result::Result<T, Error>;
@@ -37,9 +40,47 @@ pub enum Error {
     #[fail(display = "invalid address: {}", 0)]
     InvalidAddress(String),
 
+    /// Address type the format cannot carry: type bits not registered for the cash_addr format,
+    /// or any type other than P2PKH and P2SH for legacy.
+    /// # Arguments
+    /// * Address type.
+    #[fail(display = "unsupported address type: {:?}", 0)]
+    UnsupportedAddressType(AddressType),
+
+    /// Public key that is neither compressed (33 bytes) nor uncompressed (65 bytes).
+    /// # Arguments
+    /// * Public key length.
+    #[fail(display = "invalid public key: {} bytes", 0)]
+    InvalidPublicKey(usize),
+
+    /// Amount that is not a decimal BCH value with at most 8 decimal places.
+    /// # Arguments
+    /// * Amount.
+    #[fail(display = "invalid amount: {}", 0)]
+    InvalidAmount(String),
+
+    /// Hash that is not an even number of hex digits.
+    /// # Arguments
+    /// * Hex string.
+    #[fail(display = "invalid hex: {}", 0)]
+    InvalidHex(String),
+
+    /// Output buffer is too small for the encoded address.
+    /// # Arguments
+    /// * Required length (may be an upper bound).
+    #[fail(display = "buffer too small: {} bytes required", 0)]
+    BufferTooSmall(usize),
+
+    /// Address format compiled out of this build.
+    /// # Arguments
+    /// * Name of the feature the format needs.
+    #[fail(display = "not supported without the `{}` feature", 0)]
+    NotSupported(&'static str),
+
     /// bs58 library's error.
     /// # Arguments
     /// * Error.
+    #[cfg(feature = "legacy")]
     #[fail(display = "bs58 error: {}", 0)]
     Bs58(bs58::decode::DecodeError),
 
@@ -50,6 +91,7 @@ pub enum Error {
     CashAddr(cash_addr::Error),
 }
 
+#[cfg(feature = "legacy")]
 impl From<bs58::decode::DecodeError> for Error {
     fn from(err: bs58::decode::DecodeError) -> Error {
         Error::Bs58(err)
@@ -58,6 +100,65 @@ impl From<bs58::decode::DecodeError> for Error {
 
 impl From<cash_addr::Error> for Error {
     fn from(err: cash_addr::Error) -> Error {
-        Error::CashAddr(err)
+        match err {
+            cash_addr::Error::BufferTooSmall(len) => Error::BufferTooSmall(len),
+            cash_addr::Error::NotSupported        => Error::NotSupported("cashaddr"),
+            err                                   => Error::CashAddr(err),
+        }
+    }
+}
+
+/// Wording of errors shown to end users, e.g. a translation. `Error`'s `Display` is the default wording.
+/// Closures `Fn(&Error, &mut fmt::Formatter<'_>) -> fmt::Result` implement it.
+pub trait ErrorMessages {
+    /// Write the message of `err`.
+    fn fmt(&self, err: &Error, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+impl<F: Fn(&Error, &mut fmt::Formatter<'_>) -> fmt::Result> ErrorMessages for F {
+    fn fmt(&self, err: &Error, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self(err, f)
+    }
+}
+
+/// `Display` of an error with the wording of an `ErrorMessages`, returned by `Error::display_with`.
+pub struct ErrorDisplay<'a, M: ?Sized> {
+    err: &'a Error,
+    messages: &'a M,
+}
+
+impl<M: ?Sized> fmt::Debug for ErrorDisplay<'_, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ErrorDisplay").field("err", self.err).finish_non_exhaustive()
+    }
+}
+
+impl<M: ErrorMessages + ?Sized> fmt::Display for ErrorDisplay<'_, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.messages.fmt(self.err, f)
+    }
+}
+
+impl Error {
+    /// Display this error with the wording of `messages` instead of the default one.
+    /// # Arguments
+    /// * `messages` - Wording of the errors.
+    /// # Returns
+    /// * `Display` adapter.
+    /// # Example
+    /// ```
+    /// use std::fmt;
+    /// use bch_addr::{Converter, Error};
+    ///
+    /// let messages = |err: &Error, f: &mut fmt::Formatter| match err {
+    ///     Error::InvalidAddress(addr) => write!(f, "Adresse invalide : {}", addr),
+    ///     err                         => write!(f, "{}", err),
+    /// };
+    /// let err = Converter::new().to_cash_addr("nope").unwrap_err();
+    /// assert_eq!(err.display_with(&messages).to_string(), "Adresse invalide : nope");
+    /// assert_eq!(err.to_string(), "invalid address: nope");
+    /// ```
+    pub fn display_with<'a, M: ErrorMessages + ?Sized>(&'a self, messages: &'a M) -> ErrorDisplay<'a, M> {
+        ErrorDisplay { err: self, messages }
     }
 }
@@ -0,0 +1,146 @@
+//! Transaction size estimates for fee and dust calculations, matching the node's relay policy.
+
+use super::AddressType;
+use super::hash::HASH160_LEN;
+use super::script;
+
+/// Default dust relay fee of the node, in satoshis per 1000 bytes.
+pub const DUST_RELAY_FEE: u64 = 1000;
+
+/// Serialized size of an input spending a P2PKH output with an ECDSA signature of maximum length:
+/// outpoint, script length, signature and compressed public key pushes, and sequence.
+/// Schnorr signatures are 8 bytes shorter.
+pub const P2PKH_INPUT_SIZE: usize = 36 + 1 + (1 + 72) + (1 + 33) + 4;
+
+/// Input size the node assumes when computing the dust threshold of any output.
+const DUST_SPEND_SIZE: usize = P2PKH_INPUT_SIZE;
+
+/// Serialized size of an output paying to an address of a 20-byte hash: value, script length and script.
+/// # Arguments
+/// * `addr_type` - Address type.
+/// # Returns
+/// * Size in bytes, `None` for types other than P2PKH and P2SH, which have no standard script.
+/// # Example
+/// ```
+/// use bch_addr::AddressType;
+/// use bch_addr::fee::output_size;
+///
+/// assert_eq!(output_size(AddressType::P2PKH), Some(34));
+/// assert_eq!(output_size(AddressType::P2SH), Some(32));
+/// assert_eq!(output_size(AddressType::Other(2)), None);
+/// ```
+pub fn output_size(addr_type: AddressType) -> Option<usize> {
+    output_size_for(addr_type, HASH160_LEN)
+}
+
+/// Smallest amount an output paying to an address of a 20-byte hash can carry without being dust,
+/// i.e. worth spending: three times the fee of the output plus the input spending it.
+/// # Arguments
+/// * `addr_type` - Address type.
+/// * `fee_rate` - Dust relay fee in satoshis per 1000 bytes, usually `DUST_RELAY_FEE`.
+/// # Returns
+/// * Threshold in satoshis; outputs below it are not relayed. `None` for types other than P2PKH and P2SH.
+/// # Example
+/// ```
+/// use bch_addr::AddressType;
+/// use bch_addr::fee::{dust_threshold, DUST_RELAY_FEE};
+///
+/// assert_eq!(dust_threshold(AddressType::P2PKH, DUST_RELAY_FEE), Some(546));
+/// assert_eq!(dust_threshold(AddressType::P2SH, DUST_RELAY_FEE), Some(540));
+/// ```
+pub fn dust_threshold(addr_type: AddressType, fee_rate: u64) -> Option<u64> {
+    output_size(addr_type).map(|size| dust_threshold_for(size, fee_rate))
+}
+
+/// Serialized size of an input spending an output paying to an address.
+/// # Arguments
+/// * `addr_type` - Address type.
+/// * `redeem_size` - For P2SH, size of the unlocking script: the data satisfying the redeem script
+///   followed by the push of the redeem script itself. Ignored for P2PKH.
+/// # Returns
+/// * Size in bytes, `None` for P2SH without `redeem_size` and for other types, which have no standard script.
+/// # Example
+/// ```
+/// use bch_addr::AddressType;
+/// use bch_addr::fee::{input_size, P2PKH_INPUT_SIZE};
+///
+/// assert_eq!(input_size(AddressType::P2PKH, None), Some(P2PKH_INPUT_SIZE));
+/// // 2-of-3 multisig: OP_0, two signatures and the pushed 105-byte redeem script
+/// assert_eq!(input_size(AddressType::P2SH, Some(1 + 2 * (1 + 72) + 2 + 105)), Some(297));
+/// assert_eq!(input_size(AddressType::P2SH, None), None);
+/// ```
+pub fn input_size(addr_type: AddressType, redeem_size: Option<usize>) -> Option<usize> {
+    match addr_type {
+        AddressType::P2PKH    => Some(P2PKH_INPUT_SIZE),
+        AddressType::P2SH     => redeem_size.map(|size| 36 + compact_size_len(size) + size + 4),
+        AddressType::Other(_) => None,
+    }
+}
+
+impl AddressType {
+    /// Serialized size of an input spending an output of this type. See `fee::input_size`.
+    pub fn input_size(self, redeem_size: Option<usize>) -> Option<usize> {
+        input_size(self, redeem_size)
+    }
+
+    /// Serialized size of an output paying to an address of this type and a 20-byte hash.
+    /// See `fee::output_size`.
+    pub fn output_size(self) -> Option<usize> {
+        output_size(self)
+    }
+}
+
+/// Length of the CompactSize encoding of `len`.
+fn compact_size_len(len: usize) -> usize {
+    match len {
+        0..=0xfc               => 1,
+        0xfd..=0xffff          => 3,
+        0x1_0000..=0xffff_ffff => 5,
+        _                      => 9,
+    }
+}
+
+/// Output size for any hash length, `None` if no standard script pays to it.
+pub(crate) fn output_size_for(addr_type: AddressType, hash_len: usize) -> Option<usize> {
+    // value, script length (one byte for any standard script) and script
+    script::script_pub_key_len(addr_type, hash_len).map(|script_len| 8 + 1 + script_len)
+}
+
+pub(crate) fn dust_threshold_for(output_size: usize, fee_rate: u64) -> u64 {
+    3 * (output_size + DUST_SPEND_SIZE) as u64 * fee_rate / 1000
+}
+
+#[cfg(all(test, feature = "cashaddr"))]
+mod tests {
+    use super::*;
+    use crate::{ParsedAddress, Converter};
+
+    #[test]
+    fn dust_test() {
+        assert_eq!(dust_threshold(AddressType::P2PKH, 0), Some(0));
+        assert_eq!(dust_threshold(AddressType::P2PKH, 5000), Some(2730));
+        assert_eq!(dust_threshold(AddressType::Other(2), DUST_RELAY_FEE), None);
+
+        let mut parsed = ParsedAddress::default();
+        Converter::new().parse_into("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", &mut parsed).unwrap();
+        assert_eq!(parsed.output_size(), output_size(AddressType::P2PKH));
+        assert_eq!(parsed.dust_threshold(DUST_RELAY_FEE), Some(546));
+
+        assert_eq!(parsed.input_size(None), Some(P2PKH_INPUT_SIZE));
+        assert_eq!(AddressType::P2PKH.input_size(Some(1000)), Some(P2PKH_INPUT_SIZE));
+        assert_eq!(AddressType::P2SH.output_size(), Some(32));
+        assert_eq!(AddressType::Other(2).output_size(), None);
+        assert_eq!(AddressType::Other(2).input_size(Some(100)), None);
+        assert_eq!(AddressType::P2SH.input_size(Some(252)), Some(36 + 1 + 252 + 4));
+        assert_eq!(AddressType::P2SH.input_size(Some(253)), Some(36 + 3 + 253 + 4));
+
+        parsed.hash = vec![0; 32];
+        assert_eq!(parsed.output_size(), None);
+        assert_eq!(parsed.dust_threshold(DUST_RELAY_FEE), None);
+
+        parsed.hash = vec![0; 20];
+        parsed.addr_type = AddressType::Other(2);
+        assert_eq!(parsed.output_size(), None);
+        assert_eq!(parsed.dust_threshold(DUST_RELAY_FEE), None);
+    }
+}
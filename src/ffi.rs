@@ -0,0 +1,399 @@
+//! C bindings.
+//!
+//! Enabled by the `ffi` feature. Strings are NUL-terminated UTF-8 in both directions;
+//! strings returned through `out` pointers are owned by the caller and must be released
+//! with `bchaddr_free_string`. Every fallible function returns `BCHADDR_OK` or one of
+//! the `BCHADDR_ERR_*` codes, and `bchaddr_last_error_message` describes the failure.
+//!
+//! `include/bch_addr.h` is generated from this module by cbindgen:
+//! `cbindgen --config cbindgen.toml --output include/bch_addr.h`.
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::mem;
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+
+use super::{Converter, AddressFormat, Network, AddressType, Error};
+
+/// Opaque converter handle.
+#[derive(Debug, Default)]
+pub struct BchAddrConverter {
+    converter: Converter,
+}
+
+/// Success.
+pub const BCHADDR_OK: c_int = 0;
+/// A required pointer argument was NULL.
+pub const BCHADDR_ERR_NULL_POINTER: c_int = 1;
+/// A string argument was not valid UTF-8.
+pub const BCHADDR_ERR_INVALID_UTF8: c_int = 2;
+/// A network argument was out of range.
+pub const BCHADDR_ERR_INVALID_ARGUMENT: c_int = 3;
+/// The address is not valid in any registered format (bad checksum, characters, length, ...).
+pub const BCHADDR_ERR_INVALID_ADDRESS: c_int = 4;
+/// The address has a cash_addr prefix that is not registered.
+pub const BCHADDR_ERR_UNKNOWN_PREFIX: c_int = 5;
+/// No prefix is registered for the requested format and network.
+pub const BCHADDR_ERR_UNKNOWN_FORMAT: c_int = 6;
+
+/// `Network::Mainnet`.
+pub const BCHADDR_NETWORK_MAINNET: c_int = 0;
+/// `Network::Testnet`.
+pub const BCHADDR_NETWORK_TESTNET: c_int = 1;
+/// `Network::Regtest`.
+pub const BCHADDR_NETWORK_REGTEST: c_int = 2;
+/// Keep the network of the input address.
+pub const BCHADDR_NETWORK_UNCHANGED: c_int = -1;
+
+/// `AddressFormat::Legacy`.
+pub const BCHADDR_FORMAT_LEGACY: c_int = 0;
+/// `AddressFormat::CashAddr`.
+pub const BCHADDR_FORMAT_CASH_ADDR: c_int = 1;
+/// `AddressFormat::Other`, a format registered with `bchaddr_converter_add_prefix`.
+pub const BCHADDR_FORMAT_OTHER: c_int = 2;
+
+/// `AddressType::P2PKH`.
+pub const BCHADDR_TYPE_P2PKH: c_int = 0;
+/// `AddressType::P2SH`.
+pub const BCHADDR_TYPE_P2SH: c_int = 1;
+/// `AddressType::Other`, a type field registered with `bchaddr_converter_add_type_bits`;
+/// `BchAddrParsed::type_bits` holds which one.
+pub const BCHADDR_TYPE_OTHER: c_int = 2;
+
+/// Parsed address filled in by `bchaddr_parse`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct BchAddrParsed {
+    /// One of the `BCHADDR_FORMAT_*` values.
+    pub format: c_int,
+    /// One of the `BCHADDR_NETWORK_*` values.
+    pub network: c_int,
+    /// One of the `BCHADDR_TYPE_*` values.
+    pub addr_type: c_int,
+    /// Hash bytes; only the first `hash_len` are meaningful.
+    pub hash: [u8; 64],
+    /// Length of the hash.
+    pub hash_len: usize,
+    /// Type field of the version byte: 0 for P2PKH, 1 for P2SH, 2 to 15 for `BCHADDR_TYPE_OTHER`.
+    pub type_bits: u8,
+}
+
+/// Create a converter with the built-in prefixes.
+/// Release it with `bchaddr_converter_free`.
+#[no_mangle]
+pub extern "C" fn bchaddr_converter_new() -> *mut BchAddrConverter {
+    Box::into_raw(Box::new(BchAddrConverter::default()))
+}
+
+/// Release a converter created by `bchaddr_converter_new`.
+/// # Safety
+/// `converter` must be NULL or a pointer returned by `bchaddr_converter_new` that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn bchaddr_converter_free(converter: *mut BchAddrConverter) {
+    if !converter.is_null() {
+        drop(Box::from_raw(converter));
+    }
+}
+
+/// Register `prefix` for `network` under the user-defined format `format_name`.
+/// # Safety
+/// `converter` must come from `bchaddr_converter_new`; the strings must be NUL-terminated.
+#[no_mangle]
+pub unsafe extern "C" fn bchaddr_converter_add_prefix(
+    converter: *mut BchAddrConverter,
+    prefix: *const c_char,
+    network: c_int,
+    format_name: *const c_char,
+) -> c_int {
+    report((|| {
+        let converter = &mut converter.as_mut().ok_or_else(|| FfiError::null_pointer("converter"))?.converter;
+        let prefix = read_str(prefix, "prefix")?;
+        let format_name = read_str(format_name, "format_name")?;
+        let network = network_from_c(network)?;
+
+        *converter = mem::take(converter).add_prefixes(&[(prefix, network)], format_name);
+        Ok(())
+    })())
+}
+
+/// Accept the unassigned type fields `type_bits[0..len]` (2 to 15, others are ignored) in addresses
+/// of the user-defined format `format_name` (cash_addr when NULL).
+/// # Safety
+/// `converter` must come from `bchaddr_converter_new`; `format_name` must be NULL or NUL-terminated;
+/// `type_bits` must be valid for reads of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn bchaddr_converter_add_type_bits(
+    converter: *mut BchAddrConverter,
+    format_name: *const c_char,
+    type_bits: *const u8,
+    len: usize,
+) -> c_int {
+    report((|| {
+        let converter = &mut converter.as_mut().ok_or_else(|| FfiError::null_pointer("converter"))?.converter;
+        let format = if format_name.is_null() {
+            AddressFormat::CashAddr
+        } else {
+            AddressFormat::Other(read_str(format_name, "format_name")?.to_string())
+        };
+        if type_bits.is_null() {
+            return Err(FfiError::null_pointer("type_bits"));
+        }
+        let type_bits = std::slice::from_raw_parts(type_bits, len);
+
+        *converter = mem::take(converter).add_type_bits(&format, type_bits);
+        Ok(())
+    })())
+}
+
+/// Convert `addr` to cash_addr format and store the result in `*out`.
+/// # Safety
+/// `converter` must come from `bchaddr_converter_new`; `addr` must be NUL-terminated;
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn bchaddr_to_cash(converter: *const BchAddrConverter, addr: *const c_char, out: *mut *mut c_char) -> c_int {
+    bchaddr_to_cash_with_options(converter, addr, ptr::null(), BCHADDR_NETWORK_UNCHANGED, out)
+}
+
+/// Convert `addr` to the cash_addr-like format `format_name` (cash_addr when NULL)
+/// on `network` (unchanged when `BCHADDR_NETWORK_UNCHANGED`) and store the result in `*out`.
+/// # Safety
+/// Same as `bchaddr_to_cash`; `format_name` must be NULL or NUL-terminated.
+#[no_mangle]
+pub unsafe extern "C" fn bchaddr_to_cash_with_options(
+    converter: *const BchAddrConverter,
+    addr: *const c_char,
+    format_name: *const c_char,
+    network: c_int,
+    out: *mut *mut c_char,
+) -> c_int {
+    report((|| {
+        let format = if format_name.is_null() {
+            None
+        } else {
+            Some(AddressFormat::Other(read_str(format_name, "format_name")?.to_string()))
+        };
+        let network = match network {
+            BCHADDR_NETWORK_UNCHANGED => None,
+            network                   => Some(network_from_c(network)?),
+        };
+
+        convert(converter, addr, out, |converter, addr| converter.to_cash_addr_with_options(addr, format, network))
+    })())
+}
+
+/// Convert `addr` to legacy format and store the result in `*out`.
+/// # Safety
+/// Same as `bchaddr_to_cash`.
+#[no_mangle]
+pub unsafe extern "C" fn bchaddr_to_legacy(converter: *const BchAddrConverter, addr: *const c_char, out: *mut *mut c_char) -> c_int {
+    report(convert(converter, addr, out, |converter, addr| converter.to_legacy_addr(addr)))
+}
+
+/// Parse `addr` into `*out`.
+/// # Safety
+/// `converter` must come from `bchaddr_converter_new`; `addr` must be NUL-terminated;
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn bchaddr_parse(converter: *const BchAddrConverter, addr: *const c_char, out: *mut BchAddrParsed) -> c_int {
+    report((|| {
+        let converter = &converter.as_ref().ok_or_else(|| FfiError::null_pointer("converter"))?.converter;
+        let out = out.as_mut().ok_or_else(|| FfiError::null_pointer("out"))?;
+        let addr = read_str(addr, "addr")?;
+
+        let (format, network, addr_type, hash) = converter.parse(addr)?;
+        if hash.len() > out.hash.len() {
+            return Err(FfiError {
+                code: BCHADDR_ERR_INVALID_ADDRESS,
+                message: format!("hash of {} bytes does not fit BchAddrParsed: {}", hash.len(), addr),
+            });
+        }
+        out.format = format_to_c(&format);
+        out.network = network_to_c(network);
+        out.addr_type = type_to_c(addr_type);
+        out.hash = [0; 64];
+        out.hash[..hash.len()].copy_from_slice(&hash);
+        out.hash_len = hash.len();
+        out.type_bits = addr_type.type_bits();
+        Ok(())
+    })())
+}
+
+/// Release a string returned by this library.
+/// # Safety
+/// `s` must be NULL or a string returned through an `out` pointer of this library that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn bchaddr_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Message describing the most recent failure on the calling thread, or NULL if nothing has failed yet.
+/// The string is owned by the library and stays valid until the next failing call on the same thread;
+/// do not pass it to `bchaddr_free_string`.
+#[no_mangle]
+pub extern "C" fn bchaddr_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Error code together with the message recorded for `bchaddr_last_error_message`.
+struct FfiError {
+    code: c_int,
+    message: String,
+}
+
+impl FfiError {
+    fn null_pointer(name: &str) -> FfiError {
+        FfiError { code: BCHADDR_ERR_NULL_POINTER, message: format!("{} is NULL", name) }
+    }
+}
+
+impl From<Error> for FfiError {
+    fn from(err: Error) -> FfiError {
+        FfiError { code: error_code(&err), message: err.to_string() }
+    }
+}
+
+/// Turn `result` into a return code, recording the message on failure.
+fn report(result: Result<(), FfiError>) -> c_int {
+    match result {
+        Ok(()) => BCHADDR_OK,
+        Err(err) => {
+            let message = CString::new(err.message).unwrap_or_default();
+            LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+            err.code
+        },
+    }
+}
+
+unsafe fn convert<F>(converter: *const BchAddrConverter, addr: *const c_char, out: *mut *mut c_char, f: F) -> Result<(), FfiError>
+    where F: FnOnce(&Converter, &str) -> Result<String, Error>
+{
+    let converter = &converter.as_ref().ok_or_else(|| FfiError::null_pointer("converter"))?.converter;
+    let out = out.as_mut().ok_or_else(|| FfiError::null_pointer("out"))?;
+    let addr = read_str(addr, "addr")?;
+
+    let converted = f(converter, addr)?;
+    *out = CString::new(converted).expect("addresses contain no NUL").into_raw();
+    Ok(())
+}
+
+unsafe fn read_str<'a>(s: *const c_char, name: &str) -> Result<&'a str, FfiError> {
+    if s.is_null() {
+        return Err(FfiError::null_pointer(name));
+    }
+    CStr::from_ptr(s).to_str().map_err(|err| FfiError {
+        code: BCHADDR_ERR_INVALID_UTF8,
+        message: format!("{} is not valid UTF-8: {}", name, err),
+    })
+}
+
+fn error_code(err: &Error) -> c_int {
+    match err {
+        Error::UnknownCashPrefix(_)    => BCHADDR_ERR_UNKNOWN_PREFIX,
+        Error::UnknownCashFormat(_, _) => BCHADDR_ERR_UNKNOWN_FORMAT,
+        _                              => BCHADDR_ERR_INVALID_ADDRESS,
+    }
+}
+
+fn network_from_c(network: c_int) -> Result<Network, FfiError> {
+    match network {
+        BCHADDR_NETWORK_MAINNET => Ok(Network::Mainnet),
+        BCHADDR_NETWORK_TESTNET => Ok(Network::Testnet),
+        BCHADDR_NETWORK_REGTEST => Ok(Network::Regtest),
+        _ => Err(FfiError { code: BCHADDR_ERR_INVALID_ARGUMENT, message: format!("unknown network: {}", network) }),
+    }
+}
+
+fn network_to_c(network: Network) -> c_int {
+    match network {
+        Network::Mainnet => BCHADDR_NETWORK_MAINNET,
+        Network::Testnet => BCHADDR_NETWORK_TESTNET,
+        Network::Regtest => BCHADDR_NETWORK_REGTEST,
+    }
+}
+
+fn format_to_c(format: &AddressFormat) -> c_int {
+    match format {
+        AddressFormat::Legacy   => BCHADDR_FORMAT_LEGACY,
+        AddressFormat::CashAddr => BCHADDR_FORMAT_CASH_ADDR,
+        AddressFormat::Other(_) => BCHADDR_FORMAT_OTHER,
+    }
+}
+
+fn type_to_c(addr_type: AddressType) -> c_int {
+    match addr_type {
+        AddressType::P2PKH    => BCHADDR_TYPE_P2PKH,
+        AddressType::P2SH     => BCHADDR_TYPE_P2SH,
+        AddressType::Other(_) => BCHADDR_TYPE_OTHER,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ffi_test() {
+        unsafe {
+            assert!(bchaddr_last_error_message().is_null());
+
+            let converter = bchaddr_converter_new();
+            let mut out = ptr::null_mut();
+
+            let legacy = CString::new("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap();
+            assert_eq!(bchaddr_to_cash(converter, legacy.as_ptr(), &mut out), BCHADDR_OK);
+            assert_eq!(CStr::from_ptr(out).to_str().unwrap(), "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+            bchaddr_free_string(out);
+
+            let prefix = CString::new("simpleledger").unwrap();
+            let format_name = CString::new("SLPAddr").unwrap();
+            assert_eq!(bchaddr_converter_add_prefix(converter, prefix.as_ptr(), BCHADDR_NETWORK_MAINNET, format_name.as_ptr()), BCHADDR_OK);
+            assert_eq!(
+                bchaddr_to_cash_with_options(converter, legacy.as_ptr(), format_name.as_ptr(), BCHADDR_NETWORK_UNCHANGED, &mut out),
+                BCHADDR_OK,
+            );
+            let slp = CString::from_raw(out);
+            assert_eq!(slp.to_str().unwrap(), "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg");
+
+            assert_eq!(bchaddr_to_legacy(converter, slp.as_ptr(), &mut out), BCHADDR_OK);
+            assert_eq!(CStr::from_ptr(out), legacy.as_c_str());
+            bchaddr_free_string(out);
+
+            let mut parsed: BchAddrParsed = mem::zeroed();
+            assert_eq!(bchaddr_parse(converter, slp.as_ptr(), &mut parsed), BCHADDR_OK);
+            assert_eq!(parsed.format, BCHADDR_FORMAT_OTHER);
+            assert_eq!(parsed.network, BCHADDR_NETWORK_MAINNET);
+            assert_eq!(parsed.addr_type, BCHADDR_TYPE_P2PKH);
+            assert_eq!(parsed.hash_len, 20);
+            assert_eq!(parsed.type_bits, 0);
+
+            let other = CString::new("bitcoincash:zp24242424242424242424242424242425fpph3eds").unwrap();
+            assert_eq!(bchaddr_parse(converter, other.as_ptr(), &mut parsed), BCHADDR_ERR_INVALID_ADDRESS);
+            assert_eq!(bchaddr_converter_add_type_bits(converter, ptr::null(), [2].as_ptr(), 1), BCHADDR_OK);
+            assert_eq!(bchaddr_parse(converter, other.as_ptr(), &mut parsed), BCHADDR_OK);
+            assert_eq!(parsed.addr_type, BCHADDR_TYPE_OTHER);
+            assert_eq!(parsed.type_bits, 2);
+
+            // legacy payloads of any length parse, but only 64 bytes fit
+            let oversized = CString::new(crate::legacy_converter::encode(0x00, &[0xab; 100])).unwrap();
+            assert_eq!(bchaddr_parse(converter, oversized.as_ptr(), &mut parsed), BCHADDR_ERR_INVALID_ADDRESS);
+            assert!(CStr::from_ptr(bchaddr_last_error_message()).to_str().unwrap().contains("100 bytes"));
+
+            let invalid = CString::new("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwl").unwrap();
+            assert_eq!(bchaddr_to_legacy(converter, invalid.as_ptr(), &mut out), BCHADDR_ERR_INVALID_ADDRESS);
+            assert!(CStr::from_ptr(bchaddr_last_error_message()).to_str().unwrap().contains("invalid address"));
+            assert_eq!(bchaddr_to_legacy(converter, ptr::null(), &mut out), BCHADDR_ERR_NULL_POINTER);
+            assert_eq!(CStr::from_ptr(bchaddr_last_error_message()).to_str().unwrap(), "addr is NULL");
+            assert_eq!(bchaddr_converter_add_prefix(converter, prefix.as_ptr(), 7, format_name.as_ptr()), BCHADDR_ERR_INVALID_ARGUMENT);
+            assert_eq!(CStr::from_ptr(bchaddr_last_error_message()).to_str().unwrap(), "unknown network: 7");
+
+            bchaddr_converter_free(converter);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use sha2::{Digest, Sha256};
+
+use super::{Network, AddressType};
+
+/// Default alphabet of `Converter::emoji_fingerprint`: 64 emoji that render as one picture each.
+/// Must never change, since that would change the fingerprint of every address.
+pub const EMOJI_ALPHABET: [&str; 64] = [
+    "🐶", "🐱", "🐭", "🐹", "🐰", "🦊", "🐻", "🐼", "🐨", "🐯", "🦁", "🐮", "🐷", "🐸", "🐵", "🐔",
+    "🐧", "🐦", "🦆", "🦉", "🐴", "🦄", "🐝", "🦋", "🐌", "🐞", "🐢", "🐍", "🐙", "🦀", "🐠", "🐬",
+    "🐳", "🦈", "🐊", "🦒", "🐘", "🦔", "🌵", "🌲", "🍀", "🍁", "🍄", "🌻", "🌙", "⭐", "🔥", "🌈",
+    "🍎", "🍋", "🍌", "🍉", "🍇", "🍓", "🍒", "🥕", "🌽", "🍞", "🧀", "🍕", "🎈", "🎸", "🚀", "⚓",
+];
+
+/// SHA-256 of the canonical identity of an address (network, type and hash) under `tag`,
+/// so that each use of the identity gets unrelated bytes. The same for every spelling of the address.
+pub(crate) fn identity_digest(tag: &[u8], network: Network, addr_type: AddressType, hash: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::default();
+    hasher.input(tag);
+    hasher.input(&[0, network_byte(network), addr_type.version_bits()]);
+    hasher.input(hash);
+
+    let mut digest = [0; 32];
+    digest.copy_from_slice(&hasher.result());
+    digest
+}
+
+/// HMAC-SHA256 of the canonical identity of an address (network, type and hash).
+pub(crate) fn identity_hmac(key: &[u8], network: Network, addr_type: AddressType, hash: &[u8]) -> [u8; 32] {
+    hmac_sha256(key, &[&[network_byte(network), addr_type.version_bits()][..], hash].concat())
+}
+
+fn network_byte(network: Network) -> u8 {
+    match network {
+        Network::Mainnet => 0,
+        Network::Testnet => 1,
+        Network::Regtest => 2,
+    }
+}
+
+/// HMAC-SHA256 (RFC 2104).
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    const BLOCK_LEN: usize = 64;
+
+    let mut block = [0; BLOCK_LEN];
+    if key.len() > BLOCK_LEN {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sha256::default();
+    inner.input(&block.map(|b| b ^ 0x36));
+    inner.input(message);
+    let mut outer = Sha256::default();
+    outer.input(&block.map(|b| b ^ 0x5c));
+    outer.input(&inner.result());
+
+    let mut mac = [0; 32];
+    mac.copy_from_slice(&outer.result());
+    mac
+}
+
+/// `len` symbols of `alphabet` picked by the identity of an address.
+/// Each symbol takes 64 bits of a stream of digests, so any length and alphabet size work without noticeable bias.
+pub(crate) fn emoji<'a>(network: Network, addr_type: AddressType, hash: &[u8], len: usize, alphabet: &[&'a str]) -> Vec<&'a str> {
+    assert!(!alphabet.is_empty(), "empty emoji alphabet");
+
+    let mut symbols = Vec::with_capacity(len);
+    for block in 0..len.div_ceil(4) {
+        let digest = identity_digest(format!("bch_addr emoji {}", block).as_bytes(), network, addr_type, hash);
+        for chunk in digest.chunks(8).take(len - symbols.len()) {
+            let mut value = [0; 8];
+            value.copy_from_slice(chunk);
+            symbols.push(alphabet[(u64::from_le_bytes(value) % alphabet.len() as u64) as usize]);
+        }
+    }
+    symbols
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    use crate::Converter;
+    use crate::hash::to_hex;
+
+    // Words must never change for a given address; see `wordlist::WORDS`.
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    #[test]
+    fn checkwords_test() {
+        let converter = Converter::new();
+        let words = converter.checkwords("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", 4).unwrap();
+        assert_eq!(words, converter.checkwords("QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK", 4).unwrap());
+        assert_eq!(words, ["cedar", "crown", "elbow", "prism"]);
+    }
+
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    #[test]
+    fn visual_seed_test() {
+        let converter = Converter::new();
+        let seed = converter.visual_seed("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap();
+        assert_eq!(to_hex(&seed), "b790594c9c8c6b68a28b368e6d0515b6a2bb3df7986e3f7388efe62892f50dc7");
+        assert_ne!(seed, converter.visual_seed("bitcoincash:pph5kuz78czq00e3t85ugpgd7xmer5kr7crv8a2z4t").unwrap());
+        assert!(converter.visual_seed("bitcoincash:bad").is_err());
+    }
+
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    #[test]
+    fn emoji_fingerprint_test() {
+        let converter = Converter::new();
+        let emoji = converter.emoji_fingerprint("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", 6).unwrap();
+        assert_eq!(emoji.concat(), "🍋🐼🧀🦀🌵🌲");
+        assert_eq!(converter.emoji_fingerprint("qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", 3).unwrap(), emoji[..3]);
+
+        let bits = converter.emoji_fingerprint_with("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", 9, &["0", "1"]).unwrap();
+        assert_eq!(bits.concat(), "110101111");
+        assert!(converter.emoji_fingerprint("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", 0).unwrap().is_empty());
+    }
+
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    #[test]
+    fn short_id_test() {
+        let converter = Converter::new();
+        let id = converter.short_id("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap();
+        assert_eq!(id, 15296792213624883171);
+        assert_eq!(id, converter.short_id("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap());
+        assert_eq!(id, converter.short_id_with_key("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", &[0; 16]).unwrap());
+        assert_ne!(id, converter.short_id_with_key("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", &[1; 16]).unwrap());
+        assert!(converter.short_id("bitcoincash:bad").is_err());
+    }
+
+    #[test]
+    fn hmac_sha256_test() {
+        // RFC 4231, test cases 2 and 6
+        assert_eq!(
+            to_hex(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+        );
+        assert_eq!(
+            to_hex(&hmac_sha256(&[0xaa; 131], b"Test Using Larger Than Block-Size Key - Hash Key First")),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+        );
+    }
+
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    #[test]
+    fn pseudonymize_test() {
+        let converter = Converter::new();
+        let token = converter.pseudonymize("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", b"secret").unwrap();
+        assert_eq!(token, "04bbbd6186cafedabea08d5cc5fe251f63e40673254845f0a90e34688d083edc");
+        assert_eq!(token, converter.pseudonymize("QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK", b"secret").unwrap());
+        assert_ne!(token, converter.pseudonymize("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", b"other").unwrap());
+        assert!(converter.pseudonymize("bitcoincash:bad", b"secret").is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use super::{Converter, AddressFormat};
+use super::cash_addr::{self, MAX_BODY_LEN};
+
+/// Longest legacy address, for a 20-byte hash.
+const MAX_LEGACY_LEN: usize = 35;
+
+/// Weight of each format `partial` can start, one per matching prefix.
+pub(crate) fn guess_format(converter: &Converter, partial: &str) -> Vec<(AddressFormat, f32)> {
+    let mut weights: Vec<(AddressFormat, f32)> = Vec::new();
+    let mut add = |format: &AddressFormat| {
+        match weights.iter_mut().find(|(f, _)| f == format) {
+            Some((_, weight)) => *weight += 1.0,
+            None              => weights.push((format.clone(), 1.0)),
+        }
+    };
+
+    let mixed_case = partial.bytes().any(|c| c.is_ascii_lowercase()) && partial.bytes().any(|c| c.is_ascii_uppercase());
+
+    match partial.find(':') {
+        // the prefix is complete: it alone decides the format
+        Some(sep) => {
+            if let Some((format, _)) = converter.cash_converter.prefix_details(&partial[..sep]) {
+                if !mixed_case && is_cash_body(&partial[sep + 1..], true) {
+                    add(format);
+                }
+            }
+        },
+        None if !partial.is_empty() => {
+            if is_legacy(partial) {
+                add(&AddressFormat::Legacy);
+            }
+            if !mixed_case {
+                if is_cash_body(partial, false) {
+                    add(&AddressFormat::CashAddr);
+                }
+                let lower = partial.to_ascii_lowercase();
+                for (prefix, format, _) in converter.cash_converter.prefixes() {
+                    if prefix.starts_with(&lower) {
+                        add(format);
+                    }
+                }
+                for (alias, _) in converter.cash_converter.aliases() {
+                    match converter.cash_converter.prefix_details(alias) {
+                        Some((format, _)) if alias.starts_with(&lower) => add(format),
+                        _                                              => {},
+                    }
+                }
+            }
+        },
+        None => {},
+    }
+
+    let total: f32 = weights.iter().map(|(_, weight)| weight).sum();
+    for (_, weight) in &mut weights {
+        *weight /= total;
+    }
+    weights.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    weights
+}
+
+/// `partial` can start a legacy address: a known leading character followed by Base58 characters.
+fn is_legacy(partial: &str) -> bool {
+    let bytes = partial.as_bytes();
+    matches!(bytes[0], b'1' | b'3' | b'm' | b'n' | b'2')
+        && bytes.len() <= MAX_LEGACY_LEN
+        && bytes.iter().all(|&c| c.is_ascii_alphanumeric() && !b"0OIl".contains(&c))
+}
+
+/// `body` can start the part of a cash_addr address following the separator:
+/// `q` or `p` for the type, then cash_addr characters. `allow_empty` accepts a bare prefix and separator.
+fn is_cash_body(body: &str, allow_empty: bool) -> bool {
+    match body.as_bytes().first() {
+        Some(c) => matches!(c.to_ascii_lowercase(), b'q' | b'p')
+            && body.len() <= MAX_BODY_LEN
+            && body.bytes().all(|c| cash_addr::decode_char(c).is_some()),
+        None    => allow_empty,
+    }
+}
+
+#[cfg(all(test, feature = "cashaddr"))]
+mod tests {
+    use super::*;
+    use crate::Network;
+
+    #[test]
+    fn guess_format_test() {
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet), ("slptest", Network::Testnet)], "SLPAddr");
+        let slp = AddressFormat::Other("SLPAddr".to_string());
+        let formats = |partial| -> Vec<AddressFormat> {
+            guess_format(&converter, partial).into_iter().map(|(format, _)| format).collect()
+        };
+
+        assert!(formats("").is_empty());
+        assert_eq!(formats("1B9UNtBf"), [AddressFormat::Legacy]);
+        assert_eq!(formats("qph5kuz"), [AddressFormat::CashAddr]);
+        assert_eq!(formats("QPH5KUZ"), [AddressFormat::CashAddr]);
+        assert_eq!(formats("bitco"), [AddressFormat::CashAddr]);
+        assert_eq!(formats("BCHT"), [AddressFormat::CashAddr]);
+        assert_eq!(formats("s"), formats("slpt"));
+        assert_eq!(formats("bitcoincash:"), [AddressFormat::CashAddr]);
+        assert_eq!(formats("simpleledger:qph5"), [slp]);
+        assert_eq!(formats("m"), [AddressFormat::Legacy]);
+        assert_eq!(formats("pp"), [AddressFormat::CashAddr]);
+
+        let converter = Converter::new().add_prefixes(&[("bchtoken", Network::Mainnet)], "Token");
+        assert_eq!(guess_format(&converter, "bch"), [
+            (AddressFormat::CashAddr, 2.0 / 3.0),
+            (AddressFormat::Other("Token".to_string()), 1.0 / 3.0),
+        ]);
+        assert_eq!(guess_format(&converter, "bchto"), [(AddressFormat::Other("Token".to_string()), 1.0)]);
+
+        assert!(formats("bitcoincash:b").is_empty());
+        assert!(formats("unknown:qph5").is_empty());
+        assert!(formats("qPh5").is_empty());
+        assert!(formats("1B9U0").is_empty());
+        assert!(formats(&"1".repeat(MAX_LEGACY_LEN + 1)).is_empty());
+    }
+}
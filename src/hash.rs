@@ -0,0 +1,139 @@
+//! Hashes used by addresses.
+
+#[cfg(feature = "hash160")]
+use ripemd160::Ripemd160;
+use sha2::{Digest, Sha256};
+
+/// Length of a hash160 digest.
+pub const HASH160_LEN: usize = 20;
+
+/// hash160 implementation for `Converter::from_pubkey_with` and `Converter::from_redeem_script_with`,
+/// e.g. a FIPS-validated module or a hardware accelerator.
+/// Closures `Fn(&[u8]) -> [u8; HASH160_LEN]` implement it.
+/// # Example
+/// ```
+/// # #[cfg(all(feature = "cashaddr", feature = "hash160"))]
+/// # {
+/// # use bch_addr::{Converter, AddressFormat, Network};
+/// use bch_addr::hash::{self, Hasher160, HASH160_LEN};
+///
+/// struct Counting(std::cell::Cell<usize>);
+///
+/// impl Hasher160 for Counting {
+///     fn hash160(&self, data: &[u8]) -> [u8; HASH160_LEN] {
+///         self.0.set(self.0.get() + 1);
+///         hash::hash160(data)
+///     }
+/// }
+///
+/// let hasher = Counting(std::cell::Cell::new(0));
+/// let addr = Converter::new().from_redeem_script_with(&hasher, &[0x51], &AddressFormat::CashAddr, Network::Mainnet).unwrap();
+/// assert_eq!(hasher.0.get(), 1);
+/// # }
+/// ```
+pub trait Hasher160 {
+    /// RIPEMD-160 of SHA-256 of `data`.
+    fn hash160(&self, data: &[u8]) -> [u8; HASH160_LEN];
+}
+
+impl<F: Fn(&[u8]) -> [u8; HASH160_LEN]> Hasher160 for F {
+    fn hash160(&self, data: &[u8]) -> [u8; HASH160_LEN] {
+        self(data)
+    }
+}
+
+/// `Hasher160` with the `sha2` and `ripemd160` crates, used by `Converter::from_pubkey` and
+/// `Converter::from_redeem_script`. Enabled by the default `hash160` feature.
+#[cfg(feature = "hash160")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DefaultHasher160;
+
+#[cfg(feature = "hash160")]
+impl Hasher160 for DefaultHasher160 {
+    fn hash160(&self, data: &[u8]) -> [u8; HASH160_LEN] {
+        hash160(data)
+    }
+}
+
+/// RIPEMD-160 of SHA-256 of `data`, as used for P2PKH and P2SH hashes.
+#[cfg(feature = "hash160")]
+pub fn hash160(data: &[u8]) -> [u8; HASH160_LEN] {
+    let digest = Ripemd160::digest(&Sha256::digest(data));
+
+    let mut hash = [0; HASH160_LEN];
+    hash.copy_from_slice(&digest);
+    hash
+}
+
+/// Length of a Base58Check checksum.
+pub const BASE58_CHECKSUM_LEN: usize = 4;
+
+/// Base58Check checksum of `data` (version byte and hash): the first 4 bytes of its double SHA-256.
+/// # Arguments
+/// * `data` - Payload.
+/// # Returns
+/// * Checksum appended to the payload before Base58 encoding.
+/// # Example
+/// ```
+/// use bch_addr::hash::base58_checksum;
+///
+/// let payload = [0x00, 0xf5, 0x4a, 0x58, 0x51, 0xe9, 0x37, 0x2b, 0x87, 0x81, 0x0a, 0x8e, 0x60, 0xcd, 0xd2, 0xe7, 0xcf, 0xd8, 0x0b, 0x6e, 0x31];
+/// assert_eq!(base58_checksum(&payload), [0xc7, 0xf1, 0x8f, 0xe8]);
+/// ```
+pub fn base58_checksum(data: &[u8]) -> [u8; BASE58_CHECKSUM_LEN] {
+    let digest = Sha256::digest(&Sha256::digest(data));
+
+    let mut checksum = [0; BASE58_CHECKSUM_LEN];
+    checksum.copy_from_slice(&digest[..BASE58_CHECKSUM_LEN]);
+    checksum
+}
+
+/// Lowercase hex of `bytes`.
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Bytes of `hex`, in either case; `None` unless it is an even number of hex digits.
+pub(crate) fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+
+    let digit = |c: u8| char::from(c).to_digit(16).map(|d| d as u8);
+    hex.as_bytes().chunks_exact(2)
+        .map(|pair| Some(digit(pair[0])? << 4 | digit(pair[1])?))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "hash160")]
+    #[test]
+    fn hash160_test() {
+        // https://en.bitcoin.it/wiki/Technical_background_of_version_1_Bitcoin_addresses
+        let pubkey = [
+            0x02, 0x50, 0x86, 0x3a, 0xd6, 0x4a, 0x87, 0xae, 0x8a, 0x2f, 0xe8, 0x3c, 0x1a, 0xf1, 0xa8, 0x40,
+            0x3c, 0xb5, 0x3f, 0x53, 0xe4, 0x86, 0xd8, 0x51, 0x1d, 0xad, 0x8a, 0x04, 0x88, 0x7e, 0x5b, 0x23,
+            0x52,
+        ];
+        let hash = [
+            0xf5, 0x4a, 0x58, 0x51, 0xe9, 0x37, 0x2b, 0x87, 0x81, 0x0a, 0x8e, 0x60, 0xcd, 0xd2, 0xe7, 0xcf,
+            0xd8, 0x0b, 0x6e, 0x31,
+        ];
+        assert_eq!(hash160(&pubkey), hash);
+        assert_eq!(DefaultHasher160.hash160(&pubkey), hash);
+        assert_eq!((|_: &[u8]| hash).hash160(&[]), hash);
+    }
+
+    #[test]
+    fn hex_test() {
+        assert_eq!(to_hex(&[0x00, 0x6f, 0xff]), "006fff");
+        assert_eq!(from_hex("006fFF").unwrap(), [0x00, 0x6f, 0xff]);
+        assert!(from_hex("").unwrap().is_empty());
+        assert_eq!(from_hex("abc"), None);
+        assert_eq!(from_hex("+a"), None);
+        assert_eq!(from_hex("éa"), None);
+    }
+}
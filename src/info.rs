@@ -0,0 +1,95 @@
+use super::{AddressFormat, Network};
+
+/// How a conversion result was derived, returned by the `*_with_info` conversion methods.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ConversionInfo {
+    /// Format detected for the input.
+    pub source_format: AddressFormat,
+    /// Network detected for the input.
+    pub source_network: Network,
+    /// Changes made besides the format, in the order listed in `Normalization`.
+    /// Empty when the input is returned as is.
+    pub normalizations: Vec<Normalization>,
+    /// Prefix of the result, `None` for legacy addresses.
+    pub target_prefix: Option<String>,
+}
+
+/// Change made to an address besides converting its format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Normalization {
+    /// The input had no prefix; the result has one.
+    PrefixAdded,
+    /// The input was uppercase; the result is lowercase.
+    Lowercased,
+    /// The result is on another network than the input.
+    NetworkChanged,
+}
+
+/// Normalizations turning `input` into `output`.
+pub(crate) fn normalizations(input: &str, output: &str, source_format: &AddressFormat, source_network: Network, target_network: Network) -> Vec<Normalization> {
+    let mut normalizations = Vec::new();
+    if input == output {
+        return normalizations;
+    }
+
+    if *source_format != AddressFormat::Legacy && output.contains(':') {
+        if !input.contains(':') {
+            normalizations.push(Normalization::PrefixAdded);
+        }
+        if input.bytes().any(|c| c.is_ascii_uppercase()) {
+            normalizations.push(Normalization::Lowercased);
+        }
+    }
+    if source_network != target_network {
+        normalizations.push(Normalization::NetworkChanged);
+    }
+    normalizations
+}
+
+#[cfg(all(test, feature = "legacy", feature = "cashaddr"))]
+mod tests {
+    use super::*;
+    use crate::Converter;
+
+    #[test]
+    fn conversion_info_test() {
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+
+        // same format and network: returned as is
+        let (addr, info) = converter.to_cash_addr_with_info("QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK", None, Some(Network::Mainnet)).unwrap();
+        assert_eq!(addr, "QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK");
+        assert!(info.normalizations.is_empty());
+        assert_eq!(info.target_prefix.unwrap(), "bitcoincash");
+
+        let (addr, info) = converter.to_cash_addr_with_info("QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK", None, Some(Network::Regtest)).unwrap();
+        assert_eq!(addr, "bchreg:qph5kuz78czq00e3t85ugpgd7xmer5kr7c28g5v92v");
+        assert!(info.normalizations.contains(&Normalization::NetworkChanged));
+        assert_eq!(info.target_prefix.unwrap(), "bchreg");
+
+        let (addr, info) = converter.to_cash_addr_with_info("mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi", None, Some(Network::Regtest)).unwrap();
+        assert_eq!(addr, "bchreg:qph5kuz78czq00e3t85ugpgd7xmer5kr7c28g5v92v");
+        assert_eq!(info, ConversionInfo {
+            source_format: AddressFormat::Legacy,
+            source_network: Network::Testnet,
+            normalizations: vec![Normalization::NetworkChanged],
+            target_prefix: Some("bchreg".to_string()),
+        });
+
+        let slp = AddressFormat::Other("SLPAddr".to_string());
+        let (addr, info) = converter.to_cash_addr_with_info("QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK", Some(slp.clone()), None).unwrap();
+        assert_eq!(addr, "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg");
+        assert_eq!(info.normalizations, [Normalization::PrefixAdded, Normalization::Lowercased]);
+
+        let (_, info) = converter.to_cash_addr_with_info("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", Some(slp), None).unwrap();
+        assert!(info.normalizations.is_empty());
+        assert_eq!(info.target_prefix.unwrap(), "simpleledger");
+
+        let (addr, info) = converter.to_legacy_addr_with_info("BCHTEST:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7CSM740KF2").unwrap();
+        assert_eq!(addr, "mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi");
+        assert_eq!(info.source_network, Network::Testnet);
+        assert!(info.normalizations.is_empty());
+
+        assert!(converter.to_cash_addr_with_info("bitcoincash:bad", None, None).is_err());
+        assert!(converter.to_legacy_addr_with_info("bitcoincash:bad").is_err());
+    }
+}
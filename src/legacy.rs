@@ -0,0 +1,13 @@
+//! Base58Check codec of legacy addresses, with the version byte given as is.
+//! For non-standard version bytes, e.g. of other chains or of former Bitpay addresses,
+//! or other Base58Check data, without reimplementing the checksum.
+//! # Example
+//! ```rust
+//! use bch_addr::legacy::{decode, encode};
+//!
+//! let (version_byte, payload) = decode("3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9").unwrap();
+//! assert_eq!(version_byte, 0x05);
+//! assert_eq!(encode(version_byte, &payload), "3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9");
+//! ```
+
+pub use super::legacy_converter::{decode, decode_into, encode, encode_into, max_len};
@@ -2,32 +2,270 @@ use super::AddressType;
 use super::AddressFormat;
 use super::Network;
 use super::error::{Error, Result};
+use super::hash;
+use super::cash_addr;
 
-use bs58;
+use sha2::{Digest, Sha256};
+
+const CHECKSUM_LEN: usize = 4;
 
 pub fn parse(addr: &str) -> Result<(AddressFormat, Network, AddressType, Vec<u8>)> {
-    let data = bs58::decode(addr).with_check(None).into_vec()?;
-    let (network, addr_type) = match data[0] {
-        0x00 => Ok((Network::Mainnet, AddressType::P2PKH)),
-        0x05 => Ok((Network::Mainnet, AddressType::P2SH)),
-        0x6f => Ok((Network::Testnet, AddressType::P2PKH)),
-        0xc4 => Ok((Network::Testnet, AddressType::P2SH)),
-        e    => Err(Error::UnknownLegacyPrefix(e)),
-    }?;
-    let data = &data[1..];
+    let mut hash = Vec::new();
+    let (network, addr_type) = parse_into(addr, &mut hash)?;
 
-    Ok((AddressFormat::Legacy, network, addr_type, data.to_vec()))
+    Ok((AddressFormat::Legacy, network, addr_type, hash))
 }
 
-pub fn build(network: Network, addr_type: AddressType, hash: &[u8]) -> Result<String> {
-    let prefix = match (network, addr_type) {
-        (Network::Mainnet, AddressType::P2PKH) => 0x00,
-        (Network::Mainnet, AddressType::P2SH)  => 0x05,
-        (Network::Testnet, AddressType::P2PKH) => 0x6f,
-        (Network::Testnet, AddressType::P2SH)  => 0xc4,
-        (Network::Regtest, AddressType::P2PKH) => 0x6f,
-        (Network::Regtest, AddressType::P2SH)  => 0xc4,
+/// Same as `parse` but writes the hash into `hash`, reusing its allocation.
+pub fn parse_into(addr: &str, hash: &mut Vec<u8>) -> Result<(Network, AddressType)> {
+    let version_byte = decode_into(addr, hash)?;
+    version_byte_info(version_byte).ok_or(Error::UnknownLegacyPrefix(version_byte))
+}
+
+/// Classify an already Base58-decoded payload: version byte and 20-byte hash, optionally followed by the checksum.
+/// The checksum, if present, is verified. Needs no Base58 code, so it works without the `legacy` feature.
+pub fn parse_raw(data: &[u8]) -> Result<(Network, AddressType, &[u8])> {
+    let invalid = || Error::InvalidAddress(hash::to_hex(data));
+    let (&version_byte, rest) = data.split_first().ok_or_else(invalid)?;
+    let hash = match rest.len() {
+        hash::HASH160_LEN => rest,
+        len if len == hash::HASH160_LEN + CHECKSUM_LEN => {
+            let (hash, check) = rest.split_at(hash::HASH160_LEN);
+            if checksum(version_byte, hash) != check {
+                return Err(invalid());
+            }
+            hash
+        }
+        _ => return Err(invalid()),
     };
-    let hash = [&[prefix], &hash[..]].concat();
-    Ok(bs58::encode(hash).with_check().into_string())
+
+    let (network, addr_type) = version_byte_info(version_byte).ok_or(Error::UnknownLegacyPrefix(version_byte))?;
+    Ok((network, addr_type, hash))
+}
+
+/// Decode a Base58Check string into its version byte and payload, whatever the version byte and payload length.
+/// # Arguments
+/// * `data` - Base58Check string, e.g. a legacy address.
+/// # Returns
+/// * Version byte.
+/// * Payload following the version byte, without the checksum.
+/// # Example
+/// ```
+/// use bch_addr::legacy::decode;
+///
+/// let (version_byte, payload) = decode("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap();
+/// assert_eq!(version_byte, 0x00);
+/// assert_eq!(payload.len(), 20);
+/// assert!(decode("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzr").is_err());
+/// ```
+#[cfg(feature = "legacy")]
+pub fn decode(data: &str) -> Result<(u8, Vec<u8>)> {
+    let mut payload = Vec::new();
+    let version_byte = decode_into(data, &mut payload)?;
+    Ok((version_byte, payload))
+}
+
+/// Same as `decode` but writes the payload into `payload`, reusing its allocation.
+/// # Arguments
+/// * `data` - Base58Check string.
+/// * `payload` - Buffer receiving the payload.
+/// # Returns
+/// * Version byte.
+#[cfg(feature = "legacy")]
+pub fn decode_into(data: &str, payload: &mut Vec<u8>) -> Result<u8> {
+    // Base58 never decodes to more bytes than there are input characters
+    payload.clear();
+    payload.resize(data.len(), 0);
+    let len = bs58::decode(data).with_check(None).into(&mut payload[..])?;
+    let &version_byte = payload[..len].first().ok_or_else(|| Error::InvalidAddress(data.to_string()))?;
+
+    payload.copy_within(1..len, 0);
+    payload.truncate(len - 1);
+    Ok(version_byte)
+}
+
+#[cfg(not(feature = "legacy"))]
+pub fn decode_into(_data: &str, _payload: &mut Vec<u8>) -> Result<u8> {
+    Err(not_supported())
+}
+
+/// Same as `parse` but decodes into a stack buffer and skips copying the hash.
+#[cfg(feature = "legacy")]
+pub fn classify(addr: &str) -> Result<(Network, AddressType)> {
+    // Base58 never decodes to more bytes than there are input characters
+    let mut buf = [0; 128];
+    if addr.len() > buf.len() {
+        return parse(addr).map(|(_, network, addr_type, _)| (network, addr_type));
+    }
+
+    let len = bs58::decode(addr).with_check(None).into(&mut buf[..])?;
+    version_info(addr, &buf[..len])
+}
+
+#[cfg(not(feature = "legacy"))]
+pub fn classify(_addr: &str) -> Result<(Network, AddressType)> {
+    Err(not_supported())
+}
+
+/// Check the Base58Check checksum of `addr` whatever its version byte and length.
+#[cfg(feature = "legacy")]
+pub fn verify_checksum(addr: &str) -> Result<()> {
+    bs58::decode(addr).with_check(None).into_vec()?;
+    Ok(())
+}
+
+#[cfg(not(feature = "legacy"))]
+pub fn verify_checksum(_addr: &str) -> Result<()> {
+    Err(not_supported())
+}
+
+#[cfg(feature = "legacy")]
+fn version_info(addr: &str, data: &[u8]) -> Result<(Network, AddressType)> {
+    let &version_byte = data.first().ok_or_else(|| Error::InvalidAddress(addr.to_string()))?;
+    version_byte_info(version_byte).ok_or(Error::UnknownLegacyPrefix(version_byte))
+}
+
+/// Network and type of a version byte; the inverse of `version_byte`, with regtest reading as testnet.
+pub fn version_byte_info(version_byte: u8) -> Option<(Network, AddressType)> {
+    match version_byte {
+        0x00 => Some((Network::Mainnet, AddressType::P2PKH)),
+        0x05 => Some((Network::Mainnet, AddressType::P2SH)),
+        0x6f => Some((Network::Testnet, AddressType::P2PKH)),
+        0xc4 => Some((Network::Testnet, AddressType::P2SH)),
+        _    => None,
+    }
+}
+
+pub fn build(network: Network, addr_type: AddressType, hash: &[u8]) -> Result<String> {
+    let mut buf = vec![0; max_len(hash.len())];
+    let len = build_into(network, addr_type, hash, &mut buf)?;
+    Ok(buf[..len].iter().map(|&c| char::from(c)).collect())
+}
+
+/// Upper bound of the length of `encode` for a payload of `payload_len` bytes, to size `encode_into` buffers.
+pub fn max_len(payload_len: usize) -> usize {
+    // log(256) / log(58) < 1.38
+    (1 + payload_len + CHECKSUM_LEN) * 138 / 100 + 1
+}
+
+/// Leading byte of the Base58Check payload, `None` for types legacy addresses cannot carry.
+pub fn version_byte(network: Network, addr_type: AddressType) -> Option<u8> {
+    match (network, addr_type) {
+        (Network::Mainnet, AddressType::P2PKH) => Some(0x00),
+        (Network::Mainnet, AddressType::P2SH)  => Some(0x05),
+        (Network::Testnet, AddressType::P2PKH) => Some(0x6f),
+        (Network::Testnet, AddressType::P2SH)  => Some(0xc4),
+        (Network::Regtest, AddressType::P2PKH) => Some(0x6f),
+        (Network::Regtest, AddressType::P2SH)  => Some(0xc4),
+        (_, AddressType::Other(_))             => None,
+    }
+}
+
+/// Encode a legacy address; only HASH160 payloads are standard, so other hash lengths are rejected.
+pub fn build_into(network: Network, addr_type: AddressType, hash: &[u8], out: &mut [u8]) -> Result<usize> {
+    if hash.len() != hash::HASH160_LEN {
+        return Err(cash_addr::Error::InvalidHashSize(hash.len()).into());
+    }
+    let version_byte = version_byte(network, addr_type).ok_or(Error::UnsupportedAddressType(addr_type))?;
+    encode_into(version_byte, hash, out)
+}
+
+/// Encode a version byte and payload as Base58Check, whatever the version byte and payload length.
+/// # Arguments
+/// * `version_byte` - Version byte.
+/// * `payload` - Payload, e.g. a hash.
+/// # Returns
+/// * Base58Check string.
+/// # Example
+/// ```
+/// use bch_addr::legacy::{decode, encode};
+///
+/// let (_, payload) = decode("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap();
+/// assert_eq!(encode(0x00, &payload), "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR");
+/// // Bitpay's former P2PKH version byte
+/// assert_eq!(encode(0x1c, &payload), "CScMwvXjdooDnGevHgfHjGWFi9cjk75Aaj");
+/// ```
+#[cfg(feature = "legacy")]
+pub fn encode(version_byte: u8, payload: &[u8]) -> String {
+    let mut buf = vec![0; max_len(payload.len())];
+    let len = encode_into(version_byte, payload, &mut buf).expect("`max_len` bytes are enough");
+    buf[..len].iter().map(|&c| char::from(c)).collect()
+}
+
+/// Same as `encode` but writes into `out` without allocating.
+/// # Arguments
+/// * `version_byte` - Version byte.
+/// * `payload` - Payload.
+/// * `out` - Output buffer; `max_len(payload.len())` bytes are always enough.
+/// # Returns
+/// * Number of bytes written, or `Error::BufferTooSmall` with `max_len(payload.len())`.
+#[cfg(feature = "legacy")]
+pub fn encode_into(version_byte: u8, payload: &[u8], out: &mut [u8]) -> Result<usize> {
+    let checksum = checksum(version_byte, payload);
+    let version_byte = [version_byte];
+    let data = version_byte.iter().chain(payload).chain(&checksum);
+    encode_base58_into(data, out, max_len(payload.len()))
+}
+
+/// Base58Check checksum: the first bytes of the double SHA-256 of the version byte and payload.
+fn checksum(version_byte: u8, payload: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let mut hasher = Sha256::default();
+    hasher.input(&[version_byte]);
+    hasher.input(payload);
+    let digest = Sha256::digest(&hasher.result());
+
+    let mut checksum = [0; CHECKSUM_LEN];
+    checksum.copy_from_slice(&digest[..CHECKSUM_LEN]);
+    checksum
+}
+
+#[cfg(not(feature = "legacy"))]
+pub fn encode_into(_version_byte: u8, _payload: &[u8], _out: &mut [u8]) -> Result<usize> {
+    Err(not_supported())
+}
+
+/// Error of the Base58 paths in builds without the `legacy` feature.
+#[cfg(not(feature = "legacy"))]
+fn not_supported() -> Error {
+    Error::NotSupported("legacy")
+}
+
+/// Base58 encode `data` into `out` without allocation.
+/// The digits are accumulated in `out` itself (least significant first) and reversed at the end.
+#[cfg(feature = "legacy")]
+fn encode_base58_into<'a, I>(data: I, out: &mut [u8], max_len: usize) -> Result<usize>
+    where I: Iterator<Item = &'a u8> + Clone
+{
+    let mut len = 0;
+    for &byte in data.clone() {
+        let mut carry = usize::from(byte);
+        for digit in out[..len].iter_mut() {
+            carry += usize::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            if len == out.len() {
+                return Err(Error::BufferTooSmall(max_len));
+            }
+            out[len] = (carry % 58) as u8;
+            len += 1;
+            carry /= 58;
+        }
+    }
+
+    for _ in data.take_while(|&&byte| byte == 0) {
+        if len == out.len() {
+            return Err(Error::BufferTooSmall(max_len));
+        }
+        out[len] = 0;
+        len += 1;
+    }
+
+    out[..len].reverse();
+    for digit in out[..len].iter_mut() {
+        *digit = bs58::alphabet::BITCOIN[usize::from(*digit)];
+    }
+
+    Ok(len)
 }
@@ -7,6 +7,8 @@
 //! cash_addr format implementation inspired by bchaddrjs.
 //! # Example
 //! ```
+//! # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+//! # {
 //! use bch_addr::Converter;
 //! let converter = Converter::new();
 //! let cash_addr = converter.to_cash_addr("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap();
@@ -14,18 +16,98 @@
 //! 
 //! let legacy_addr = converter.to_legacy_addr(&cash_addr).unwrap();
 //! assert_eq!(legacy_addr, "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR");
+//! # }
 //! ```
 
 mod error;
 mod cash_converter;
 mod legacy_converter;
+mod context;
+mod uri;
+mod db;
+mod trace;
+mod observer;
+mod address_set;
+mod watch;
+mod scan;
+mod diff;
+mod info;
+mod guess;
+mod validator;
+mod fingerprint;
+mod wordlist;
+mod capabilities;
+pub mod cash_addr;
+#[cfg(feature = "legacy")]
+pub mod legacy;
+pub mod hash;
+pub mod node_compat;
+pub mod script;
+pub mod fee;
+pub mod batch;
+#[cfg(feature = "cashaddr")]
+pub mod vanity;
+pub mod compat;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "uniffi")]
+pub mod mobile;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+#[cfg(feature = "spec-vectors")]
+pub mod spec_vectors;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "clap")]
+pub mod clap;
+#[cfg(any(feature = "axum", feature = "actix"))]
+pub mod web;
+#[cfg(feature = "addressbook")]
+pub mod addressbook;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+#[cfg(feature = "report")]
+pub mod report;
+#[cfg(feature = "async")]
+pub mod stream;
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
 
 pub use cash_addr::AddressType as AddressType;
-pub use error::{Error, Result};
+pub use error::{Error, Result, ErrorMessages, ErrorDisplay};
+pub use context::ConversionCtx;
+pub use db::DbAddress;
+pub use observer::Observer;
+pub use address_set::AddressSet;
+pub use watch::{WatchList, WatchTarget};
+pub use validator::{IncrementalValidator, ValidationState};
+pub use fingerprint::EMOJI_ALPHABET;
+pub use scan::{AddressMatch, ScanReader, StreamMatch};
+pub use diff::AddressDiff;
+pub use info::{ConversionInfo, Normalization};
+pub use capabilities::{capabilities, Capabilities, SpecRevision};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::iter;
+use std::sync::Arc;
+
 use cash_converter::CashConverter;
+use observer::ObserverSlot;
+use hash::Hasher160;
+
+/// Buffer length sufficient for any address built by `Converter::encode_into`
+/// with the built-in prefixes (up to 512-bit hashes).
+/// User-defined prefixes longer than `bitcoincash` need the difference on top.
+pub const MAX_ADDR_LEN: usize = "bitcoincash:".len() + cash_addr::MAX_BODY_LEN;
 
 /// Type of bitcoin network
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq), derive(Debug)))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Network {
     /// mainnet
     Mainnet,
@@ -37,6 +119,9 @@ pub enum Network {
 
 /// Type of address format
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq), derive(Debug)))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum AddressFormat {
     /// Legacy format.
     /// Same as bitcoin core address.
@@ -58,12 +143,222 @@ pub enum AddressFormat {
     Other(String),
 }
 
+/// Letter case of cash_addr output.
+/// Addresses are case-insensitive but must not mix cases; uppercase packs tighter into QR codes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Case {
+    /// Lowercase, the canonical form.
+    #[default]
+    Lower,
+    /// Uppercase.
+    Upper,
+    /// Uppercase if the input is a cash_addr address in uppercase, lowercase otherwise.
+    /// Legacy inputs have no case convention and give lowercase.
+    MatchInput,
+}
+
+impl Case {
+    /// Apply to `output`, converted from `input` of format `input_format`.
+    fn apply(self, input: &str, input_format: &AddressFormat, mut output: String) -> String {
+        let upper = match self {
+            Case::Lower      => false,
+            Case::Upper      => true,
+            Case::MatchInput => *input_format != AddressFormat::Legacy && !input.bytes().any(|c| c.is_ascii_lowercase()),
+        };
+        if upper {
+            output.make_ascii_uppercase();
+        } else {
+            output.make_ascii_lowercase();
+        }
+        output
+    }
+}
+
+/// Parsed address.
+/// Can be reused across `Converter::parse_into` calls to avoid allocating a new hash buffer.
+/// # Example
+/// ```
+/// # use bch_addr::{ParsedAddress, AddressFormat, Network, AddressType};
+/// let parsed = ParsedAddress::default();
+/// assert_eq!(parsed.format, AddressFormat::Legacy);
+/// assert!(parsed.hash.is_empty());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq), derive(Debug)))]
+pub struct ParsedAddress {
+    /// Address format.
+    pub format: AddressFormat,
+    /// Address network.
+    pub network: Network,
+    /// Address type.
+    pub addr_type: AddressType,
+    /// hashed publickey.
+    pub hash: Vec<u8>,
+}
+
+impl ParsedAddress {
+    /// Build an address from its hash, e.g. a hash160 read by an indexer, without going through a string.
+    /// # Arguments
+    /// * `format` - Address format.
+    /// * `network` - Address network.
+    /// * `addr_type` - Address type.
+    /// * `hash` - hashed publickey. 20 bytes for legacy, any cash_addr hash size otherwise.
+    /// # Returns
+    /// * Address, or an error if the format can not encode a hash of that length.
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "legacy")]
+    /// # {
+    /// # use bch_addr::{Converter, ParsedAddress, AddressFormat, Network, AddressType};
+    /// let hash = [0x6f, 0x4b, 0x70, 0x5e, 0x3e, 0x04, 0x07, 0xbf, 0x31, 0x59, 0xe9, 0xc4, 0x05, 0x0d, 0xf1, 0xb7, 0x91, 0xd2, 0xc3, 0xf6];
+    /// let addr = ParsedAddress::from_hash(AddressFormat::Legacy, Network::Mainnet, AddressType::P2PKH, &hash).unwrap();
+    /// assert_eq!(Converter::new().encode(&addr.format, addr.network, addr.addr_type, &addr.hash).unwrap(), "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR");
+    ///
+    /// assert!(ParsedAddress::from_hash(AddressFormat::Legacy, Network::Mainnet, AddressType::P2SH, &[0; 32]).is_err());
+    /// # }
+    /// ```
+    pub fn from_hash(format: AddressFormat, network: Network, addr_type: AddressType, hash: &[u8]) -> Result<ParsedAddress> {
+        let encodable = match format {
+            AddressFormat::Legacy => hash.len() == hash::HASH160_LEN,
+            _                     => cash_addr::version_byte(addr_type, hash).is_ok(),
+        };
+        if !encodable {
+            return Err(cash_addr::Error::InvalidHashSize(hash.len()).into());
+        }
+
+        Ok(ParsedAddress { format, network, addr_type, hash: hash.to_vec() })
+    }
+
+    /// `from_hash` with the hash in hex, as databases often store it.
+    /// # Arguments
+    /// * `format` - Address format.
+    /// * `network` - Address network.
+    /// * `addr_type` - Address type.
+    /// * `hash_hex` - hashed publickey in hex, in either case.
+    /// # Returns
+    /// * Address.
+    /// # Example
+    /// ```
+    /// # use bch_addr::{ParsedAddress, AddressFormat, Network, AddressType};
+    /// let addr = ParsedAddress::from_hash_hex(AddressFormat::CashAddr, Network::Testnet, AddressType::P2SH, "76a04053bda0a88bda5177b86a15c3b29f559873").unwrap();
+    /// assert_eq!(addr.hash.len(), 20);
+    /// assert_eq!(addr.hash_hex(), "76a04053bda0a88bda5177b86a15c3b29f559873");
+    ///
+    /// assert!(ParsedAddress::from_hash_hex(AddressFormat::CashAddr, Network::Testnet, AddressType::P2SH, "76a0405").is_err());
+    /// ```
+    pub fn from_hash_hex(format: AddressFormat, network: Network, addr_type: AddressType, hash_hex: &str) -> Result<ParsedAddress> {
+        let hash = hash::from_hex(hash_hex).ok_or_else(|| Error::InvalidHex(hash_hex.to_string()))?;
+        ParsedAddress::from_hash(format, network, addr_type, &hash)
+    }
+
+    /// Hash in lowercase hex.
+    pub fn hash_hex(&self) -> String {
+        hash::to_hex(&self.hash)
+    }
+
+    /// Serialized size of an output paying to this address. See `fee::output_size`.
+    /// # Returns
+    /// * Size in bytes, `None` if no standard script pays to the hash (other than 20 bytes, or 32 bytes for P2SH).
+    pub fn output_size(&self) -> Option<usize> {
+        fee::output_size_for(self.addr_type, self.hash.len())
+    }
+
+    /// Serialized size of an input spending an output paying to this address. See `fee::input_size`.
+    /// # Arguments
+    /// * `redeem_size` - For P2SH, size of the unlocking script including the pushed redeem script.
+    /// # Returns
+    /// * Size in bytes, `None` for P2SH without `redeem_size`.
+    pub fn input_size(&self, redeem_size: Option<usize>) -> Option<usize> {
+        fee::input_size(self.addr_type, redeem_size)
+    }
+
+    /// Dust threshold of an output paying to this address. See `fee::dust_threshold`.
+    /// # Arguments
+    /// * `fee_rate` - Dust relay fee in satoshis per 1000 bytes, usually `fee::DUST_RELAY_FEE`.
+    /// # Returns
+    /// * Threshold in satoshis, `None` if no standard script pays to the hash.
+    /// # Example
+    /// ```
+    /// # use bch_addr::{Converter, ParsedAddress, AddressFormat, Network, AddressType};
+    /// use bch_addr::fee::DUST_RELAY_FEE;
+    ///
+    /// let p2sh32 = ParsedAddress::from_hash(AddressFormat::CashAddr, Network::Mainnet, AddressType::P2SH, &[0xab; 32]).unwrap();
+    /// assert_eq!(p2sh32.output_size(), Some(44));
+    /// assert_eq!(p2sh32.dust_threshold(DUST_RELAY_FEE), Some(576));
+    /// ```
+    pub fn dust_threshold(&self, fee_rate: u64) -> Option<u64> {
+        self.output_size().map(|size| fee::dust_threshold_for(size, fee_rate))
+    }
+}
+
+impl Default for ParsedAddress {
+    fn default() -> Self {
+        ParsedAddress {
+            format: AddressFormat::Legacy,
+            network: Network::Mainnet,
+            addr_type: AddressType::P2PKH,
+            hash: Vec::new(),
+        }
+    }
+}
+
+/// Generates hashes of a length the format can encode:
+/// 20 bytes for legacy, any cash_addr hash size otherwise.
+/// Only the assigned address types are generated, as `Other` needs registration to be built.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ParsedAddress {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let format = AddressFormat::arbitrary(u)?;
+        let hash_len = match format {
+            AddressFormat::Legacy => 20,
+            _                     => *u.choose(&[20, 24, 28, 32, 40, 48, 56, 64])?,
+        };
+
+        let mut hash = vec![0; hash_len];
+        u.fill_buffer(&mut hash)?;
+
+        Ok(ParsedAddress {
+            format,
+            network: u.arbitrary()?,
+            addr_type: *u.choose(&[AddressType::P2PKH, AddressType::P2SH])?,
+            hash,
+        })
+    }
+}
+
+/// Parsed address with its encoding details.
+/// Returned by `Converter::inspect`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq), derive(Debug)))]
+pub struct AddressDetails {
+    /// Address format.
+    pub format: AddressFormat,
+    /// Address network.
+    pub network: Network,
+    /// Address type.
+    pub addr_type: AddressType,
+    /// cash_addr prefix, `None` for legacy addresses.
+    pub prefix: Option<String>,
+    /// Base58Check version byte for legacy addresses, payload version byte for cash_addr.
+    pub version_byte: u8,
+    /// hashed publickey.
+    pub hash: Vec<u8>,
+}
+
 /// Address converter.
 #[derive(Debug)]
 pub struct Converter {
-    cash_converter: CashConverter,
+    cash_converter: Arc<CashConverter>,
+    observer: ObserverSlot,
 }
 
+/// Prefix configuration of a `Converter` (prefixes, formats and aliases), returned by `Converter::snapshot`.
+/// Cloning it and restoring it are `Arc` clones, not copies of the configuration.
+#[derive(Clone, Debug)]
+pub struct ConverterSnapshot(Arc<CashConverter>);
+
 impl Default for Converter {
     fn default() -> Self {
         Self::new()
@@ -81,12 +376,15 @@ impl Converter {
     /// ```
     pub fn new() -> Converter {
         Converter {
-            cash_converter: CashConverter::new()
+            cash_converter: Arc::new(CashConverter::new()),
+            observer: ObserverSlot::default(),
         }
     }
 
     /// Add user-defined address prefix.
     /// By calling this function, you can use other address formats.
+    /// Prefixes are lowercased, as addresses are matched and built with lowercase prefixes:
+    /// registering `SimpleLedger` is the same as registering `simpleledger`.
     /// # Arguments
     /// * `prefixes` - Slice of tuple of prefix and `Network`.
     /// * `format_name` - Format name you want to add.
@@ -101,10 +399,217 @@ impl Converter {
     /// );
     /// ```
     pub fn add_prefixes(mut self, prefixes: &[(&str, Network)], format_name: &str) -> Converter {
-        self.cash_converter = self.cash_converter.add_prefixes(prefixes, format_name);
+        self.cash_converter = Arc::new(Arc::unwrap_or_clone(self.cash_converter).add_prefixes(prefixes, format_name));
         self
     }
 
+    /// Add an alias accepted on input in place of a prefix, e.g. `bch` for `bitcoincash`.
+    /// Addresses with an alias parse as if they had the prefix, whose checksum they must carry,
+    /// and are never built with the alias: conversions always write the prefix.
+    /// Registered prefixes take precedence over aliases, and like them aliases are lowercased.
+    /// # Arguments
+    /// * `alias` - Alias without the separator.
+    /// * `prefix` - Prefix the alias stands for, registered now or later.
+    /// # Returns
+    /// * Object for address conversion.
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
+    /// # use bch_addr::{Converter, Network};
+    /// let converter = Converter::new().add_prefix_alias("bch", "bitcoincash");
+    /// assert_eq!(converter.detect_addr_network("bch:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap(), Network::Mainnet);
+    /// assert_eq!(converter.to_cash_addr("bch:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap(), "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+    /// # }
+    /// ```
+    pub fn add_prefix_alias(mut self, alias: &str, prefix: &str) -> Converter {
+        self.cash_converter = Arc::new(Arc::unwrap_or_clone(self.cash_converter).add_alias(alias, prefix));
+        self
+    }
+
+    /// Accept type fields the spec does not assign in addresses of `format`, as `AddressType::Other`,
+    /// e.g. for an experimental protocol using a reserved type. Other formats keep rejecting them.
+    /// # Arguments
+    /// * `format` - `AddressFormat::CashAddr` or a user-defined format, registered now or later.
+    ///   Legacy addresses have no type field, so `AddressFormat::Legacy` is ignored.
+    /// * `type_bits` - Type fields, 2 to 15. Others are ignored: 0 and 1 are P2PKH and P2SH,
+    ///   and larger ones do not fit the version byte.
+    /// # Returns
+    /// * Object for address conversion.
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
+    /// # use bch_addr::{Converter, AddressFormat, Network, AddressType};
+    /// let converter = Converter::new()
+    ///     .add_prefixes(&[("experimental", Network::Mainnet)], "Experimental")
+    ///     .add_type_bits(&AddressFormat::Other("Experimental".to_string()), &[2]);
+    /// let format = AddressFormat::Other("Experimental".to_string());
+    ///
+    /// let addr = converter.encode(&format, Network::Mainnet, AddressType::Other(2), &[0xab; 20]).unwrap();
+    /// let (_, _, addr_type, hash) = converter.parse(&addr).unwrap();
+    /// assert_eq!(addr_type, AddressType::Other(2));
+    /// assert_eq!(hash, [0xab; 20]);
+    ///
+    /// assert!(converter.encode(&AddressFormat::CashAddr, Network::Mainnet, AddressType::Other(2), &[0xab; 20]).is_err());
+    /// # }
+    /// ```
+    pub fn add_type_bits(mut self, format: &AddressFormat, type_bits: &[u8]) -> Converter {
+        self.cash_converter = Arc::new(Arc::unwrap_or_clone(self.cash_converter).add_type_bits(format, type_bits));
+        self
+    }
+
+    /// Register an observer notified of parse failures and conversions, replacing any previous one.
+    /// # Arguments
+    /// * `observer` - Observer.
+    /// # Returns
+    /// * Object for address conversion.
+    /// # Example
+    /// ```
+    /// # use bch_addr::{Converter, AddressFormat, Observer};
+    /// struct Log;
+    ///
+    /// impl Observer for Log {
+    ///     fn on_conversion(&self, input: &str, output: &str, _format: &AddressFormat) {
+    ///         println!("{} -> {}", input, output);
+    ///     }
+    /// }
+    ///
+    /// let converter = Converter::new().with_observer(Log);
+    /// ```
+    pub fn with_observer<O: Observer + 'static>(mut self, observer: O) -> Converter {
+        self.observer = ObserverSlot::new(Box::new(observer));
+        self
+    }
+
+    /// Take the prefix configuration, e.g. to roll back to it or to hand it to other converters.
+    /// The observer is not part of it.
+    /// # Returns
+    /// * Snapshot sharing the configuration with this converter.
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
+    /// # use bch_addr::{Converter, Network};
+    /// let mut converter = Converter::new();
+    /// let defaults = converter.snapshot();
+    ///
+    /// // e.g. reloaded from a config file
+    /// let reloaded = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr").snapshot();
+    /// converter.restore(reloaded);
+    /// assert!(converter.is_mainnet_addr("simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg"));
+    ///
+    /// converter.restore(defaults);
+    /// assert!(!converter.is_mainnet_addr("simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg"));
+    /// # }
+    /// ```
+    pub fn snapshot(&self) -> ConverterSnapshot {
+        ConverterSnapshot(Arc::clone(&self.cash_converter))
+    }
+
+    /// Swap in the prefix configuration of `snapshot` at once, keeping the observer.
+    /// Prefixes added to this converter afterwards do not affect the snapshot or the converters it came from.
+    /// # Arguments
+    /// * `snapshot` - Configuration taken with `snapshot`.
+    pub fn restore(&mut self, snapshot: ConverterSnapshot) {
+        self.cash_converter = snapshot.0;
+    }
+
+    /// Look up what a cash_addr prefix stands for.
+    /// # Arguments
+    /// * `prefix` - Prefix without the separator, in any case.
+    /// # Returns
+    /// * Format and network of the prefix, `None` if it is not registered.
+    /// # Example
+    /// ```
+    /// # use bch_addr::{Converter, AddressFormat, Network};
+    /// let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+    /// assert_eq!(converter.format_for_prefix("bchtest"), Some((AddressFormat::CashAddr, Network::Testnet)));
+    /// assert_eq!(converter.format_for_prefix("SIMPLELEDGER"), Some((AddressFormat::Other("SLPAddr".to_string()), Network::Mainnet)));
+    /// assert_eq!(converter.format_for_prefix("slptest"), None);
+    /// ```
+    pub fn format_for_prefix(&self, prefix: &str) -> Option<(AddressFormat, Network)> {
+        self.cash_converter.prefix_details(prefix).map(|(format, network)| (format.clone(), network))
+    }
+
+    /// Look up the prefix addresses of a format and network are built with; the inverse of `format_for_prefix`.
+    /// # Arguments
+    /// * `format` - `AddressFormat::CashAddr` or a user-defined format.
+    /// * `network` - Network.
+    /// # Returns
+    /// * Prefix without the separator, `None` for legacy and for formats without a prefix on `network`.
+    ///   If several prefixes were registered for the same format and network, the last one.
+    /// # Example
+    /// ```
+    /// # use bch_addr::{Converter, AddressFormat, Network};
+    /// let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+    /// let slp = AddressFormat::Other("SLPAddr".to_string());
+    /// assert_eq!(converter.prefix_for(&AddressFormat::CashAddr, Network::Regtest), Some("bchreg"));
+    /// assert_eq!(converter.prefix_for(&slp, Network::Mainnet), Some("simpleledger"));
+    /// assert_eq!(converter.prefix_for(&slp, Network::Testnet), None);
+    /// assert_eq!(converter.prefix_for(&AddressFormat::Legacy, Network::Mainnet), None);
+    /// ```
+    pub fn prefix_for(&self, format: &AddressFormat, network: Network) -> Option<&str> {
+        self.cash_converter.find_prefix(format, network)
+    }
+
+    /// Look up the version byte leading the Base58Check payload of legacy addresses.
+    /// # Arguments
+    /// * `network` - Network.
+    /// * `addr_type` - Address type.
+    /// # Returns
+    /// * Version byte, `None` for `AddressType::Other`. Regtest uses the testnet bytes.
+    /// # Example
+    /// ```
+    /// # use bch_addr::{Converter, Network, AddressType};
+    /// let converter = Converter::new();
+    /// assert_eq!(converter.legacy_version_byte(Network::Mainnet, AddressType::P2SH), Some(0x05));
+    /// assert_eq!(converter.legacy_version_byte(Network::Regtest, AddressType::P2PKH), Some(0x6f));
+    /// assert_eq!(converter.legacy_version_byte(Network::Mainnet, AddressType::Other(2)), None);
+    /// ```
+    pub fn legacy_version_byte(&self, network: Network, addr_type: AddressType) -> Option<u8> {
+        legacy_converter::version_byte(network, addr_type)
+    }
+
+    /// Look up the network and type of a legacy version byte; the inverse of `legacy_version_byte`.
+    /// # Arguments
+    /// * `version_byte` - Leading byte of a Base58Check payload.
+    /// # Returns
+    /// * Network and type, `None` for unknown bytes. Testnet bytes read as testnet, not regtest.
+    /// # Example
+    /// ```
+    /// # use bch_addr::{Converter, Network, AddressType};
+    /// let converter = Converter::new();
+    /// assert_eq!(converter.legacy_version_byte_info(0xc4), Some((Network::Testnet, AddressType::P2SH)));
+    /// assert_eq!(converter.legacy_version_byte_info(0x80), None);
+    /// ```
+    pub fn legacy_version_byte_info(&self, version_byte: u8) -> Option<(Network, AddressType)> {
+        legacy_converter::version_byte_info(version_byte)
+    }
+
+    /// Guess the format of an address still being typed or pasted, e.g. to hint "looks like a CashAddr".
+    /// Only the characters and the prefix are looked at, not the checksum.
+    /// # Arguments
+    /// * `partial` - Beginning of an address.
+    /// # Returns
+    /// * Formats `partial` can be the beginning of, most likely first, with probabilities summing to 1.
+    ///   A prefix being typed counts once for each registered prefix it matches. Empty if none fits.
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
+    /// # use bch_addr::{Converter, AddressFormat, Network};
+    /// let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+    /// assert_eq!(converter.guess_format("qph5k"), [(AddressFormat::CashAddr, 1.0)]);
+    /// assert_eq!(converter.guess_format("1B9U"), [(AddressFormat::Legacy, 1.0)]);
+    /// assert_eq!(converter.guess_format("simple"), [(AddressFormat::Other("SLPAddr".to_string()), 1.0)]);
+    /// assert!(converter.guess_format("0x12").is_empty());
+    /// # }
+    /// ```
+    pub fn guess_format(&self, partial: &str) -> Vec<(AddressFormat, f32)> {
+        guess::guess_format(self, partial)
+    }
+
     /// Convert to cash_addr format with some options.
     /// # Arguments
     /// * `legacy` - Address to be converted. Usually legacy format but cash_addr format is acceptable.
@@ -114,6 +619,8 @@ impl Converter {
     /// * Converted address.
     /// # Example
     /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
     /// # use bch_addr::{Converter, Network, AddressFormat};
     /// # let converter = Converter::new().add_prefixes(
     /// #     &[("simpleledger", Network::Mainnet), ("slptest", Network::Testnet)],
@@ -132,92 +639,959 @@ impl Converter {
     ///     None
     /// ).unwrap();
     /// assert_eq!(slp_addr, "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg");
+    /// # }
+    /// ```
+    pub fn to_cash_addr_with_options(&self, legacy: &str, format: Option<AddressFormat>, network: Option<Network>) -> Result<String> {
+        let format = format.unwrap_or(AddressFormat::CashAddr);
+        let (converted, _, _) = self.convert_to_cash_addr(legacy, &format, network)?;
+        Ok(converted.into_owned())
+    }
+
+    /// Same as `to_cash_addr_with_options` but borrows `legacy` when it is returned unchanged,
+    /// i.e. when it is already in the target format and network, and lowercase or prefix-less.
+    /// # Arguments
+    /// * `legacy` - Address to be converted. Usually legacy format but cash_addr format is acceptable.
+    /// * `format` - (option) Address format. `AddressFormat::CashAddr` or `AddressFormat::Other("other format")` is required.
+    /// * `network` - (option) Address network.
+    /// # Returns
+    /// * Converted address.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::Converter;
+    /// use std::borrow::Cow;
+    ///
+    /// let converter = Converter::new();
+    /// let addr = "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk";
+    /// assert!(matches!(converter.to_cash_addr_with_options_cow(addr, None, None).unwrap(), Cow::Borrowed(_)));
+    /// assert!(matches!(converter.to_cash_addr_with_options_cow("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", None, None).unwrap(), Cow::Owned(_)));
+    /// # }
+    /// ```
+    pub fn to_cash_addr_with_options_cow<'a>(&self, legacy: &'a str, format: Option<AddressFormat>, network: Option<Network>) -> Result<Cow<'a, str>> {
+        let format = format.unwrap_or(AddressFormat::CashAddr);
+        let (converted, _, _) = self.convert_to_cash_addr(legacy, &format, network)?;
+        Ok(converted)
+    }
+
+    /// `to_cash_addr_with_options` with the letter case of the output chosen.
+    /// # Arguments
+    /// * `legacy` - Address to be converted. Usually legacy format but cash_addr format is acceptable.
+    /// * `format` - (option) Address format. `AddressFormat::CashAddr` or `AddressFormat::Other("other format")` is required.
+    /// * `network` - (option) Address network.
+    /// * `case` - Letter case of the output.
+    /// # Returns
+    /// * Converted address.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::{Converter, Case};
+    /// # let converter = Converter::new();
+    /// let addr = converter.to_cash_addr_with_case("BCHTEST:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7CSM740KF2", None, None, Case::MatchInput).unwrap();
+    /// assert_eq!(addr, "BCHTEST:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7CSM740KF2");
+    ///
+    /// let addr = converter.to_cash_addr_with_case("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", None, None, Case::Upper).unwrap();
+    /// assert_eq!(addr, "BITCOINCASH:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK");
+    /// # }
+    /// ```
+    pub fn to_cash_addr_with_case(&self, legacy: &str, format: Option<AddressFormat>, network: Option<Network>, case: Case) -> Result<String> {
+        let format = format.unwrap_or(AddressFormat::CashAddr);
+        let (converted, source_format, _) = self.convert_to_cash_addr(legacy, &format, network)?;
+        Ok(case.apply(legacy, source_format, converted.into_owned()))
+    }
+
+    /// `to_cash_addr_with_options`, also reporting how the result was derived, e.g. for audit logs.
+    /// # Arguments
+    /// * `legacy` - Address to be converted. Usually legacy format but cash_addr format is acceptable.
+    /// * `format` - (option) Address format. `AddressFormat::CashAddr` or `AddressFormat::Other("other format")` is required.
+    /// * `network` - (option) Address network.
+    /// # Returns
+    /// * Converted address.
+    /// * Conversion details.
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
+    /// # use bch_addr::{Converter, AddressFormat, Network, Normalization};
+    /// # let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+    /// let slp = AddressFormat::Other("SLPAddr".to_string());
+    /// let (slp_addr, info) = converter.to_cash_addr_with_info("QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK", Some(slp), None).unwrap();
+    /// assert_eq!(slp_addr, "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg");
+    /// assert_eq!(info.source_format, AddressFormat::CashAddr);
+    /// assert_eq!(info.source_network, Network::Mainnet);
+    /// assert_eq!(info.normalizations, [Normalization::PrefixAdded, Normalization::Lowercased]);
+    /// assert_eq!(info.target_prefix.unwrap(), "simpleledger");
+    /// # }
+    /// ```
+    pub fn to_cash_addr_with_info(&self, legacy: &str, format: Option<AddressFormat>, network: Option<Network>) -> Result<(String, ConversionInfo)> {
+        let format = format.unwrap_or(AddressFormat::CashAddr);
+        let (converted, source_format, source_network) = self.convert_to_cash_addr(legacy, &format, network)?;
+
+        let target_network = network.unwrap_or(source_network);
+        let info = ConversionInfo {
+            normalizations: info::normalizations(legacy, &converted, source_format, source_network, target_network),
+            source_format: source_format.clone(),
+            source_network,
+            target_prefix: Some(self.cash_converter.prefix(&format, target_network)?.to_string()),
+        };
+        Ok((converted.into_owned(), info))
+    }
+
+    /// Shared by the `to_cash_addr*` methods; also returns the format and network of `legacy`.
+    /// `legacy` is reused when it already has the requested format and network.
+    fn convert_to_cash_addr<'a>(&self, legacy: &'a str, format: &AddressFormat, network: Option<Network>) -> Result<(Cow<'a, str>, &AddressFormat, Network)> {
+        let mut hash = Vec::new();
+        let (current_format, current_network, addr_type) = self.parse_hash_into(legacy, &mut hash)?;
+        let network = network.unwrap_or(current_network);
+        let converted = if current_format == format && network == current_network && *format != AddressFormat::Legacy && !self.cash_converter.has_alias(legacy) {
+            reused_cash_addr(legacy)
+        } else {
+            Cow::Owned(self.cash_converter.build(format, network, addr_type, &hash)?)
+        };
+
+        self.observer.conversion(legacy, &converted, format);
+        Ok((converted, current_format, current_network))
+    }
+
+    /// Convert to cash_addr format.
+    /// # Arguments
+    /// * `legacy` - Address to be converted. Usually legacy format but cash_addr format is acceptable.
+    /// # Returns
+    /// * Converted address.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::Converter;
+    /// # let converter = Converter::new();
+    /// let cash_addr = converter.to_cash_addr("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap();
+    /// assert_eq!(cash_addr, "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+    ///
+    /// // as scanned from a QR code
+    /// let cash_addr = converter.to_cash_addr("BITCOINCASH:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK").unwrap();
+    /// assert_eq!(cash_addr, "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+    /// # }
+    /// ```
+    pub fn to_cash_addr(&self, legacy: &str) -> Result<String> {
+        self.to_cash_addr_with_options(legacy, None, None)
+    }
+
+    /// Same as `to_cash_addr` but borrows `legacy` when it is returned unchanged,
+    /// so pipelines passing through canonical addresses don't allocate.
+    /// # Arguments
+    /// * `legacy` - Address to be converted. Usually legacy format but cash_addr format is acceptable.
+    /// # Returns
+    /// * Converted address.
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
+    /// # use bch_addr::Converter;
+    /// use std::borrow::Cow;
+    ///
+    /// let converter = Converter::new();
+    /// let addr = "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk";
+    /// assert!(matches!(converter.to_cash_addr_cow(addr).unwrap(), Cow::Borrowed(borrowed) if borrowed == addr));
+    /// # }
+    /// ```
+    pub fn to_cash_addr_cow<'a>(&self, legacy: &'a str) -> Result<Cow<'a, str>> {
+        self.to_cash_addr_with_options_cow(legacy, None, None)
+    }
+
+    /// Convert to legacy format.
+    /// # Arguments
+    /// * `cash` - Address to be converted. Usually cash_addr format but legacy format is acceptable.
+    /// # Returns
+    /// * Converted address.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::Converter;
+    /// # let converter = Converter::new();
+    /// let cash_addr = converter.to_legacy_addr("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap();
+    /// assert_eq!(cash_addr, "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR");
+    /// # }
+    /// ```
+    pub fn to_legacy_addr(&self, cash: &str) -> Result<String> {
+        let (converted, _, _) = self.convert_to_legacy_addr(cash)?;
+        Ok(converted.into_owned())
+    }
+
+    /// Same as `to_legacy_addr` but borrows `cash` when it is already a legacy address.
+    /// # Arguments
+    /// * `cash` - Address to be converted. Usually cash_addr format but legacy format is acceptable.
+    /// # Returns
+    /// * Converted address.
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "legacy")]
+    /// # {
+    /// # use bch_addr::Converter;
+    /// use std::borrow::Cow;
+    ///
+    /// let converter = Converter::new();
+    /// assert!(matches!(converter.to_legacy_addr_cow("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap(), Cow::Borrowed(_)));
+    /// # }
+    /// ```
+    pub fn to_legacy_addr_cow<'a>(&self, cash: &'a str) -> Result<Cow<'a, str>> {
+        let (converted, _, _) = self.convert_to_legacy_addr(cash)?;
+        Ok(converted)
+    }
+
+    /// `to_legacy_addr`, also reporting how the result was derived, e.g. for audit logs.
+    /// Legacy addresses have no prefix, and regtest ones are the same as testnet ones.
+    /// # Arguments
+    /// * `cash` - Address to be converted. Usually cash_addr format but legacy format is acceptable.
+    /// # Returns
+    /// * Converted address.
+    /// * Conversion details.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::{Converter, AddressFormat, Network};
+    /// # let converter = Converter::new();
+    /// let (legacy_addr, info) = converter.to_legacy_addr_with_info("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap();
+    /// assert_eq!(legacy_addr, "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR");
+    /// assert_eq!(info.source_format, AddressFormat::CashAddr);
+    /// assert!(info.normalizations.is_empty());
+    /// assert_eq!(info.target_prefix, None);
+    /// # }
+    /// ```
+    pub fn to_legacy_addr_with_info(&self, cash: &str) -> Result<(String, ConversionInfo)> {
+        let (converted, source_format, source_network) = self.convert_to_legacy_addr(cash)?;
+        let info = ConversionInfo {
+            normalizations: info::normalizations(cash, &converted, source_format, source_network, source_network),
+            source_format: source_format.clone(),
+            source_network,
+            target_prefix: None,
+        };
+        Ok((converted.into_owned(), info))
+    }
+
+    /// Shared by the `to_legacy_addr*` methods; also returns the format and network of `cash`.
+    fn convert_to_legacy_addr<'a>(&self, cash: &'a str) -> Result<(Cow<'a, str>, &AddressFormat, Network)> {
+        let mut hash = Vec::new();
+        let (format, network, addr_type) = self.parse_hash_into(cash, &mut hash)?;
+        let converted = if *format == AddressFormat::Legacy {
+            // actually `cash` is legacy_addr
+            Cow::Borrowed(cash)
+        } else {
+            Cow::Owned(legacy_converter::build(network, addr_type, &hash)?)
+        };
+
+        self.observer.conversion(cash, &converted, &AddressFormat::Legacy);
+        Ok((converted, format, network))
+    }
+
+    /// Derive words for two people to confirm aloud that they hold the same address, e.g. "cedar-crown-elbow".
+    /// The words depend on the network, type and hash only, so every format of an address gets the same ones.
+    /// # Arguments
+    /// * `addr` - Address in any format.
+    /// * `n` - Number of words, at most 32; larger values give 32 words.
+    ///   Each word carries 8 bits, so 3 or 4 words are usually enough to tell addresses apart.
+    /// # Returns
+    /// * Words from a fixed list of 256.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::Converter;
+    /// let converter = Converter::new();
+    /// let words = converter.checkwords("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", 3).unwrap();
+    /// assert_eq!(words, converter.checkwords("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", 3).unwrap());
+    /// assert_ne!(words, converter.checkwords("bchtest:qph5kuz78czq00e3t85ugpgd7xmer5kr7csm740kf2", 3).unwrap());
+    /// println!("{}", words.join("-"));
+    /// # }
+    /// ```
+    pub fn checkwords(&self, addr: &str, n: usize) -> Result<Vec<&'static str>> {
+        let mut hash = Vec::new();
+        let (_, network, addr_type) = self.parse_hash_into(addr, &mut hash)?;
+
+        let digest = fingerprint::identity_digest(b"bch_addr checkwords", network, addr_type, &hash);
+        Ok(digest.iter().take(n).map(|&byte| wordlist::WORDS[usize::from(byte)]).collect())
+    }
+
+    /// Derive a seed for identicons or color gradients representing an address.
+    /// The seed depends on the network, type and hash only, so the picture stays the same
+    /// whether the legacy or the cash_addr form is pasted.
+    /// # Arguments
+    /// * `addr` - Address in any format.
+    /// # Returns
+    /// * 32 bytes, stable across versions of this crate.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::Converter;
+    /// let converter = Converter::new();
+    /// let seed = converter.visual_seed("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap();
+    /// assert_eq!(seed, converter.visual_seed("qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap());
+    /// let (r, g, b) = (seed[0], seed[1], seed[2]);
+    /// # }
+    /// ```
+    pub fn visual_seed(&self, addr: &str) -> Result<[u8; 32]> {
+        let mut hash = Vec::new();
+        let (_, network, addr_type) = self.parse_hash_into(addr, &mut hash)?;
+
+        Ok(fingerprint::identity_digest(b"bch_addr visual seed", network, addr_type, &hash))
+    }
+
+    /// Derive a short emoji sequence for comparing addresses at a glance, e.g. in chat-based payment flows.
+    /// Like `checkwords`, it depends on the network, type and hash only.
+    /// # Arguments
+    /// * `addr` - Address in any format.
+    /// * `len` - Number of emoji. Each carries 6 bits; 4 to 6 are usually enough.
+    /// # Returns
+    /// * Emoji from `EMOJI_ALPHABET`.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::Converter;
+    /// let converter = Converter::new();
+    /// let emoji = converter.emoji_fingerprint("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", 5).unwrap();
+    /// assert_eq!(emoji, converter.emoji_fingerprint("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", 5).unwrap());
+    /// println!("{}", emoji.concat());
+    /// # }
+    /// ```
+    pub fn emoji_fingerprint(&self, addr: &str, len: usize) -> Result<Vec<&'static str>> {
+        self.emoji_fingerprint_with(addr, len, &EMOJI_ALPHABET)
+    }
+
+    /// Same as `emoji_fingerprint` with another alphabet, e.g. emoji that fit an application's theme.
+    /// The first symbols stay the same when `len` grows, but change with the alphabet.
+    /// # Arguments
+    /// * `addr` - Address in any format.
+    /// * `len` - Number of symbols.
+    /// * `alphabet` - Symbols to pick from, typically emoji. Must not be empty.
+    /// # Returns
+    /// * Symbols from `alphabet`.
+    pub fn emoji_fingerprint_with<'s>(&self, addr: &str, len: usize, alphabet: &[&'s str]) -> Result<Vec<&'s str>> {
+        let mut hash = Vec::new();
+        let (_, network, addr_type) = self.parse_hash_into(addr, &mut hash)?;
+
+        Ok(fingerprint::emoji(network, addr_type, &hash, len, alphabet))
+    }
+
+    /// Derive a 64-bit id of an address, e.g. as a compact index or sharding key.
+    /// Every format of an address gets the same id.
+    ///
+    /// The id is the first 8 bytes (little endian) of a SHA-256 digest of the network, type and hash,
+    /// and will not change in future versions. Ids of distinct addresses collide with probability 2^-64 per pair.
+    /// # Arguments
+    /// * `addr` - Address in any format.
+    /// # Returns
+    /// * Id, the same as `short_id_with_key` with a zero key.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::Converter;
+    /// let converter = Converter::new();
+    /// let id = converter.short_id("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap();
+    /// assert_eq!(id, converter.short_id("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap());
+    /// let shard = id % 16;
+    /// # }
+    /// ```
+    pub fn short_id(&self, addr: &str) -> Result<u64> {
+        self.short_id_with_key(addr, &[0; 16])
+    }
+
+    /// Same as `short_id` with a secret key, so that outsiders cannot pick addresses that land in the same shard.
+    /// # Arguments
+    /// * `addr` - Address in any format.
+    /// * `key` - Key, kept for as long as the ids are.
+    /// # Returns
+    /// * Id.
+    pub fn short_id_with_key(&self, addr: &str, key: &[u8; 16]) -> Result<u64> {
+        let mut hash = Vec::new();
+        let (_, network, addr_type) = self.parse_hash_into(addr, &mut hash)?;
+
+        let tag = [&b"bch_addr short id"[..], key].concat();
+        let digest = fingerprint::identity_digest(&tag, network, addr_type, &hash);
+        let mut id = [0; 8];
+        id.copy_from_slice(&digest[..8]);
+        Ok(u64::from_le_bytes(id))
+    }
+
+    /// Replace an address with a keyed token, e.g. so that logs and analytics can correlate
+    /// the activity of an address without storing it. Every format of an address gets the same token.
+    /// # Arguments
+    /// * `addr` - Address in any format.
+    /// * `key` - Secret key; without it, tokens cannot be linked back to addresses.
+    /// # Returns
+    /// * Token: HMAC-SHA256 of the network, type and hash, in lowercase hex.
+    ///   The message is the network (0 for mainnet, 1 for testnet, 2 for regtest), the type field in place
+    ///   in the cash_addr version byte (0 for P2PKH, 8 for P2SH, `n << 3` for `AddressType::Other(n)`)
+    ///   and the hash, so the tokens can be reproduced elsewhere.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::Converter;
+    /// let converter = Converter::new();
+    /// let token = converter.pseudonymize("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", b"secret").unwrap();
+    /// assert_eq!(token, converter.pseudonymize("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", b"secret").unwrap());
+    /// assert_eq!(token.len(), 64);
+    /// # }
+    /// ```
+    pub fn pseudonymize(&self, addr: &str, key: &[u8]) -> Result<String> {
+        let mut hash = Vec::new();
+        let (_, network, addr_type) = self.parse_hash_into(addr, &mut hash)?;
+
+        Ok(hash::to_hex(&fingerprint::identity_hmac(key, network, addr_type, &hash)))
+    }
+
+    /// Convert to every format at once: legacy, cash_addr and each user-defined format.
+    /// # Arguments
+    /// * `addr` - Address in any format.
+    /// # Returns
+    /// * Address in each format, keeping the network of `addr`.
+    ///   Formats `encode` fails for are silently left out: those without a prefix for that network or
+    ///   that do not accept the address type, and legacy for hashes other than 20 bytes, as it only carries HASH160s.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::{Converter, AddressFormat, Network};
+    /// let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+    /// let formats = converter.to_all_formats("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap();
+    /// assert_eq!(formats.len(), 3);
+    /// assert_eq!(formats[&AddressFormat::CashAddr], "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+    /// assert_eq!(formats[&AddressFormat::Other("SLPAddr".to_string())], "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg");
+    /// # }
+    /// ```
+    pub fn to_all_formats(&self, addr: &str) -> Result<HashMap<AddressFormat, String>> {
+        let mut hash = Vec::new();
+        let (_, network, addr_type) = self.parse_hash_into(addr, &mut hash)?;
+
+        let formats = iter::once(&AddressFormat::Legacy).chain(self.cash_converter.formats());
+        Ok(formats
+            .filter_map(|format| {
+                let converted = self.encode(format, network, addr_type, &hash).ok()?;
+                Some((format.clone(), converted))
+            })
+            .collect())
+    }
+
+    /// Encode the hash of an address for every network, keeping its format and type.
+    /// # Arguments
+    /// * `addr` - Address in any format.
+    /// # Returns
+    /// * Address on each network. Networks without a prefix in the address's format are left out;
+    ///   legacy regtest addresses are the same as testnet ones.
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
+    /// # use bch_addr::{Converter, Network};
+    /// # let converter = Converter::new();
+    /// let equivalents = converter.equivalents_across_networks("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap();
+    /// assert_eq!(equivalents[&Network::Testnet], "bchtest:qph5kuz78czq00e3t85ugpgd7xmer5kr7csm740kf2");
+    /// assert_eq!(equivalents[&Network::Regtest], "bchreg:qph5kuz78czq00e3t85ugpgd7xmer5kr7c28g5v92v");
+    /// # }
+    /// ```
+    pub fn equivalents_across_networks(&self, addr: &str) -> Result<HashMap<Network, String>> {
+        let mut hash = Vec::new();
+        let (format, _, addr_type) = self.parse_hash_into(addr, &mut hash)?;
+
+        Ok([Network::Mainnet, Network::Testnet, Network::Regtest].iter()
+            .filter_map(|&network| {
+                let converted = self.encode(format, network, addr_type, &hash).ok()?;
+                Some((network, converted))
+            })
+            .collect())
+    }
+
+    /// Re-encode an address on another network, keeping its format and type,
+    /// e.g. to turn a list of production addresses into testnet ones.
+    /// # Arguments
+    /// * `addr` - Address in any format.
+    /// * `network` - Target network.
+    /// # Returns
+    /// * Address on `network`, lowercase and prefixed for cash_addr formats even if already on `network`.
+    ///   Legacy regtest addresses are the same as testnet ones;
+    ///   formats without a prefix on `network` fail with `Error::UnknownCashFormat`.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::{Converter, Network};
+    /// # let converter = Converter::new();
+    /// let testnet = converter.to_network("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", Network::Testnet).unwrap();
+    /// assert_eq!(testnet, "bchtest:qph5kuz78czq00e3t85ugpgd7xmer5kr7csm740kf2");
+    /// let testnet = converter.to_network("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", Network::Testnet).unwrap();
+    /// assert_eq!(testnet, "mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi");
+    /// # }
+    /// ```
+    pub fn to_network(&self, addr: &str, network: Network) -> Result<String> {
+        let mut hash = Vec::new();
+        let (format, _, addr_type) = self.parse_hash_into(addr, &mut hash)?;
+        let converted = self.encode(format, network, addr_type, &hash)?;
+
+        self.observer.conversion(addr, &converted, format);
+        Ok(converted)
+    }
+
+    /// Compare two addresses by what they encode rather than by spelling,
+    /// e.g. to tell whether a deposit went to the right key on the wrong network.
+    /// # Arguments
+    /// * `a` - Address in any format.
+    /// * `b` - Address in any format.
+    /// # Returns
+    /// * Properties in which the addresses differ.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::{Converter, AddressDiff};
+    /// # let converter = Converter::new();
+    /// let diff = converter.diff("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap();
+    /// assert!(diff.is_format_only());
+    ///
+    /// let diff = converter.diff("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "bchtest:qph5kuz78czq00e3t85ugpgd7xmer5kr7csm740kf2").unwrap();
+    /// assert!(diff.network && diff.is_same_hash());
+    /// # }
+    /// ```
+    pub fn diff(&self, a: &str, b: &str) -> Result<AddressDiff> {
+        diff::diff(self, a, b)
+    }
+
+    /// Whether two addresses pay to the same destination, in whatever format, with the network, type and hash
+    /// compared in constant time, e.g. to check a user-supplied address against a secret allowlist.
+    /// Parsing is not constant-time. Enabled by the `subtle` feature.
+    /// # Arguments
+    /// * `a` - Address in any format.
+    /// * `b` - Address in any format.
+    /// # Returns
+    /// * `true` if `diff(a, b)?.is_same_destination()` would be.
+    /// # Example
+    /// ```
+    /// # use bch_addr::Converter;
+    /// # let converter = Converter::new();
+    /// assert!(converter.addr_eq_ct("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap());
+    /// assert!(!converter.addr_eq_ct("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "185K5yAfcrARrHjNVt4iAUHtkYqcogF4km").unwrap());
+    /// ```
+    #[cfg(feature = "subtle")]
+    pub fn addr_eq_ct(&self, a: &str, b: &str) -> Result<bool> {
+        diff::ct_same_destination(self, a, b)
+    }
+
+    /// Find the valid addresses in free text, e.g. logs or chat messages.
+    /// Candidates are runs of ASCII alphanumerics and `:`, so URIs and `label:address` pairs are found too.
+    /// # Arguments
+    /// * `text` - Text to scan.
+    /// # Returns
+    /// * Addresses found, in order, with their byte ranges.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::{Converter, AddressFormat};
+    /// # let converter = Converter::new();
+    /// let found = converter.scan_text("pay bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk?amount=1 or 3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9.");
+    /// assert_eq!(found.len(), 2);
+    /// assert_eq!(found[0].range, 4..58);
+    /// assert_eq!(found[1].text, "3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9");
+    /// assert_eq!(found[1].parsed.format, AddressFormat::Legacy);
+    /// # }
+    /// ```
+    pub fn scan_text<'t>(&self, text: &'t str) -> Vec<AddressMatch<'t>> {
+        scan::scan_text(self, text)
+    }
+
+    /// Find the valid addresses in a stream of any length, reading it in chunks.
+    /// Finds the same addresses as `scan_text`; invalid UTF-8 only separates candidates.
+    /// # Arguments
+    /// * `reader` - Stream to scan. Wrapping it in a `BufReader` is unnecessary.
+    /// # Returns
+    /// * Iterator over the addresses found, with their byte offsets, or the read error.
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "legacy")]
+    /// # {
+    /// # use bch_addr::Converter;
+    /// # let converter = Converter::new();
+    /// let log = "GET /balance/1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR 200\nGET /balance/nope 404\n";
+    /// let found: Vec<_> = converter.scan_reader(log.as_bytes()).collect::<Result<_, _>>().unwrap();
+    /// assert_eq!(found.len(), 1);
+    /// assert_eq!(found[0].offset, 13);
+    /// assert_eq!(found[0].address, "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR");
+    /// # }
+    /// ```
+    pub fn scan_reader<R: std::io::Read>(&self, reader: R) -> ScanReader<'_, R> {
+        ScanReader::new(self, reader)
+    }
+
+    /// Encode hash to the given format without heap allocation.
+    /// # Arguments
+    /// * `format` - Address format.
+    /// * `network` - Address network.
+    /// * `addr_type` - Address type.
+    /// * `hash` - hashed publickey.
+    /// * `out` - Output buffer. `MAX_ADDR_LEN` bytes are enough for the built-in formats.
+    /// # Returns
+    /// * Number of bytes written. `out[..len]` holds the ASCII address.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::{Converter, AddressFormat, Network, AddressType, MAX_ADDR_LEN};
+    /// # let converter = Converter::new();
+    /// # let (_, _, _, hash) = converter.parse("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap();
+    /// let mut buf = [0; MAX_ADDR_LEN];
+    /// let len = converter.encode_into(&AddressFormat::CashAddr, Network::Mainnet, AddressType::P2PKH, &hash, &mut buf).unwrap();
+    /// assert_eq!(&buf[..len], &b"bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk"[..]);
+    ///
+    /// let len = converter.encode_into(&AddressFormat::Legacy, Network::Mainnet, AddressType::P2PKH, &hash, &mut buf).unwrap();
+    /// assert_eq!(&buf[..len], &b"1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR"[..]);
+    /// # }
+    /// ```
+    pub fn encode_into(&self, format: &AddressFormat, network: Network, addr_type: AddressType, hash: &[u8], out: &mut [u8]) -> Result<usize> {
+        match format {
+            AddressFormat::Legacy => legacy_converter::build_into(network, addr_type, hash, out),
+            _                     => self.cash_converter.build_into(format, network, addr_type, hash, out),
+        }
+    }
+
+    /// Encode hash to an address.
+    /// # Arguments
+    /// * `format` - Address format.
+    /// * `network` - Address network.
+    /// * `addr_type` - Address type.
+    /// * `hash` - hashed publickey or redeem script.
+    /// # Returns
+    /// * Address.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::{Converter, AddressFormat, Network, AddressType};
+    /// # let converter = Converter::new();
+    /// let (_, _, addr_type, hash) = converter.parse("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap();
+    /// let cash_addr = converter.encode(&AddressFormat::CashAddr, Network::Mainnet, addr_type, &hash).unwrap();
+    /// assert_eq!(cash_addr, "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+    /// # }
+    /// ```
+    pub fn encode(&self, format: &AddressFormat, network: Network, addr_type: AddressType, hash: &[u8]) -> Result<String> {
+        match format {
+            AddressFormat::Legacy => legacy_converter::build(network, addr_type, hash),
+            _                     => self.cash_converter.build(format, network, addr_type, hash),
+        }
+    }
+
+    /// Build the P2PKH address of a public key.
+    /// # Arguments
+    /// * `pubkey` - Compressed (33 bytes) or uncompressed (65 bytes) public key.
+    /// * `format` - Address format.
+    /// * `network` - Address network.
+    /// # Returns
+    /// * Address.
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "legacy")]
+    /// # {
+    /// # use bch_addr::{Converter, AddressFormat, Network};
+    /// # let converter = Converter::new();
+    /// let pubkey = [
+    ///     0x02, 0x50, 0x86, 0x3a, 0xd6, 0x4a, 0x87, 0xae, 0x8a, 0x2f, 0xe8, 0x3c, 0x1a, 0xf1, 0xa8, 0x40,
+    ///     0x3c, 0xb5, 0x3f, 0x53, 0xe4, 0x86, 0xd8, 0x51, 0x1d, 0xad, 0x8a, 0x04, 0x88, 0x7e, 0x5b, 0x23,
+    ///     0x52,
+    /// ];
+    /// let legacy = converter.from_pubkey(&pubkey, &AddressFormat::Legacy, Network::Mainnet).unwrap();
+    /// assert_eq!(legacy, "1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAs");
+    /// # }
+    /// ```
+    #[cfg(feature = "hash160")]
+    pub fn from_pubkey(&self, pubkey: &[u8], format: &AddressFormat, network: Network) -> Result<String> {
+        self.from_pubkey_with(&hash::DefaultHasher160, pubkey, format, network)
+    }
+
+    /// `from_pubkey` hashing with `hasher` instead of the `sha2` and `ripemd160` crates.
+    /// # Arguments
+    /// * `hasher` - hash160 implementation.
+    /// * `pubkey` - Compressed (33 bytes) or uncompressed (65 bytes) public key.
+    /// * `format` - Address format.
+    /// * `network` - Address network.
+    /// # Returns
+    /// * Address.
+    pub fn from_pubkey_with<H>(&self, hasher: &H, pubkey: &[u8], format: &AddressFormat, network: Network) -> Result<String>
+        where H: Hasher160 + ?Sized
+    {
+        match (pubkey.len(), pubkey.first()) {
+            (33, Some(0x02)) | (33, Some(0x03)) | (65, Some(0x04)) => {},
+            _ => return Err(Error::InvalidPublicKey(pubkey.len())),
+        }
+
+        self.encode(format, network, AddressType::P2PKH, &hasher.hash160(pubkey))
+    }
+
+    /// Build the P2SH address of a redeem script.
+    /// # Arguments
+    /// * `script` - Serialized redeem script.
+    /// * `format` - Address format.
+    /// * `network` - Address network.
+    /// # Returns
+    /// * Address.
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
+    /// # use bch_addr::{Converter, AddressFormat, Network};
+    /// # let converter = Converter::new();
+    /// // OP_TRUE
+    /// let cash_addr = converter.from_redeem_script(&[0x51], &AddressFormat::CashAddr, Network::Mainnet).unwrap();
+    /// assert!(converter.is_p2sh_addr(&cash_addr));
+    /// # }
+    /// ```
+    #[cfg(feature = "hash160")]
+    pub fn from_redeem_script(&self, script: &[u8], format: &AddressFormat, network: Network) -> Result<String> {
+        self.from_redeem_script_with(&hash::DefaultHasher160, script, format, network)
+    }
+
+    /// `from_redeem_script` hashing with `hasher` instead of the `sha2` and `ripemd160` crates.
+    /// # Arguments
+    /// * `hasher` - hash160 implementation.
+    /// * `script` - Serialized redeem script.
+    /// * `format` - Address format.
+    /// * `network` - Address network.
+    /// # Returns
+    /// * Address.
+    pub fn from_redeem_script_with<H>(&self, hasher: &H, script: &[u8], format: &AddressFormat, network: Network) -> Result<String>
+        where H: Hasher160 + ?Sized
+    {
+        self.encode(format, network, AddressType::P2SH, &hasher.hash160(script))
+    }
+
+    /// Build a payment URI (`bitcoincash:...?amount=...`) for an address in any format.
+    /// # Arguments
+    /// * `addr` - Address in any format.
+    /// * `amount` - (option) Amount in BCH, e.g. `"0.001"`.
+    /// # Returns
+    /// * Payment URI.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::Converter;
+    /// # let converter = Converter::new();
+    /// let uri = converter.payment_uri("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", Some("0.001")).unwrap();
+    /// assert_eq!(uri, "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk?amount=0.001");
+    /// # }
+    /// ```
+    pub fn payment_uri(&self, addr: &str, amount: Option<&str>) -> Result<String> {
+        uri::build(self.to_cash_addr(addr)?, amount)
+    }
+
+    /// Same as `payment_uri` but with the address uppercased,
+    /// which QR codes can encode in the denser alphanumeric mode.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::Converter;
+    /// # let converter = Converter::new();
+    /// let uri = converter.qr_payment_uri("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", None).unwrap();
+    /// assert_eq!(uri, "BITCOINCASH:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK");
+    /// # }
+    /// ```
+    pub fn qr_payment_uri(&self, addr: &str, amount: Option<&str>) -> Result<String> {
+        uri::build(self.to_cash_addr(addr)?.to_ascii_uppercase(), amount)
+    }
+
+    /// Parse address.
+    /// # Arguments
+    /// * `addr` - Address to be parsed.
+    /// # Returns
+    /// * Address format.
+    /// * Address network.
+    /// * Address type.
+    /// * hashed pubilckey.
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
+    /// # use bch_addr::{Converter, AddressFormat, Network, AddressType};
+    /// # let converter = Converter::new();
+    /// let (format, network, addr_type, hash) = converter.parse("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap();
+    /// assert_eq!(format, AddressFormat::CashAddr);
+    /// assert_eq!(network, Network::Mainnet);
+    /// assert_eq!(addr_type, AddressType::P2PKH);
+    /// assert_eq!(hash.len(), 20);
+    /// # }
     /// ```
-    pub fn to_cash_addr_with_options(&self, legacy: &str, format: Option<AddressFormat>, network: Option<Network>) -> Result<String> {
-        let format = format.unwrap_or(AddressFormat::CashAddr);
-
-        if let Ok((_, current_network, addr_type, hash)) = legacy_converter::parse(legacy) {
-            let network = network.unwrap_or(current_network);
-            return Ok(self.cash_converter.build(&format, network, addr_type, &hash)?);
-        }
-
-        // actually `legacy` may be cash_addr
-        if let Ok(current_format) = self.detect_addr_format(legacy) {
-            if format == current_format {
-                return Ok(legacy.to_string());
-            } else {
-                let (_, current_network, addr_type, hash) = self.cash_converter.parse(legacy)?;
-                let network = network.unwrap_or(current_network);
-                return Ok(self.cash_converter.build(&format, network, addr_type, &hash)?);
-            }
-        }
-
-        Err(Error::InvalidAddress(legacy.to_string()))
+    pub fn parse(&self, addr: &str) -> Result<(AddressFormat, Network, AddressType, Vec<u8>)> {
+        let mut hash = Vec::new();
+        let (format, network, addr_type) = self.parse_hash_into(addr, &mut hash)?;
+        Ok((format.clone(), network, addr_type, hash))
     }
 
-    /// Convert to cash_addr format.
+    /// Parse a legacy address already Base58-decoded, e.g. a payload extracted from binary data by an indexer.
+    /// No string is decoded; only the version byte and the checksum are checked.
     /// # Arguments
-    /// * `legacy` - Address to be converted. Usually legacy format but cash_addr format is acceptable.
+    /// * `data` - Version byte and 20-byte hash, optionally followed by the 4-byte Base58Check checksum.
     /// # Returns
-    /// * Converted address.
+    /// * Address network. Testnet version bytes read as testnet, not regtest.
+    /// * Address type.
+    /// * Hash, borrowed from `data`.
     /// # Example
     /// ```
-    /// # use bch_addr::Converter;
+    /// # #[cfg(feature = "legacy")]
+    /// # {
+    /// # use bch_addr::{Converter, Network, AddressType};
     /// # let converter = Converter::new();
-    /// let cash_addr = converter.to_cash_addr("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap();
-    /// assert_eq!(cash_addr, "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+    /// let (_, _, _, hash) = converter.parse("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap();
+    /// let mut raw = vec![0x05];
+    /// raw.extend_from_slice(&hash);
+    /// assert_eq!(converter.parse_raw_legacy(&raw).unwrap(), (Network::Mainnet, AddressType::P2SH, &hash[..]));
+    ///
+    /// raw.push(0);
+    /// assert!(converter.parse_raw_legacy(&raw).is_err());
+    /// # }
     /// ```
-    pub fn to_cash_addr(&self, legacy: &str) -> Result<String> {
-        self.to_cash_addr_with_options(legacy, None, None)
+    pub fn parse_raw_legacy<'a>(&self, data: &'a [u8]) -> Result<(Network, AddressType, &'a [u8])> {
+        legacy_converter::parse_raw(data)
     }
 
-    /// Convert to legacy format.
+    /// Check only the checksum of an address: Base58Check for legacy addresses,
+    /// the cash_addr checksum against the address's own prefix (registered or not) otherwise.
+    /// Version bytes and hash sizes are not checked, so integrity checks pass for addresses `parse` rejects.
+    /// Prefix-less cash_addr addresses are checked against the registered prefixes.
     /// # Arguments
-    /// * `cash` - Address to be converted. Usually cash_addr format but legacy format is acceptable.
+    /// * `addr` - Address in any format.
     /// # Returns
-    /// * Converted address.
+    /// * `Ok(())` if the checksum matches.
     /// # Example
     /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
     /// # use bch_addr::Converter;
     /// # let converter = Converter::new();
-    /// let cash_addr = converter.to_legacy_addr("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap();
-    /// assert_eq!(cash_addr, "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR");
+    /// assert!(converter.verify_checksum("simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg").is_ok());
+    /// assert!(converter.verify_checksum("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").is_ok());
+    /// assert!(converter.verify_checksum("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzS").is_err());
+    /// # }
     /// ```
-    pub fn to_legacy_addr(&self, cash: &str) -> Result<String> {
-        if let Ok((_, network, addr_type, hash)) = self.cash_converter.parse(cash) {
-            return Ok(legacy_converter::build(network, addr_type, &hash)?);
+    pub fn verify_checksum(&self, addr: &str) -> Result<()> {
+        if is_prefixed(addr) {
+            return self.cash_converter.verify_checksum(addr);
         }
 
-        if self.is_legacy_addr(cash) {
-            // actually `cash` is legacy_addr
-            return Ok(cash.to_string());
-        }
-
-        Err(Error::InvalidAddress(cash.to_string()))
+        legacy_converter::verify_checksum(addr)
+            .or_else(|_| self.cash_converter.verify_checksum(addr))
+            .map_err(|_| Error::InvalidAddress(addr.to_string()))
     }
 
-    /// Parse address.
+    /// Parse address, also reporting how it is encoded.
     /// # Arguments
     /// * `addr` - Address to be parsed.
     /// # Returns
-    /// * Address format.
-    /// * Address network.
-    /// * Address type.
-    /// * hashed pubilckey.
+    /// * Address details.
     /// # Example
     /// ```
-    /// # use bch_addr::{Converter, AddressFormat, Network, AddressType};
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::Converter;
     /// # let converter = Converter::new();
-    /// let (format, network, addr_type, hash) = converter.parse("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap();
-    /// assert_eq!(format, AddressFormat::CashAddr);
-    /// assert_eq!(network, Network::Mainnet);
-    /// assert_eq!(addr_type, AddressType::P2PKH);
-    /// assert_eq!(hash.len(), 20);
+    /// let details = converter.inspect("qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap();
+    /// assert_eq!(details.prefix.as_ref().unwrap(), "bitcoincash");
+    /// assert_eq!(details.version_byte, 0);
+    ///
+    /// let details = converter.inspect("3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9").unwrap();
+    /// assert_eq!(details.prefix, None);
+    /// assert_eq!(details.version_byte, 0x05);
+    /// # }
     /// ```
-    pub fn parse(&self, addr: &str) -> Result<(AddressFormat, Network, AddressType, Vec<u8>)> {
-        legacy_converter::parse(addr)
-        .or_else(|_| self.cash_converter.parse(addr))
-        .or_else(|_| Err(Error::InvalidAddress(addr.to_string())))
+    pub fn inspect(&self, addr: &str) -> Result<AddressDetails> {
+        let (format, network, addr_type, hash) = self.parse(addr)?;
+        let (prefix, version_byte) = match format {
+            AddressFormat::Legacy => (None, legacy_converter::version_byte(network, addr_type).ok_or(Error::UnsupportedAddressType(addr_type))?),
+            _ => (
+                Some(self.cash_converter.prefix(&format, network)?.to_string()),
+                cash_addr::version_byte(addr_type, &hash)?,
+            ),
+        };
+
+        Ok(AddressDetails { format, network, addr_type, prefix, version_byte, hash })
+    }
+
+    /// Parse address into a caller-provided `ParsedAddress`, reusing its hash buffer.
+    /// # Arguments
+    /// * `addr` - Address to be parsed.
+    /// * `parsed` - Output. Its contents are unspecified if an error is returned.
+    /// # Example
+    /// ```
+    /// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    /// # {
+    /// # use bch_addr::{Converter, ParsedAddress, AddressFormat, Network, AddressType};
+    /// # let converter = Converter::new();
+    /// let mut parsed = ParsedAddress::default();
+    /// for addr in &["bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", "3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9"] {
+    ///     converter.parse_into(addr, &mut parsed).unwrap();
+    ///     assert_eq!(parsed.network, Network::Mainnet);
+    ///     assert_eq!(parsed.hash.len(), 20);
+    /// }
+    /// assert_eq!(parsed.format, AddressFormat::Legacy);
+    /// assert_eq!(parsed.addr_type, AddressType::P2SH);
+    /// # }
+    /// ```
+    pub fn parse_into(&self, addr: &str, parsed: &mut ParsedAddress) -> Result<()> {
+        let (format, network, addr_type) = self.parse_hash_into(addr, &mut parsed.hash)?;
+        if parsed.format != *format {
+            parsed.format = format.clone();
+        }
+        parsed.network = network;
+        parsed.addr_type = addr_type;
+
+        Ok(())
+    }
+
+    /// `parse` writing the hash into `hash`, with the format borrowed from the registry.
+    fn parse_hash_into(&self, addr: &str, hash: &mut Vec<u8>) -> Result<(&AddressFormat, Network, AddressType)> {
+        static LEGACY: AddressFormat = AddressFormat::Legacy;
+        let _span = trace::parse_span(addr);
+
+        if is_prefixed(addr) {
+            return self.cash_converter.parse_into(addr, hash)
+                .map_err(|err| self.invalid_address(addr, err));
+        }
+
+        legacy_converter::parse_into(addr, hash).map(|(network, addr_type)| (&LEGACY, network, addr_type))
+        .inspect(|_| trace::decoder_matched(trace::Decoder::Legacy))
+        .or_else(|err| {
+            trace::decoder_failed(trace::Decoder::Legacy, &err);
+            self.cash_converter.parse_into(addr, hash)
+        })
+        .map_err(|err| self.invalid_address(addr, err))
+    }
+
+    /// Error for an address no decoder accepted; `err` only reaches the trace and the observer.
+    fn invalid_address(&self, addr: &str, err: Error) -> Error {
+        trace::rejected(addr, &err);
+        self.observer.parse_failure(addr, &err);
+        Error::InvalidAddress(addr.to_string())
+    }
+
+    /// Lightweight `parse` for the network/type queries; the hash is never copied out.
+    fn classify(&self, addr: &str) -> Result<(&AddressFormat, Network, AddressType)> {
+        static LEGACY: AddressFormat = AddressFormat::Legacy;
+        let _span = trace::parse_span(addr);
+
+        if is_prefixed(addr) {
+            return self.cash_converter.classify(addr)
+                .map_err(|err| self.invalid_address(addr, err));
+        }
+
+        legacy_converter::classify(addr).map(|(network, addr_type)| (&LEGACY, network, addr_type))
+        .inspect(|_| trace::decoder_matched(trace::Decoder::Legacy))
+        .or_else(|err| {
+            trace::decoder_failed(trace::Decoder::Legacy, &err);
+            self.cash_converter.classify(addr)
+        })
+        .map_err(|err| self.invalid_address(addr, err))
     }
 
     /// Detect address format.
@@ -226,14 +1600,17 @@ impl Converter {
     /// # Returns
     /// * Address format.
     /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
     /// # use bch_addr::{Converter, AddressFormat};
     /// # let converter = Converter::new();
     /// let format = converter.detect_addr_format("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap();
     /// assert_eq!(format, AddressFormat::CashAddr);
+    /// # }
     /// ```
     pub fn detect_addr_format(&self, addr: &str) -> Result<AddressFormat> {
-        let (format, _, _, _) = self.parse(addr)?;
-        Ok(format)
+        let (format, _, _) = self.classify(addr)?;
+        Ok(format.clone())
     }
 
     /// Return `true` if the given address is in cash_addr format.
@@ -242,10 +1619,13 @@ impl Converter {
     /// # Returns
     /// * `true` if the given address is in cash_addr format, `false` otherwise.
     /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
     /// # use bch_addr::Converter;
     /// # let converter = Converter::new();
     /// let is_cash = converter.is_cash_addr("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
     /// assert_eq!(is_cash, true);
+    /// # }
     /// ```
     pub fn is_cash_addr(&self, addr: &str) -> bool {
         self.cash_converter.parse(addr).is_ok()
@@ -257,10 +1637,13 @@ impl Converter {
     /// # Returns
     /// * `true` if the given address is in legacy format, `false` otherwise.
     /// ```
+    /// # #[cfg(feature = "legacy")]
+    /// # {
     /// # use bch_addr::Converter;
     /// # let converter = Converter::new();
     /// let is_legacy = converter.is_legacy_addr("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR");
     /// assert_eq!(is_legacy, true);
+    /// # }
     /// ```
     pub fn is_legacy_addr(&self, addr: &str) -> bool {
         legacy_converter::parse(addr).is_ok()
@@ -272,13 +1655,16 @@ impl Converter {
     /// # Returns
     /// * Address network.
     /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
     /// # use bch_addr::{Converter, Network};
     /// # let converter = Converter::new();
     /// let network = converter.detect_addr_network("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap();
     /// assert_eq!(network, Network::Mainnet);
+    /// # }
     /// ```
     pub fn detect_addr_network(&self, addr: &str) -> Result<Network> {
-        let (_, network, _, _) = self.parse(addr)?;
+        let (_, network, _) = self.classify(addr)?;
         Ok(network)
     }
 
@@ -288,10 +1674,13 @@ impl Converter {
     /// # Returns
     /// * `true` if the given address is in mainnet address, `false` otherwise.
     /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
     /// # use bch_addr::Converter;
     /// # let converter = Converter::new();
     /// let is_mainnet = converter.is_mainnet_addr("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
     /// assert_eq!(is_mainnet, true);
+    /// # }
     /// ```
     pub fn is_mainnet_addr(&self, addr: &str) -> bool {
         match self.detect_addr_network(addr) {
@@ -306,10 +1695,13 @@ impl Converter {
     /// # Returns
     /// * `true` if the given address is in testnet address, `false` otherwise.
     /// ```
+    /// # #[cfg(feature = "legacy")]
+    /// # {
     /// # use bch_addr::Converter;
     /// # let converter = Converter::new();
     /// let is_testnet = converter.is_testnet_addr("mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi");
     /// assert_eq!(is_testnet, true);
+    /// # }
     /// ```
     pub fn is_testnet_addr(&self, addr: &str) -> bool {
         match self.detect_addr_network(addr) {
@@ -324,10 +1716,13 @@ impl Converter {
     /// # Returns
     /// * `true` if the given address is in regtest address, `false` otherwise.
     /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
     /// # use bch_addr::Converter;
     /// # let converter = Converter::new();
     /// let is_regtest = converter.is_regtest_addr("bchreg:qph5kuz78czq00e3t85ugpgd7xmer5kr7c28g5v92v");
     /// assert_eq!(is_regtest, true);
+    /// # }
     /// ```
     pub fn is_regtest_addr(&self, addr: &str) -> bool {
         match self.detect_addr_network(addr) {
@@ -342,26 +1737,56 @@ impl Converter {
     /// # Returns
     /// * Address type.
     /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
     /// # use bch_addr::{Converter, AddressType};
     /// # let converter = Converter::new();
     /// let addr_type = converter.detect_addr_type("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap();
     /// assert_eq!(addr_type, AddressType::P2PKH);
+    /// # }
     /// ```
     pub fn detect_addr_type(&self, addr: &str) -> Result<AddressType> {
-        let (_, _, addr_type, _) = self.parse(addr)?;
+        let (_, _, addr_type) = self.classify(addr)?;
         Ok(addr_type)
     }
 
+    /// Detect format, network and type of many addresses in one call, e.g. to route an intake batch.
+    /// Hashes are validated but never copied out.
+    /// # Arguments
+    /// * `addrs` - Addresses in any format.
+    /// # Returns
+    /// * Format, network and type of each address, in input order.
+    /// ```
+    /// # #[cfg(feature = "legacy")]
+    /// # {
+    /// # use bch_addr::{Converter, AddressFormat, Network, AddressType};
+    /// # let converter = Converter::new();
+    /// let detected = converter.detect_batch(&["1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "nope"]);
+    /// assert_eq!(detected[0].as_ref().unwrap(), &(AddressFormat::Legacy, Network::Mainnet, AddressType::P2PKH));
+    /// assert!(detected[1].is_err());
+    /// # }
+    /// ```
+    pub fn detect_batch<I, T>(&self, addrs: I) -> Vec<Result<(AddressFormat, Network, AddressType)>>
+        where I: IntoIterator<Item = T>, T: AsRef<str>
+    {
+        addrs.into_iter()
+            .map(|addr| self.classify(addr.as_ref()).map(|(format, network, addr_type)| (format.clone(), network, addr_type)))
+            .collect()
+    }
+
     /// Return `true` if the given address is in P2PKH address.
     /// # Arguments
     /// * `addr` - Address in any format.
     /// # Returns
     /// * `true` if the given address is in P2PKH address, `false` otherwise.
     /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
     /// # use bch_addr::Converter;
     /// # let converter = Converter::new();
     /// let is_p2pkh = converter.is_p2pkh_addr("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
     /// assert_eq!(is_p2pkh, true);
+    /// # }
     /// ```
     pub fn is_p2pkh_addr(&self, addr: &str) -> bool {
         match self.detect_addr_type(addr) {
@@ -376,10 +1801,13 @@ impl Converter {
     /// # Returns
     /// * `true` if the given address is in P2SH address, `false` otherwise.
     /// ```
+    /// # #[cfg(feature = "legacy")]
+    /// # {
     /// # use bch_addr::Converter;
     /// # let converter = Converter::new();
     /// let is_p2sh = converter.is_p2sh_addr("3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9");
     /// assert_eq!(is_p2sh, true);
+    /// # }
     /// ```
     pub fn is_p2sh_addr(&self, addr: &str) -> bool {
         match self.detect_addr_type(addr) {
@@ -387,10 +1815,71 @@ impl Converter {
             Err(_)     => false,
         }
     }
+
+    /// Return `true` if the given address is of `network` and `addr_type`, decoding it once.
+    /// Same as `is_mainnet_addr(addr) && is_p2pkh_addr(addr)` and the like, without the second decode.
+    /// # Arguments
+    /// * `addr` - Address in any format.
+    /// * `network` - Expected network. Legacy testnet addresses are testnet, not regtest.
+    /// * `addr_type` - Expected address type.
+    /// # Returns
+    /// * `true` if the given address is valid and matches, `false` otherwise.
+    /// ```
+    /// # #[cfg(feature = "legacy")]
+    /// # {
+    /// # use bch_addr::{Converter, Network, AddressType};
+    /// # let converter = Converter::new();
+    /// assert!(converter.is_address_of("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", Network::Mainnet, AddressType::P2PKH));
+    /// assert!(!converter.is_address_of("3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9", Network::Mainnet, AddressType::P2PKH));
+    /// # }
+    /// ```
+    pub fn is_address_of(&self, addr: &str, network: Network, addr_type: AddressType) -> bool {
+        matches!(self.classify(addr), Ok((_, found_network, found_type)) if found_network == network && found_type == addr_type)
+    }
+
+    /// Same as `is_address_of` but also requires the address to be in `format`.
+    /// # Arguments
+    /// * `addr` - Address in any format.
+    /// * `format` - Expected address format.
+    /// * `network` - Expected network.
+    /// * `addr_type` - Expected address type.
+    /// # Returns
+    /// * `true` if the given address is valid and matches, `false` otherwise.
+    /// ```
+    /// # #[cfg(feature = "cashaddr")]
+    /// # {
+    /// # use bch_addr::{Converter, AddressFormat, Network, AddressType};
+    /// # let converter = Converter::new();
+    /// let addr = "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk";
+    /// assert!(converter.is_address_of_format(addr, &AddressFormat::CashAddr, Network::Mainnet, AddressType::P2PKH));
+    /// assert!(!converter.is_address_of_format(addr, &AddressFormat::Legacy, Network::Mainnet, AddressType::P2PKH));
+    /// # }
+    /// ```
+    pub fn is_address_of_format(&self, addr: &str, format: &AddressFormat, network: Network, addr_type: AddressType) -> bool {
+        matches!(self.classify(addr), Ok((found_format, found_network, found_type))
+            if found_format == format && found_network == network && found_type == addr_type)
+    }
+}
+
+/// `true` if `addr` carries a cash_addr prefix; base58 has no `:`, so legacy decoding can be skipped.
+fn is_prefixed(addr: &str) -> bool {
+    addr.contains(':')
+}
+
+/// Output for a cash_addr address already in the target format and network: as is,
+/// but lowercase if prefixed, as QR codes carry `BITCOINCASH:QPH5...`.
+fn reused_cash_addr(addr: &str) -> Cow<'_, str> {
+    if is_prefixed(addr) && addr.bytes().any(|c| c.is_ascii_uppercase()) {
+        Cow::Owned(addr.to_ascii_lowercase())
+    } else {
+        Cow::Borrowed(addr)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    #![cfg_attr(not(all(feature = "legacy", feature = "cashaddr")), allow(dead_code, unused_imports))]
+
     use super::*;
 
     // https://github.com/bitcoincashjs/bchaddrjs/blob/master/test/bchaddr.js
@@ -771,7 +2260,7 @@ mod tests {
 
     fn no_prefix(data: Vec<&'static str>) -> Vec<&'static str> {
         data.into_iter().map(|el| {
-            el.splitn(2, ':').nth(1).unwrap()
+            el.split_once(':').unwrap().1
         }).collect()
     }
 
@@ -799,8 +2288,16 @@ mod tests {
             assert_eq!(conv_legacy, legacy_addresses()[i]);
             assert_eq!(conv_cash, cash_addresses_no_prefix()[i]);
         }
+
+        for (i, addr) in cash_addresses().iter().enumerate() {
+            let upper = addr.to_uppercase();
+            let conv_legacy = converter.to_legacy_addr(&upper).unwrap();
+
+            assert_eq!(conv_legacy, legacy_addresses()[i]);
+        }
     }
 
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
     #[test]
     fn convert_test() {
         let converter = Converter::new();
@@ -808,6 +2305,184 @@ mod tests {
         convert_test_base(&converter);
     }
 
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    #[test]
+    fn encode_into_test() {
+        let converter = Converter::new();
+        let mut buf = [0; MAX_ADDR_LEN];
+
+        for (i, addr) in legacy_addresses().iter().enumerate() {
+            let (_, network, addr_type, hash) = converter.parse(addr).unwrap();
+
+            let len = converter.encode_into(&AddressFormat::Legacy, network, addr_type, &hash, &mut buf).unwrap();
+            assert_eq!(&buf[..len], addr.as_bytes());
+
+            let len = converter.encode_into(&AddressFormat::CashAddr, network, addr_type, &hash, &mut buf).unwrap();
+            assert_eq!(&buf[..len], cash_addresses()[i].as_bytes());
+
+            match converter.encode_into(&AddressFormat::Legacy, network, addr_type, &hash, &mut buf[..20]) {
+                Err(Error::BufferTooSmall(_)) => (),
+                other => panic!("unexpected result: {:?}", other),
+            }
+        }
+    }
+
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    #[test]
+    fn to_all_formats_test() {
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+        let slp = AddressFormat::Other("SLPAddr".to_string());
+
+        for (i, addr) in legacy_addresses().iter().enumerate() {
+            let formats = converter.to_all_formats(cash_addresses_no_prefix()[i]).unwrap();
+            assert_eq!(formats[&AddressFormat::Legacy], *addr);
+            assert_eq!(formats[&AddressFormat::CashAddr], cash_addresses()[i]);
+            match formats.get(&slp) {
+                Some(slp_addr) => assert_eq!(converter.to_cash_addr(slp_addr).unwrap(), cash_addresses()[i]),
+                None           => assert_eq!(converter.detect_addr_network(addr).unwrap(), Network::Testnet),
+            }
+        }
+
+        let p2sh32 = converter.encode(&AddressFormat::CashAddr, Network::Mainnet, AddressType::P2SH, &[0xab; 32]).unwrap();
+        let formats = converter.to_all_formats(&p2sh32).unwrap();
+        assert!(!formats.contains_key(&AddressFormat::Legacy));
+        assert_eq!(formats[&AddressFormat::CashAddr], p2sh32);
+
+        assert!(converter.to_all_formats("bitcoincash:bad").is_err());
+    }
+
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    #[test]
+    fn equivalents_across_networks_test() {
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+
+        for addr in [legacy_addresses(), cash_addresses(), regtest_addresses()].concat() {
+            let (format, network, addr_type, hash) = converter.parse(addr).unwrap();
+            let equivalents = converter.equivalents_across_networks(addr).unwrap();
+            assert_eq!(equivalents.len(), 3);
+
+            for (&other, equivalent) in &equivalents {
+                let (other_format, _, other_type, other_hash) = converter.parse(equivalent).unwrap();
+                assert_eq!((other_format, other_type, other_hash), (format.clone(), addr_type, hash.clone()));
+                assert_eq!(&converter.to_network(addr, other).unwrap(), equivalent);
+                if other == network {
+                    assert_eq!(equivalent, addr);
+                }
+            }
+        }
+
+        let equivalents = converter.equivalents_across_networks("simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg").unwrap();
+        assert_eq!(equivalents.into_iter().collect::<Vec<_>>(), [(Network::Mainnet, "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg".to_string())]);
+        assert!(matches!(
+            converter.to_network("simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg", Network::Testnet),
+            Err(Error::UnknownCashFormat(AddressFormat::Other(_), Network::Testnet)),
+        ));
+        assert_eq!(converter.to_network("BCHTEST:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7CSM740KF2", Network::Testnet).unwrap(), "bchtest:qph5kuz78czq00e3t85ugpgd7xmer5kr7csm740kf2");
+    }
+
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    #[test]
+    fn detect_test() {
+        let converter = Converter::new();
+        let addresses = [legacy_addresses(), cash_addresses(), cash_addresses_no_prefix(), regtest_addresses()].concat();
+
+        let detected = converter.detect_batch(&addresses);
+        assert_eq!(detected.len(), addresses.len());
+        for (addr, detected) in addresses.iter().zip(detected) {
+            let (format, network, addr_type, _) = converter.parse(addr).unwrap();
+
+            assert_eq!(converter.detect_addr_format(addr).unwrap(), format);
+            assert_eq!(converter.detect_addr_network(addr).unwrap(), network);
+            assert_eq!(converter.detect_addr_type(addr).unwrap(), addr_type);
+            assert_eq!(detected.unwrap(), (format, network, addr_type));
+        }
+
+        assert!(converter.detect_addr_network("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzr").is_err());
+        assert!(converter.detect_addr_type("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwq").is_err());
+
+        for addr in legacy_addresses().into_iter().chain(cash_addresses()).chain(regtest_addresses()) {
+            let (format, network, addr_type, _) = converter.parse(addr).unwrap();
+            for &other_network in &[Network::Mainnet, Network::Testnet, Network::Regtest] {
+                for &other_type in &[AddressType::P2PKH, AddressType::P2SH] {
+                    let expected = other_network == network && other_type == addr_type;
+                    assert_eq!(converter.is_address_of(addr, other_network, other_type), expected);
+                    assert_eq!(converter.is_address_of_format(addr, &format, other_network, other_type), expected);
+                    assert!(!converter.is_address_of_format(addr, &AddressFormat::Other("SLPAddr".to_string()), other_network, other_type));
+                }
+            }
+        }
+        assert!(!converter.is_address_of("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzr", Network::Mainnet, AddressType::P2PKH));
+
+        let slp_converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+        let detected = slp_converter.detect_batch(vec![
+            "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg".to_string(),
+            "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwq".to_string(),
+        ]);
+        assert_eq!(detected[0].as_ref().unwrap(), &(AddressFormat::Other("SLPAddr".to_string()), Network::Mainnet, AddressType::P2PKH));
+        assert!(detected[1].is_err());
+        assert!(converter.detect_batch(Vec::<&str>::new()).is_empty());
+    }
+
+    #[cfg(feature = "legacy")]
+    #[cfg(feature = "cashaddr")]
+    #[test]
+    fn verify_checksum_test() {
+        let converter = Converter::new();
+        let addresses = [legacy_addresses(), cash_addresses(), cash_addresses_no_prefix(), regtest_addresses()].concat();
+        for addr in addresses {
+            assert!(converter.verify_checksum(addr).is_ok(), "{}", addr);
+        }
+
+        // unknown version byte (litecoin) and unregistered prefix: bad addresses, good checksums
+        let litecoin = bs58::encode([&[0x30][..], &[0xab; 20]].concat()).with_check().into_string();
+        assert!(converter.parse(&litecoin).is_err());
+        assert!(converter.verify_checksum(&litecoin).is_ok());
+        let foreign = cash_addr::encode("foo", AddressType::P2PKH, &[0xab; 20]).unwrap();
+        assert!(converter.parse(&foreign).is_err());
+        assert!(converter.verify_checksum(&foreign).is_ok());
+
+        assert!(converter.verify_checksum("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzr").is_err());
+        assert!(converter.verify_checksum("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwq").is_err());
+        assert!(converter.verify_checksum("qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwq").is_err());
+        assert!(converter.verify_checksum("").is_err());
+    }
+
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    #[test]
+    fn context_test() {
+        let converter = Converter::new().add_prefixes(
+            &[
+                ("simpleledger", Network::Mainnet),
+                ("slptest", Network::Testnet),
+            ],
+            "SLP",
+        );
+        let mut ctx = ConversionCtx::new(&converter);
+        let slp = Some(AddressFormat::Other("SLP".to_string()));
+
+        for (i, addr) in legacy_addresses().iter().enumerate() {
+            assert_eq!(ctx.to_cash_addr(addr).unwrap(), cash_addresses()[i]);
+            assert_eq!(ctx.to_legacy_addr(addr).unwrap(), *addr);
+            assert_eq!(ctx.to_cash_addr_with_options(addr, slp.clone(), None).unwrap(), slp_addresses()[i]);
+        }
+
+        for (i, addr) in cash_addresses_no_prefix().iter().enumerate() {
+            assert_eq!(ctx.to_legacy_addr(addr).unwrap(), legacy_addresses()[i]);
+            assert_eq!(ctx.to_cash_addr(addr).unwrap(), *addr);
+            assert_eq!(ctx.to_cash_addr_with_options(addr, slp.clone(), None).unwrap(), slp_addresses()[i]);
+        }
+
+        for addr in legacy_addresses().iter().chain(&slp_addresses()) {
+            let (format, network, addr_type, hash) = converter.parse(addr).unwrap();
+            let (ctx_format, ctx_network, ctx_addr_type, ctx_hash) = ctx.parse(addr).unwrap();
+            assert_eq!((ctx_format, ctx_network, ctx_addr_type, ctx_hash), (&format, network, addr_type, &hash[..]));
+        }
+
+        assert!(ctx.to_cash_addr("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwq").is_err());
+        assert!(ctx.to_cash_addr_with_options(legacy_addresses()[0], Some(AddressFormat::Legacy), None).is_err());
+    }
+
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
     #[test]
     fn regtest_addr() {
         let converter = Converter::new();
@@ -835,6 +2510,7 @@ mod tests {
         }
     }
 
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
     #[test]
     fn slp_convert() {
         let converter = Converter::new().add_prefixes(
@@ -869,4 +2545,413 @@ mod tests {
             assert_eq!(conv_cash, slp_addresses_no_prefix()[i]);
         }
     }
+
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    #[test]
+    fn prefix_case_test() {
+        let converter = Converter::new().add_prefixes(&[("SimpleLedger", Network::Mainnet)], "SLP");
+        let slp = AddressFormat::Other("SLP".to_string());
+
+        assert_eq!(converter.to_legacy_addr("simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg").unwrap(), "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR");
+        assert_eq!(converter.to_legacy_addr("SIMPLELEDGER:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7CCJ3FCPSG").unwrap(), "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR");
+        assert_eq!(
+            converter.to_cash_addr_with_options("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", Some(slp), None).unwrap(),
+            "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg",
+        );
+    }
+
+    #[test]
+    fn parse_raw_legacy_test() {
+        let converter = Converter::new();
+        let hash = hash::from_hex("6f4b705e3e0407bf3159e9c4050df1b791d2c3f6").unwrap();
+        let mut raw = vec![0xc4];
+        raw.extend_from_slice(&hash);
+        assert_eq!(converter.parse_raw_legacy(&raw).unwrap(), (Network::Testnet, AddressType::P2SH, &hash[..]));
+
+        // checksum included, as decoded from the address
+        #[cfg(feature = "legacy")]
+        {
+            let (version_byte, payload) = legacy::decode("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap();
+            let mut full = bs58::decode("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").into_vec().unwrap();
+            assert_eq!(full[0], version_byte);
+            assert_eq!(converter.parse_raw_legacy(&full).unwrap(), (Network::Mainnet, AddressType::P2PKH, &payload[..]));
+            full[24] ^= 1;
+            assert!(converter.parse_raw_legacy(&full).is_err());
+        }
+
+        raw[0] = 0x80;
+        match converter.parse_raw_legacy(&raw) {
+            Err(Error::UnknownLegacyPrefix(0x80)) => {},
+            result => panic!("{:?}", result),
+        }
+        assert!(converter.parse_raw_legacy(&raw[..20]).is_err());
+        assert!(converter.parse_raw_legacy(&[]).is_err());
+    }
+
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    #[test]
+    fn case_test() {
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+        let slp = Some(AddressFormat::Other("SLPAddr".to_string()));
+        let to_cash_addr = |addr, format, case| converter.to_cash_addr_with_case(addr, format, None, case).unwrap();
+
+        for &(input, match_input) in &[
+            ("BITCOINCASH:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK", "SIMPLELEDGER:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7CCJ3FCPSG"),
+            ("QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK", "SIMPLELEDGER:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7CCJ3FCPSG"),
+            ("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg"),
+            ("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg"),
+        ] {
+            assert_eq!(to_cash_addr(input, slp.clone(), Case::MatchInput), match_input);
+            assert_eq!(to_cash_addr(input, slp.clone(), Case::Lower), "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg");
+            assert_eq!(to_cash_addr(input, slp.clone(), Case::Upper), "SIMPLELEDGER:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7CCJ3FCPSG");
+        }
+
+        // addresses already in the target format are returned as is, but in the requested case
+        assert_eq!(to_cash_addr("QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK", None, Case::Lower), "qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+        assert_eq!(to_cash_addr("QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK", None, Case::MatchInput), "QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK");
+        assert_eq!(Case::default(), Case::Lower);
+    }
+
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    #[test]
+    fn cow_test() {
+        let converter = Converter::new().add_prefix_alias("bch", "bitcoincash");
+        let borrowed = |cow: &Cow<'_, str>| matches!(cow, Cow::Borrowed(_));
+
+        for &(input, expected, is_borrowed) in &[
+            ("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", true),
+            ("qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", "qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", true),
+            ("QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK", "QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK", true),
+            ("BITCOINCASH:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK", "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", false),
+            ("bch:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", false),
+            ("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", false),
+        ] {
+            let cow = converter.to_cash_addr_cow(input).unwrap();
+            assert_eq!((&*cow, borrowed(&cow)), (expected, is_borrowed), "{}", input);
+            assert_eq!(converter.to_cash_addr(input).unwrap(), expected);
+        }
+
+        let cow = converter.to_cash_addr_with_options_cow("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", None, Some(Network::Mainnet)).unwrap();
+        assert!(borrowed(&cow));
+        let cow = converter.to_cash_addr_with_options_cow("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", None, Some(Network::Testnet)).unwrap();
+        assert_eq!((&*cow, borrowed(&cow)), ("bchtest:qph5kuz78czq00e3t85ugpgd7xmer5kr7csm740kf2", false));
+        let cow = converter.to_cash_addr_with_options_cow("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", None, Some(Network::Testnet)).unwrap();
+        assert_eq!(cow, "bchtest:qph5kuz78czq00e3t85ugpgd7xmer5kr7csm740kf2");
+
+        #[cfg(feature = "legacy")]
+        {
+            let cow = converter.to_legacy_addr_cow("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap();
+            assert!(borrowed(&cow));
+            let cow = converter.to_legacy_addr_cow("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap();
+            assert_eq!((&*cow, borrowed(&cow)), ("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", false));
+        }
+        assert!(converter.to_cash_addr_cow("bitcoincash:bad").is_err());
+    }
+
+    #[cfg(feature = "cashaddr")]
+    #[test]
+    fn qr_uppercase_test() {
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+        let slp = AddressFormat::Other("SLPAddr".to_string());
+
+        assert_eq!(converter.to_cash_addr("BITCOINCASH:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK").unwrap(), "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+        assert_eq!(converter.to_cash_addr("BCHTEST:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7CSM740KF2").unwrap(), "bchtest:qph5kuz78czq00e3t85ugpgd7xmer5kr7csm740kf2");
+        assert_eq!(
+            converter.to_cash_addr_with_options("SIMPLELEDGER:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7CCJ3FCPSG", Some(slp.clone()), None).unwrap(),
+            "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg",
+        );
+        let mut ctx = ConversionCtx::new(&converter);
+        assert_eq!(ctx.to_cash_addr("BITCOINCASH:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK").unwrap(), "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+        assert_eq!(ctx.to_cash_addr_with_options("BITCOINCASH:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK", None, Some(Network::Testnet)).unwrap(), "bchtest:qph5kuz78czq00e3t85ugpgd7xmer5kr7csm740kf2");
+
+        let (addr, info) = converter.to_cash_addr_with_info("BITCOINCASH:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK", None, Some(Network::Mainnet)).unwrap();
+        assert_eq!(addr, "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+        assert_eq!(info.normalizations, [Normalization::Lowercased]);
+        let (addr, _) = converter.to_cash_addr_with_info("BITCOINCASH:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK", None, Some(Network::Regtest)).unwrap();
+        assert_eq!(addr, "bchreg:qph5kuz78czq00e3t85ugpgd7xmer5kr7c28g5v92v");
+
+        // mixed case is still invalid, and prefix-less addresses are still returned as is
+        assert!(converter.to_cash_addr("BITCOINCASH:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWk").is_err());
+        assert_eq!(converter.to_cash_addr("QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK").unwrap(), "QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK");
+    }
+
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    #[test]
+    fn prefix_alias_test() {
+        // the target of an alias can be registered after it
+        let converter = Converter::new()
+            .add_prefix_alias("BCH", "bitcoincash")
+            .add_prefix_alias("xec", "ecash")
+            .add_prefixes(&[("ecash", Network::Mainnet)], "XEC")
+            .add_prefix_alias("simpleledger", "nowhere");
+        let xec = AddressFormat::Other("XEC".to_string());
+
+        assert_eq!(converter.to_cash_addr("bch:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap(), "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+        assert_eq!(converter.to_cash_addr("BCH:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK").unwrap(), "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+        assert_eq!(converter.to_legacy_addr("xec:qph5kuz78czq00e3t85ugpgd7xmer5kr7cdywekmgp").unwrap(), "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR");
+        assert_eq!(
+            converter.to_cash_addr_with_options("xec:qph5kuz78czq00e3t85ugpgd7xmer5kr7cdywekmgp", Some(xec.clone()), None).unwrap(),
+            "ecash:qph5kuz78czq00e3t85ugpgd7xmer5kr7cdywekmgp",
+        );
+        assert_eq!(ConversionCtx::new(&converter).to_cash_addr("bch:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").unwrap(), "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+        assert_eq!(converter.format_for_prefix("xec"), Some((xec, Network::Mainnet)));
+        assert!(converter.verify_checksum("bch:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").is_ok());
+
+        // the checksum is the prefix's, not the alias's
+        assert!(converter.parse("bch:qph5kuz78czq00e3t85ugpgd7xmer5kr7c0pl5utcx").is_err());
+        assert!(converter.verify_checksum("bch:qph5kuz78czq00e3t85ugpgd7xmer5kr7c0pl5utcx").is_err());
+        // aliases of unregistered prefixes are unknown
+        assert!(converter.parse("simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg").is_err());
+        assert!(Converter::new().parse("bch:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").is_err());
+
+        let mut validator = IncrementalValidator::new(&converter);
+        assert_eq!(validator.push_str("bch:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk"), ValidationState::CompleteValid);
+        assert_eq!(converter.guess_format("xe"), [(AddressFormat::Other("XEC".to_string()), 1.0)]);
+    }
+
+    #[cfg(feature = "cashaddr")]
+    #[test]
+    fn type_bits_test() {
+        for bits in 0..=15 {
+            assert_eq!(AddressType::from_type_bits(bits).type_bits(), bits);
+        }
+
+        let hash = [0x5a; 20];
+        let exp = AddressFormat::Other("Experimental".to_string());
+        let converter = Converter::new()
+            .add_type_bits(&AddressFormat::CashAddr, &[0, 1, 2, 200])
+            .add_type_bits(&exp, &[15])
+            .add_prefixes(&[("experimental", Network::Mainnet)], "Experimental");
+
+        let addr = converter.encode(&AddressFormat::CashAddr, Network::Mainnet, AddressType::Other(2), &hash).unwrap();
+        assert_eq!(converter.parse(&addr).unwrap(), (AddressFormat::CashAddr, Network::Mainnet, AddressType::Other(2), hash.to_vec()));
+        let body = addr.split(':').nth(1).unwrap();
+        assert_eq!(converter.parse(body).unwrap().2, AddressType::Other(2));
+        let addr = converter.encode(&exp, Network::Mainnet, AddressType::Other(15), &hash).unwrap();
+        assert_eq!(converter.parse(&addr).unwrap(), (exp.clone(), Network::Mainnet, AddressType::Other(15), hash.to_vec()));
+
+        // registration is per format and per type
+        assert!(converter.encode(&exp, Network::Mainnet, AddressType::Other(2), &hash).is_err());
+        assert!(converter.encode(&AddressFormat::CashAddr, Network::Mainnet, AddressType::Other(15), &hash).is_err());
+        // the assigned and oversized type fields are not registered
+        for &bits in &[0, 1, 200] {
+            assert!(converter.encode(&AddressFormat::CashAddr, Network::Mainnet, AddressType::Other(bits), &hash).is_err());
+            assert!(cash_addr::encode("bitcoincash", AddressType::Other(bits), &hash).is_err());
+        }
+        assert!(Converter::new().parse(&converter.encode(&AddressFormat::CashAddr, Network::Mainnet, AddressType::Other(2), &hash).unwrap()).is_err());
+
+        // legacy has no type field for them
+        match converter.encode(&AddressFormat::Legacy, Network::Mainnet, AddressType::Other(2), &hash) {
+            Err(Error::UnsupportedAddressType(AddressType::Other(2))) => {},
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(converter.to_legacy_addr(&addr).is_err());
+    }
+
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    #[test]
+    fn snapshot_test() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct Failures(Arc<AtomicUsize>);
+
+        impl Observer for Failures {
+            fn on_parse_failure(&self, _addr: &str, _err: &Error) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let failures = Arc::new(AtomicUsize::new(0));
+        let mut converter = Converter::new().with_observer(Failures(Arc::clone(&failures)));
+        let defaults = converter.snapshot();
+        let slp = "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg";
+
+        let reloaded = Converter::new()
+            .add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr")
+            .add_prefix_alias("slp", "simpleledger")
+            .snapshot();
+        converter.restore(reloaded.clone());
+        assert!(Arc::ptr_eq(&converter.cash_converter, &reloaded.0));
+        assert_eq!(converter.to_legacy_addr("slp:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg").unwrap(), "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR");
+
+        // building on a restored configuration leaves the snapshot as it was
+        let converter = converter.add_prefixes(&[("ecash", Network::Mainnet)], "XEC");
+        assert!(converter.is_mainnet_addr("ecash:qph5kuz78czq00e3t85ugpgd7xmer5kr7cdywekmgp"));
+        let mut from_snapshot = Converter::new();
+        from_snapshot.restore(reloaded);
+        assert!(from_snapshot.is_mainnet_addr(slp));
+        assert!(!from_snapshot.is_mainnet_addr("ecash:qph5kuz78czq00e3t85ugpgd7xmer5kr7cdywekmgp"));
+
+        // the observer stays across restores
+        let mut converter = converter;
+        converter.restore(defaults);
+        assert!(converter.parse(slp).is_err());
+        assert_eq!(failures.load(Ordering::Relaxed), 1);
+    }
+
+    #[cfg(feature = "cashaddr")]
+    #[test]
+    fn hasher160_test() {
+        let converter = Converter::new();
+        let pubkey = [0x02; 33];
+        let fixed = |_: &[u8]| [0xab; hash::HASH160_LEN];
+
+        let addr = converter.from_pubkey_with(&fixed, &pubkey, &AddressFormat::CashAddr, Network::Mainnet).unwrap();
+        assert_eq!(addr, converter.encode(&AddressFormat::CashAddr, Network::Mainnet, AddressType::P2PKH, &[0xab; 20]).unwrap());
+        let addr = converter.from_redeem_script_with(&fixed, &[0x51], &AddressFormat::CashAddr, Network::Mainnet).unwrap();
+        assert_eq!(addr, converter.encode(&AddressFormat::CashAddr, Network::Mainnet, AddressType::P2SH, &[0xab; 20]).unwrap());
+
+        // the key is checked before hashing
+        let unreachable = |_: &[u8]| -> [u8; hash::HASH160_LEN] { panic!("hashed an invalid key") };
+        assert!(matches!(converter.from_pubkey_with(&unreachable, &[0x05; 33], &AddressFormat::CashAddr, Network::Mainnet), Err(Error::InvalidPublicKey(33))));
+
+        #[cfg(all(feature = "hash160", feature = "legacy"))]
+        {
+            let dyn_hasher: &dyn Hasher160 = &hash::DefaultHasher160;
+            assert_eq!(
+                converter.from_pubkey_with(dyn_hasher, &pubkey, &AddressFormat::Legacy, Network::Testnet).unwrap(),
+                converter.from_pubkey(&pubkey, &AddressFormat::Legacy, Network::Testnet).unwrap(),
+            );
+        }
+    }
+
+    struct Terse;
+
+    impl ErrorMessages for Terse {
+        fn fmt(&self, err: &Error, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match err {
+                Error::InvalidAddress(_)         => f.write_str("not an address"),
+                Error::UnknownCashPrefix(prefix) => write!(f, "unknown network {:?}", prefix),
+                err                              => write!(f, "{}", err),
+            }
+        }
+    }
+
+    #[test]
+    fn error_display_test() {
+        let converter = Converter::new();
+
+        let err = converter.to_legacy_addr("bitcoincash:bad").unwrap_err();
+        assert_eq!(err.display_with(&Terse).to_string(), "not an address");
+        assert!(matches!(err, Error::InvalidAddress(_)));
+        let messages: &dyn ErrorMessages = &Terse;
+        assert_eq!(Error::UnknownCashPrefix("foo".to_string()).display_with(messages).to_string(), "unknown network \"foo\"");
+        assert_eq!(Error::InvalidHex("zz".to_string()).display_with(&Terse).to_string(), "invalid hex: zz");
+        assert_eq!(format!("{:>16}", err.display_with(&|_: &Error, f: &mut std::fmt::Formatter<'_>| f.pad("padded"))), "          padded");
+    }
+
+    #[cfg(feature = "legacy")]
+    #[cfg(feature = "cashaddr")]
+    #[test]
+    fn legacy_codec_test() {
+        let (_, hash) = legacy::decode("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap();
+
+        // non-standard version bytes and payload lengths round-trip
+        for &(version_byte, payload) in &[(0x1c, &hash[..]), (0x80, &[0xab; 33][..]), (0xff, &[][..])] {
+            let encoded = legacy::encode(version_byte, payload);
+            assert_eq!(legacy::decode(&encoded).unwrap(), (version_byte, payload.to_vec()));
+
+            let mut out = vec![0; legacy::max_len(payload.len())];
+            let len = legacy::encode_into(version_byte, payload, &mut out).unwrap();
+            assert_eq!(&out[..len], encoded.as_bytes());
+        }
+        // which the converter still rejects
+        assert!(Converter::new().parse(&legacy::encode(0x1c, &hash)).is_err());
+        // and only builds from HASH160 payloads
+        let converter = Converter::new();
+        assert!(matches!(
+            converter.encode(&AddressFormat::Legacy, Network::Mainnet, AddressType::P2SH, &[0xab; 32]),
+            Err(Error::CashAddr(cash_addr::Error::InvalidHashSize(32)))
+        ));
+        let long = converter.encode(&AddressFormat::CashAddr, Network::Mainnet, AddressType::P2SH, &[0xab; 32]).unwrap();
+        assert!(converter.to_legacy_addr(&long).is_err());
+
+        let mut out = [0; 8];
+        assert!(matches!(legacy::encode_into(0, &hash, &mut out), Err(Error::BufferTooSmall(_))));
+        assert!(legacy::decode("").is_err());
+        assert!(legacy::decode("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzr").is_err());
+    }
+
+    #[cfg(all(feature = "legacy", not(feature = "cashaddr")))]
+    #[test]
+    fn legacy_only_test() {
+        let converter = Converter::new();
+        let legacy_addr = "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR";
+
+        assert_eq!(converter.detect_addr_type(legacy_addr).unwrap(), AddressType::P2PKH);
+        assert_eq!(converter.to_network(legacy_addr, Network::Testnet).unwrap(), "mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi");
+        assert!(converter.is_legacy_addr(legacy_addr));
+        assert!(matches!(converter.to_cash_addr(legacy_addr), Err(Error::NotSupported("cashaddr"))));
+        assert!(matches!(cash_addr::decode("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk"), Err(cash_addr::Error::NotSupported)));
+        assert!(converter.parse("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").is_err());
+        assert!(converter.parse("qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk").is_err());
+    }
+
+    #[cfg(all(feature = "cashaddr", not(feature = "legacy")))]
+    #[test]
+    fn cashaddr_only_test() {
+        let converter = Converter::new();
+        let cash_addr = "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk";
+
+        let (_, network, addr_type, hash) = converter.parse("QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK").unwrap();
+        assert_eq!(converter.encode(&AddressFormat::CashAddr, network, addr_type, &hash).unwrap(), cash_addr);
+        assert_eq!(converter.to_network(cash_addr, Network::Testnet).unwrap(), "bchtest:qph5kuz78czq00e3t85ugpgd7xmer5kr7csm740kf2");
+        assert!(matches!(converter.to_legacy_addr(cash_addr), Err(Error::NotSupported("legacy"))));
+        assert!(matches!(converter.encode(&AddressFormat::Legacy, Network::Mainnet, AddressType::P2PKH, &[0xab; 20]), Err(Error::NotSupported("legacy"))));
+        assert!(converter.is_cash_addr(cash_addr));
+        assert!(!converter.is_legacy_addr("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR"));
+        assert!(converter.parse("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").is_err());
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn borsh_test() {
+        let mut parsed = ParsedAddress::default();
+        Converter::new().parse_into("bchtest:pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t", &mut parsed).unwrap();
+
+        let bytes = borsh::to_vec(&parsed).unwrap();
+        assert_eq!(borsh::from_slice::<ParsedAddress>(&bytes).unwrap(), parsed);
+        assert_eq!(bytes[..3], [1, 1, 8]);
+    }
+
+    #[cfg(feature = "rkyv")]
+    #[test]
+    fn rkyv_test() {
+        let mut parsed = ParsedAddress::default();
+        Converter::new().parse_into("bchtest:pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t", &mut parsed).unwrap();
+
+        let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&parsed).unwrap();
+        let archived = rkyv::access::<ArchivedParsedAddress, rkyv::rancor::Error>(&bytes).unwrap();
+        assert_eq!(archived.network, Network::Testnet);
+        assert_eq!(archived.addr_type, AddressType::P2SH);
+        assert_eq!(rkyv::deserialize::<ParsedAddress, rkyv::rancor::Error>(archived).unwrap(), parsed);
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary_test() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let converter = Converter::new();
+        let data: Vec<u8> = (0..4096u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8).collect();
+        let mut u = Unstructured::new(&data);
+
+        while !u.is_empty() {
+            let parsed = ParsedAddress::arbitrary(&mut u).unwrap();
+            if let AddressFormat::Other(_) = parsed.format {
+                continue;
+            }
+
+            let addr = converter.encode(&parsed.format, parsed.network, parsed.addr_type, &parsed.hash).unwrap();
+            let mut reparsed = ParsedAddress::default();
+            converter.parse_into(&addr, &mut reparsed).unwrap();
+
+            // legacy regtest addresses share the testnet version bytes.
+            let mut expected = parsed;
+            if expected.format == AddressFormat::Legacy && expected.network == Network::Regtest {
+                expected.network = Network::Testnet;
+            }
+            assert_eq!(reparsed, expected);
+        }
+    }
 }
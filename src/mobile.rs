@@ -0,0 +1,284 @@
+//! Kotlin and Swift bindings built with UniFFI.
+//!
+//! Enabled by the `uniffi` feature. The interface is declared with UniFFI's proc-macros,
+//! so the bindings are generated from the compiled library:
+//! `cargo run --features uniffi-cli --bin uniffi-bindgen generate --library target/debug/libbch_addr.so --language kotlin --out-dir out`
+//! (or `--language swift`).
+//!
+//! The types mirror the crate's own so that they can carry UniFFI's derives.
+
+use std::fmt;
+use std::sync::RwLock;
+
+/// Type of bitcoin network.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, uniffi::Enum)]
+pub enum Network {
+    /// mainnet
+    Mainnet,
+    /// testnet
+    Testnet,
+    /// regtest
+    Regtest,
+}
+
+/// Address type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, uniffi::Enum)]
+pub enum AddressType {
+    /// P2PKH address.
+    P2PKH,
+    /// P2SH address.
+    P2SH,
+    /// Type field the spec does not assign.
+    Other {
+        /// Type field of the version byte.
+        type_bits: u8,
+    },
+}
+
+/// Type of address format.
+#[derive(Clone, Debug, PartialEq, Eq, uniffi::Enum)]
+pub enum AddressFormat {
+    /// Legacy format.
+    Legacy,
+    /// cash_addr format.
+    CashAddr,
+    /// Format registered with `AddressConverter::add_prefix`.
+    Other {
+        /// Format name.
+        name: String,
+    },
+}
+
+/// Parsed address.
+#[derive(Clone, Debug, PartialEq, Eq, uniffi::Record)]
+pub struct ParsedAddress {
+    /// Address format.
+    pub format: AddressFormat,
+    /// Address network.
+    pub network: Network,
+    /// Address type.
+    pub addr_type: AddressType,
+    /// hashed publickey.
+    pub hash: Vec<u8>,
+}
+
+/// Errors thrown to Kotlin/Swift.
+#[derive(Debug, PartialEq, Eq, uniffi::Error)]
+pub enum AddressError {
+    /// Address that can not be parsed.
+    InvalidAddress {
+        /// Error description.
+        message: String,
+    },
+    /// cash_addr prefix that is not registered.
+    UnknownPrefix {
+        /// Error description.
+        message: String,
+    },
+    /// No prefix is registered for the requested format and network.
+    UnknownFormat {
+        /// Error description.
+        message: String,
+    },
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressError::InvalidAddress { message } |
+            AddressError::UnknownPrefix { message } |
+            AddressError::UnknownFormat { message } => f.write_str(message),
+        }
+    }
+}
+
+impl From<crate::Error> for AddressError {
+    fn from(err: crate::Error) -> AddressError {
+        let message = err.to_string();
+        match err {
+            crate::Error::UnknownCashPrefix(_)    => AddressError::UnknownPrefix { message },
+            crate::Error::UnknownCashFormat(_, _) => AddressError::UnknownFormat { message },
+            _                                     => AddressError::InvalidAddress { message },
+        }
+    }
+}
+
+/// `Converter` exported to Kotlin/Swift.
+/// Prefixes can be registered after construction, so the converter sits behind a lock.
+#[derive(Debug, Default, uniffi::Object)]
+pub struct AddressConverter {
+    converter: RwLock<crate::Converter>,
+}
+
+#[uniffi::export]
+impl AddressConverter {
+    /// Construct a converter with the built-in prefixes.
+    #[uniffi::constructor]
+    pub fn new() -> AddressConverter {
+        AddressConverter::default()
+    }
+
+    /// Register `prefix` for `network` under the user-defined format `format_name`.
+    pub fn add_prefix(&self, prefix: String, network: Network, format_name: String) {
+        let mut converter = self.converter.write().expect("converter lock poisoned");
+        *converter = std::mem::take(&mut *converter).add_prefixes(&[(&prefix, network.into())], &format_name);
+    }
+
+    /// Accept the unassigned type fields `type_bits` (2 to 15) in addresses of `format`, parsed as `AddressType::Other`.
+    pub fn add_type_bits(&self, format: AddressFormat, type_bits: Vec<u8>) {
+        let mut converter = self.converter.write().expect("converter lock poisoned");
+        *converter = std::mem::take(&mut *converter).add_type_bits(&format.into(), &type_bits);
+    }
+
+    /// Convert to cash_addr format, or to `format`/`network` when given.
+    pub fn to_cash_addr(&self, addr: String, format: Option<AddressFormat>, network: Option<Network>) -> Result<String, AddressError> {
+        Ok(self.read().to_cash_addr_with_options(&addr, format.map(Into::into), network.map(Into::into))?)
+    }
+
+    /// Convert to legacy format.
+    pub fn to_legacy_addr(&self, addr: String) -> Result<String, AddressError> {
+        Ok(self.read().to_legacy_addr(&addr)?)
+    }
+
+    /// Parse address.
+    pub fn parse(&self, addr: String) -> Result<ParsedAddress, AddressError> {
+        let (format, network, addr_type, hash) = self.read().parse(&addr)?;
+
+        Ok(ParsedAddress {
+            format: format.into(),
+            network: network.into(),
+            addr_type: addr_type.into(),
+            hash,
+        })
+    }
+
+    /// `true` if `addr` is valid in any registered format.
+    pub fn is_valid_addr(&self, addr: String) -> bool {
+        self.read().parse(&addr).is_ok()
+    }
+
+    /// `true` if `addr` is a legacy address.
+    pub fn is_legacy_addr(&self, addr: String) -> bool {
+        self.read().is_legacy_addr(&addr)
+    }
+
+    /// `true` if `addr` is a cash_addr (or user-defined format) address.
+    pub fn is_cash_addr(&self, addr: String) -> bool {
+        self.read().is_cash_addr(&addr)
+    }
+
+    /// `true` if `addr` is a mainnet address.
+    pub fn is_mainnet_addr(&self, addr: String) -> bool {
+        self.read().is_mainnet_addr(&addr)
+    }
+
+    /// `true` if `addr` is a testnet address.
+    pub fn is_testnet_addr(&self, addr: String) -> bool {
+        self.read().is_testnet_addr(&addr)
+    }
+
+    /// `true` if `addr` is a regtest address.
+    pub fn is_regtest_addr(&self, addr: String) -> bool {
+        self.read().is_regtest_addr(&addr)
+    }
+
+    /// `true` if `addr` is a P2PKH address.
+    pub fn is_p2pkh_addr(&self, addr: String) -> bool {
+        self.read().is_p2pkh_addr(&addr)
+    }
+
+    /// `true` if `addr` is a P2SH address.
+    pub fn is_p2sh_addr(&self, addr: String) -> bool {
+        self.read().is_p2sh_addr(&addr)
+    }
+}
+
+impl AddressConverter {
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, crate::Converter> {
+        self.converter.read().expect("converter lock poisoned")
+    }
+}
+
+impl From<Network> for crate::Network {
+    fn from(network: Network) -> crate::Network {
+        match network {
+            Network::Mainnet => crate::Network::Mainnet,
+            Network::Testnet => crate::Network::Testnet,
+            Network::Regtest => crate::Network::Regtest,
+        }
+    }
+}
+
+impl From<crate::Network> for Network {
+    fn from(network: crate::Network) -> Network {
+        match network {
+            crate::Network::Mainnet => Network::Mainnet,
+            crate::Network::Testnet => Network::Testnet,
+            crate::Network::Regtest => Network::Regtest,
+        }
+    }
+}
+
+impl From<crate::AddressType> for AddressType {
+    fn from(addr_type: crate::AddressType) -> AddressType {
+        match addr_type {
+            crate::AddressType::P2PKH           => AddressType::P2PKH,
+            crate::AddressType::P2SH            => AddressType::P2SH,
+            crate::AddressType::Other(type_bits) => AddressType::Other { type_bits },
+        }
+    }
+}
+
+impl From<AddressFormat> for crate::AddressFormat {
+    fn from(format: AddressFormat) -> crate::AddressFormat {
+        match format {
+            AddressFormat::Legacy         => crate::AddressFormat::Legacy,
+            AddressFormat::CashAddr       => crate::AddressFormat::CashAddr,
+            AddressFormat::Other { name } => crate::AddressFormat::Other(name),
+        }
+    }
+}
+
+impl From<crate::AddressFormat> for AddressFormat {
+    fn from(format: crate::AddressFormat) -> AddressFormat {
+        match format {
+            crate::AddressFormat::Legacy      => AddressFormat::Legacy,
+            crate::AddressFormat::CashAddr    => AddressFormat::CashAddr,
+            crate::AddressFormat::Other(name) => AddressFormat::Other { name },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converter_test() {
+        let converter = AddressConverter::new();
+        converter.add_prefix("simpleledger".to_string(), Network::Mainnet, "SLPAddr".to_string());
+
+        let legacy = "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR".to_string();
+        let slp_format = AddressFormat::Other { name: "SLPAddr".to_string() };
+        let slp = converter.to_cash_addr(legacy.clone(), Some(slp_format.clone()), None).unwrap();
+        assert_eq!(slp, "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg");
+        assert_eq!(converter.to_legacy_addr(slp.clone()).unwrap(), legacy);
+
+        let parsed = converter.parse(slp).unwrap();
+        assert_eq!(parsed.format, slp_format);
+        assert_eq!(parsed.network, Network::Mainnet);
+        assert_eq!(parsed.addr_type, AddressType::P2PKH);
+        assert_eq!(parsed.hash.len(), 20);
+
+        assert!(matches!(
+            converter.to_cash_addr(legacy, None, Some(Network::Regtest)),
+            Ok(ref addr) if addr.starts_with("bchreg:")
+        ));
+        assert!(matches!(converter.to_legacy_addr("bitcoincash:bad".to_string()), Err(AddressError::InvalidAddress { .. })));
+
+        let other = "bitcoincash:zp24242424242424242424242424242425fpph3eds".to_string();
+        assert!(converter.parse(other.clone()).is_err());
+        converter.add_type_bits(AddressFormat::CashAddr, vec![2]);
+        assert_eq!(converter.parse(other).unwrap().addr_type, AddressType::Other { type_bits: 2 });
+    }
+}
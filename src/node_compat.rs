@@ -0,0 +1,132 @@
+//! Responses shaped like the node RPCs (bitcoind / BCHN), built without a node.
+
+use super::{Converter, ParsedAddress, AddressFormat, AddressType};
+use super::hash;
+use super::script::script_pub_key;
+
+/// Result of the `validateaddress` RPC.
+/// With the `serde` feature it serializes to the same JSON, e.g.
+/// `{"isvalid":true,"address":"bitcoincash:...","scriptPubKey":"76a914...88ac","isscript":false}`,
+/// and to `{"isvalid":false}` for invalid addresses.
+/// # Example
+/// ```
+/// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+/// # {
+/// # use bch_addr::Converter;
+/// use bch_addr::node_compat::ValidateAddress;
+///
+/// let result = ValidateAddress::new(&Converter::new(), "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR");
+/// assert!(result.isvalid);
+/// assert_eq!(result.address.unwrap(), "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+/// assert_eq!(result.script_pub_key.unwrap(), "76a9146f4b705e3e0407bf3159e9c4050df1b791d2c3f688ac");
+/// assert_eq!(result.isscript, Some(false));
+///
+/// assert_eq!(ValidateAddress::new(&Converter::new(), "bitcoincash:bad"), ValidateAddress::invalid());
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidateAddress {
+    /// `true` if the address is valid.
+    pub isvalid: bool,
+    /// Address in cash_addr format with prefix, as the node prints it.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub address: Option<String>,
+    /// Hex of the output script paying to the address.
+    #[cfg_attr(feature = "serde", serde(rename = "scriptPubKey", default, skip_serializing_if = "Option::is_none"))]
+    pub script_pub_key: Option<String>,
+    /// `true` for P2SH addresses.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub isscript: Option<bool>,
+}
+
+impl ValidateAddress {
+    /// Validate `addr` like the node.
+    /// # Arguments
+    /// * `converter` - Converter providing the prefix configuration.
+    /// * `addr` - Address in any format.
+    /// # Returns
+    /// * RPC result.
+    pub fn new(converter: &Converter, addr: &str) -> ValidateAddress {
+        let mut parsed = ParsedAddress::default();
+        match converter.parse_into(addr, &mut parsed) {
+            Ok(()) => ValidateAddress::from_parsed(converter, &parsed),
+            Err(_) => ValidateAddress::invalid(),
+        }
+    }
+
+    /// Build the result for an address already parsed.
+    /// Hashes the node cannot pay to (other than 20 bytes, or 32 bytes for P2SH) are reported invalid.
+    /// # Arguments
+    /// * `converter` - Converter providing the prefix configuration.
+    /// * `parsed` - Parsed address.
+    /// # Returns
+    /// * RPC result.
+    pub fn from_parsed(converter: &Converter, parsed: &ParsedAddress) -> ValidateAddress {
+        let script = match script_pub_key(parsed.addr_type, &parsed.hash) {
+            Some(script) => script,
+            None         => return ValidateAddress::invalid(),
+        };
+        let address = match converter.encode(&AddressFormat::CashAddr, parsed.network, parsed.addr_type, &parsed.hash) {
+            Ok(address) => address,
+            Err(_)      => return ValidateAddress::invalid(),
+        };
+
+        ValidateAddress {
+            isvalid: true,
+            address: Some(address),
+            script_pub_key: Some(hash::to_hex(&script)),
+            isscript: Some(parsed.addr_type == AddressType::P2SH),
+        }
+    }
+
+    /// Result for an invalid address, `{"isvalid": false}`.
+    pub fn invalid() -> ValidateAddress {
+        ValidateAddress {
+            isvalid: false,
+            address: None,
+            script_pub_key: None,
+            isscript: None,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "cashaddr"))]
+mod tests {
+    use super::*;
+    use crate::Network;
+
+    #[test]
+    fn validate_address_test() {
+        let converter = Converter::new();
+
+        let result = ValidateAddress::new(&converter, "bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu");
+        assert_eq!(result, ValidateAddress {
+            isvalid: true,
+            address: Some("bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu".to_string()),
+            script_pub_key: Some("a91476a04053bda0a88bda5177b86a15c3b29f55987387".to_string()),
+            isscript: Some(true),
+        });
+
+        let p2sh32 = converter.encode(&AddressFormat::CashAddr, Network::Mainnet, AddressType::P2SH, &[0xab; 32]).unwrap();
+        let result = ValidateAddress::new(&converter, &p2sh32);
+        assert_eq!(result.script_pub_key.unwrap(), format!("aa20{}87", "ab".repeat(32)));
+
+        let p2pkh32 = converter.encode(&AddressFormat::CashAddr, Network::Mainnet, AddressType::P2PKH, &[0xab; 32]).unwrap();
+        assert_eq!(ValidateAddress::new(&converter, &p2pkh32), ValidateAddress::invalid());
+    }
+
+    #[cfg(all(feature = "serde", feature = "legacy"))]
+    #[test]
+    fn validate_address_json_test() {
+        let result = ValidateAddress::new(&Converter::new(), "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR");
+        assert_eq!(serde_json::to_value(&result).unwrap(), serde_json::json!({
+            "isvalid": true,
+            "address": "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk",
+            "scriptPubKey": "76a9146f4b705e3e0407bf3159e9c4050df1b791d2c3f688ac",
+            "isscript": false,
+        }));
+        assert_eq!(serde_json::to_string(&ValidateAddress::invalid()).unwrap(), r#"{"isvalid":false}"#);
+        assert_eq!(serde_json::from_str::<ValidateAddress>(r#"{"isvalid":false}"#).unwrap(), ValidateAddress::invalid());
+    }
+}
@@ -0,0 +1,121 @@
+use std::fmt;
+
+use super::AddressFormat;
+use super::error::Error;
+
+/// Receives the outcomes of a `Converter`'s calls, e.g. to count them or sample bad inputs.
+/// Registered with `Converter::with_observer`. Both methods do nothing by default.
+/// # Example
+/// ```
+/// # use bch_addr::{Converter, Error, Observer};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// #[derive(Default)]
+/// struct Metrics {
+///     failures: AtomicUsize,
+/// }
+///
+/// impl Observer for Metrics {
+///     fn on_parse_failure(&self, _addr: &str, _err: &Error) {
+///         self.failures.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// let converter = Converter::new().with_observer(Metrics::default());
+/// assert!(converter.to_legacy_addr("bitcoincash:bad").is_err());
+/// ```
+pub trait Observer: Send + Sync {
+    /// Called when `addr` fails to parse in every format, by the methods that need a valid address:
+    /// conversions, `parse*`, `detect_*`, `inspect` and the methods built on them.
+    /// The single-format checks `is_cash_addr` and `is_legacy_addr`, for which a rejection is an expected answer,
+    /// and `verify_checksum` do not call it.
+    /// # Arguments
+    /// * `addr` - Rejected address.
+    /// * `err` - Reason given by the last decoder tried; the caller gets `Error::InvalidAddress`.
+    fn on_parse_failure(&self, addr: &str, err: &Error) {
+        let _ = (addr, err);
+    }
+
+    /// Called when `to_cash_addr*` or `to_legacy_addr` succeeds, on `Converter` and `ConversionCtx`.
+    /// # Arguments
+    /// * `input` - Address given.
+    /// * `output` - Address returned.
+    /// * `format` - Format of `output`.
+    fn on_conversion(&self, input: &str, output: &str, format: &AddressFormat) {
+        let _ = (input, output, format);
+    }
+}
+
+/// Observer registered on a converter, if any.
+#[derive(Default)]
+pub(crate) struct ObserverSlot(Option<Box<dyn Observer>>);
+
+impl ObserverSlot {
+    pub fn new(observer: Box<dyn Observer>) -> ObserverSlot {
+        ObserverSlot(Some(observer))
+    }
+
+    pub fn parse_failure(&self, addr: &str, err: &Error) {
+        if let Some(observer) = &self.0 {
+            observer.on_parse_failure(addr, err);
+        }
+    }
+
+    pub fn conversion(&self, input: &str, output: &str, format: &AddressFormat) {
+        if let Some(observer) = &self.0 {
+            observer.on_conversion(input, output, format);
+        }
+    }
+}
+
+impl fmt::Debug for ObserverSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.0.is_some() { "Some(..)" } else { "None" })
+    }
+}
+
+#[cfg(all(test, feature = "legacy", feature = "cashaddr"))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::{Converter, ConversionCtx};
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl Observer for Arc<Recorder> {
+        fn on_parse_failure(&self, addr: &str, _err: &Error) {
+            self.events.lock().unwrap().push(format!("failure {}", addr));
+        }
+
+        fn on_conversion(&self, input: &str, output: &str, _format: &AddressFormat) {
+            self.events.lock().unwrap().push(format!("{} -> {}", input, output));
+        }
+    }
+
+    #[test]
+    fn observer_test() {
+        let recorder = Arc::new(Recorder::default());
+        let converter = Converter::new().with_observer(recorder.clone());
+        let legacy = "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR";
+        let cash_addr = "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk";
+
+        converter.to_cash_addr(legacy).unwrap();
+        assert!(converter.to_legacy_addr("bitcoincash:bad").is_err());
+        assert!(!converter.is_mainnet_addr("nope"));
+        // single-format checks answer without reporting a failure
+        assert!(!converter.is_cash_addr(legacy));
+        assert!(!converter.is_legacy_addr("nope"));
+        ConversionCtx::new(&converter).to_legacy_addr(cash_addr).unwrap();
+
+        assert_eq!(*recorder.events.lock().unwrap(), [
+            format!("{} -> {}", legacy, cash_addr),
+            "failure bitcoincash:bad".to_string(),
+            "failure nope".to_string(),
+            format!("{} -> {}", cash_addr, legacy),
+        ]);
+    }
+}
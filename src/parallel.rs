@@ -0,0 +1,242 @@
+//! Parallel conversion of address files, the library counterpart of `bch-addr convert --csv`.
+//!
+//! Records are read in chunks, converted on all cores with rayon and written back in input order,
+//! so files of any size are processed in constant memory.
+//! # Example
+//! ```
+//! # use bch_addr::{Converter, AddressFormat};
+//! use bch_addr::parallel::{convert_file, FileOptions};
+//!
+//! let input = "id,address\n1,1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR\n2,nope\n";
+//! let mut output = Vec::new();
+//! let summary = convert_file(&Converter::new(), input.as_bytes(), &mut output, &FileOptions::default()).unwrap();
+//! assert_eq!((summary.total, summary.failed), (2, 1));
+//! assert_eq!(String::from_utf8(output).unwrap(), "id,address,error\n\
+//!     1,bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk,\n\
+//!     2,nope,invalid address: nope\n");
+//! ```
+
+// `failure_derive` expands its impls inside an anonymous const.
+#![allow(non_local_definitions)]
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::iter;
+use std::result;
+
+use failure::Fail;
+use rayon::prelude::*;
+
+use super::{Converter, ParsedAddress, AddressFormat, Network};
+
+/// Alias of `Result` used by `convert_file`.
+pub type Result<T> = result::Result<T, Error>;
+
+/// Errors reading or writing a file. Addresses that fail to convert are reported in the output instead.
+#[derive(Debug, Fail)]
+pub enum Error {
+    /// CSV error, including I/O errors.
+    /// # Arguments
+    /// * Error.
+    #[fail(display = "csv error: {}", 0)]
+    Csv(csv::Error),
+
+    /// The CSV header has no column of the given name.
+    /// # Arguments
+    /// * Column name.
+    #[fail(display = "missing column: {}", 0)]
+    MissingColumn(String),
+}
+
+impl From<csv::Error> for Error {
+    fn from(err: csv::Error) -> Error {
+        Error::Csv(err)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Csv(csv::Error::from(err))
+    }
+}
+
+/// Layout of the input file.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Layout {
+    /// CSV with a header row. The output keeps every column, converts `column` and appends the error column.
+    Csv {
+        /// Name of the column holding the addresses.
+        column: String,
+    },
+    /// One address per line; blank lines are skipped.
+    /// The output is CSV without a header: the converted address and the error column.
+    Lines,
+}
+
+/// Options of `convert_file`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FileOptions {
+    /// Input layout. Defaults to CSV with an `address` column.
+    pub layout: Layout,
+    /// Target format. Defaults to cash_addr.
+    pub format: AddressFormat,
+    /// Target network, `None` (the default) to keep the network of each address.
+    pub network: Option<Network>,
+    /// Header of the appended column holding the error of each failed record, empty for the others.
+    /// Defaults to `error`.
+    pub error_column: String,
+    /// Number of records converted in parallel before they are written. Defaults to 4096.
+    pub chunk_size: usize,
+}
+
+impl Default for FileOptions {
+    fn default() -> Self {
+        FileOptions {
+            layout: Layout::Csv { column: "address".to_string() },
+            format: AddressFormat::CashAddr,
+            network: None,
+            error_column: "error".to_string(),
+            chunk_size: 4096,
+        }
+    }
+}
+
+/// Counts returned by `convert_file`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Summary {
+    /// Number of records read.
+    pub total: usize,
+    /// Number of records whose address failed to convert; they keep their original value.
+    pub failed: usize,
+}
+
+/// Convert the addresses of a file, in parallel.
+/// Converted addresses are lowercase and prefixed, even when already in the target format.
+/// # Arguments
+/// * `converter` - Converter providing the prefix configuration.
+/// * `input` - CSV or line file, see `Layout`.
+/// * `output` - Output, CSV with the error column appended.
+/// * `options` - Options.
+/// # Returns
+/// * Counts of records read and failed.
+pub fn convert_file<R: Read, W: Write>(converter: &Converter, input: R, output: W, options: &FileOptions) -> Result<Summary> {
+    let mut writer = csv::Writer::from_writer(output);
+    let (mut rows, index) = match &options.layout {
+        Layout::Csv { column } => {
+            let mut reader = csv::Reader::from_reader(input);
+            let mut headers = reader.headers()?.clone();
+            let index = headers.iter().position(|header| header == column)
+                .ok_or_else(|| Error::MissingColumn(column.clone()))?;
+            headers.push_field(&options.error_column);
+            writer.write_record(&headers)?;
+            (Rows::Csv(reader.into_records()), index)
+        },
+        Layout::Lines => (Rows::Lines(BufReader::new(input).lines()), 0),
+    };
+
+    let mut summary = Summary::default();
+    let mut chunk = Vec::with_capacity(options.chunk_size.max(1));
+    loop {
+        chunk.clear();
+        while chunk.len() < chunk.capacity() {
+            match rows.next() {
+                Some(record) => chunk.push(record?),
+                None         => break,
+            }
+        }
+        if chunk.is_empty() {
+            break;
+        }
+
+        let converted: Vec<_> = chunk.par_iter()
+            .map_init(ParsedAddress::default, |parsed, record| convert(converter, &record[index], options, parsed))
+            .collect();
+
+        for (record, converted) in chunk.iter().zip(converted) {
+            summary.total += 1;
+            let (value, error) = match &converted {
+                Ok(addr) => (addr.as_str(), String::new()),
+                Err(err) => {
+                    summary.failed += 1;
+                    (&record[index], err.to_string())
+                },
+            };
+            let fields = record.iter().enumerate()
+                .map(|(i, field)| if i == index { value } else { field })
+                .chain(iter::once(error.as_str()));
+            writer.write_record(fields)?;
+        }
+    }
+    writer.flush()?;
+
+    Ok(summary)
+}
+
+fn convert(converter: &Converter, addr: &str, options: &FileOptions, parsed: &mut ParsedAddress) -> super::Result<String> {
+    converter.parse_into(addr, parsed)?;
+    converter.encode(&options.format, options.network.unwrap_or(parsed.network), parsed.addr_type, &parsed.hash)
+}
+
+/// Records of either layout.
+enum Rows<R: Read> {
+    Csv(csv::StringRecordsIntoIter<R>),
+    Lines(io::Lines<BufReader<R>>),
+}
+
+impl<R: Read> Iterator for Rows<R> {
+    type Item = Result<csv::StringRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Rows::Csv(records) => records.next().map(|record| Ok(record?)),
+            Rows::Lines(lines) => lines.find_map(|line| match line {
+                Ok(line) if line.trim().is_empty() => None,
+                Ok(line)                           => Some(Ok(csv::StringRecord::from(vec![line.trim()]))),
+                Err(err)                           => Some(Err(err.into())),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_file_test() {
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+        let input = "id,address,note\n\
+                     1,QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK,\"a, b\"\n\
+                     2,bitcoincash:bad,\n\
+                     3,mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi,\n";
+        let options = FileOptions {
+            format: AddressFormat::Legacy,
+            error_column: "reason".to_string(),
+            chunk_size: 2,
+            ..FileOptions::default()
+        };
+
+        let mut output = Vec::new();
+        let summary = convert_file(&converter, input.as_bytes(), &mut output, &options).unwrap();
+        assert_eq!(summary, Summary { total: 3, failed: 1 });
+        assert_eq!(String::from_utf8(output).unwrap(), "id,address,note,reason\n\
+            1,1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR,\"a, b\",\n\
+            2,bitcoincash:bad,,invalid address: bitcoincash:bad\n\
+            3,mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi,,\n");
+
+        let options = FileOptions {
+            layout: Layout::Lines,
+            format: AddressFormat::Other("SLPAddr".to_string()),
+            network: Some(Network::Mainnet),
+            ..FileOptions::default()
+        };
+        let mut output = Vec::new();
+        let summary = convert_file(&converter, " mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi\n\nnope\n".as_bytes(), &mut output, &options).unwrap();
+        assert_eq!(summary, Summary { total: 2, failed: 1 });
+        assert_eq!(String::from_utf8(output).unwrap(), "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg,\n\
+            nope,invalid address: nope\n");
+
+        let options = FileOptions { layout: Layout::Csv { column: "addr".to_string() }, ..FileOptions::default() };
+        let ret = convert_file(&converter, input.as_bytes(), Vec::new(), &options);
+        assert!(matches!(ret, Err(Error::MissingColumn(_))));
+    }
+}
@@ -0,0 +1,364 @@
+//! Structured reports of batch conversions, e.g. for archiving address-migration runs for compliance.
+//!
+//! A report records the prefixes registered in the converter, the acceptance policy, and for every input
+//! the output or the reason it was rejected, as decided by `batch::normalize_batch`.
+//! For bulk migrations, `write_json_lines` streams one record per address instead.
+//! Enabled by the `report` feature.
+//! # Example
+//! ```
+//! # use bch_addr::Converter;
+//! use bch_addr::batch::Policy;
+//! use bch_addr::report;
+//!
+//! let report = report::generate(&Converter::new(), vec!["1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "nope"], &Policy::default());
+//! assert_eq!((report.totals.accepted, report.totals.rejected), (1, 1));
+//! assert_eq!(report.entries[1].reason, Some("invalid"));
+//!
+//! let mut output = Vec::new();
+//! report::write_json(&mut output, &report).unwrap();
+//! let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+//! assert_eq!(json["entries"][0]["output"], "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+//! assert_eq!(json["registry"]["prefixes"][0]["prefix"], "bitcoincash");
+//! ```
+
+use std::io::Write;
+
+use serde::Serialize;
+
+use super::{Converter, ParsedAddress, AddressFormat, AddressType, Network};
+use super::batch::{self, Policy, RejectReason};
+
+/// Report of a batch conversion, serialized as one JSON object.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Report {
+    /// Version of this crate, e.g. `"0.1.0"`.
+    pub version: &'static str,
+    /// Prefix configuration of the converter.
+    pub registry: Registry,
+    /// Policy the inputs were checked against.
+    pub policy: PolicyRecord,
+    /// One entry per input, in input order.
+    pub entries: Vec<EntryRecord>,
+    /// Counts over the entries.
+    pub totals: Totals,
+}
+
+/// Prefix configuration of a converter.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Registry {
+    /// Registered prefixes, in registration order.
+    pub prefixes: Vec<PrefixRecord>,
+}
+
+/// Registered prefix.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PrefixRecord {
+    /// Prefix, lowercase.
+    pub prefix: String,
+    /// Format name: `"cashaddr"`, or the name of an additional format.
+    pub format: String,
+    /// Network name: `"mainnet"`, `"testnet"` or `"regtest"`.
+    pub network: &'static str,
+}
+
+/// Policy of a report, with the names used in `PrefixRecord`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PolicyRecord {
+    /// Format name of the outputs: `"legacy"`, `"cashaddr"`, or the name of an additional format.
+    pub format: String,
+    /// Network name accepted inputs must be on, `None` for any.
+    pub network: Option<&'static str>,
+    /// Type name accepted inputs must have, `"p2pkh"`, `"p2sh"` or `"other"`, `None` for any.
+    #[serde(rename = "type")]
+    pub addr_type: Option<&'static str>,
+    /// Type field of `addr_type` (0 for P2PKH, 1 for P2SH, 2 to 15 for `"other"`), `None` for any.
+    pub type_bits: Option<u8>,
+    /// Whether legacy inputs are accepted.
+    pub accept_legacy: bool,
+}
+
+/// Decision about one input.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct EntryRecord {
+    /// Position of the input.
+    pub index: usize,
+    /// Input as given.
+    pub input: String,
+    /// Output in the policy's format, `None` if rejected.
+    pub output: Option<String>,
+    /// Why the input was rejected, `None` if accepted: `"invalid"`, `"legacy"`, `"wrong_network"`,
+    /// `"wrong_type"`, `"unencodable"` or `"duplicate"`.
+    pub reason: Option<&'static str>,
+    /// Details of the rejection: the parse error, the network or type found (`"other(n)"` with the type field
+    /// for `AddressType::Other(n)`), or the index of the first input of a duplicate.
+    pub detail: Option<String>,
+}
+
+/// One line of `write_json_lines`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct LineRecord {
+    /// Input as given.
+    pub input: String,
+    /// Output in the requested format, `None` on error.
+    pub output: Option<String>,
+    /// Format name of the input: `"legacy"`, `"cashaddr"`, or the name of an additional format.
+    /// `None` if it could not be parsed.
+    pub format: Option<String>,
+    /// Network name of the input, `None` if it could not be parsed.
+    pub network: Option<&'static str>,
+    /// Parse or conversion error, `None` on success.
+    pub error: Option<String>,
+}
+
+/// Counts over the entries of a report.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Totals {
+    /// Number of inputs.
+    pub total: usize,
+    /// Number of accepted inputs.
+    pub accepted: usize,
+    /// Number of rejected inputs.
+    pub rejected: usize,
+}
+
+/// Convert addresses with `batch::normalize_batch` and record every decision.
+/// # Arguments
+/// * `converter` - Converter providing the prefix configuration.
+/// * `addrs` - Addresses in any format.
+/// * `policy` - Acceptance policy.
+/// # Returns
+/// * Report.
+pub fn generate<I, T>(converter: &Converter, addrs: I, policy: &Policy) -> Report
+    where I: IntoIterator<Item = T>, T: AsRef<str>
+{
+    let inputs: Vec<T> = addrs.into_iter().collect();
+    let normalized = batch::normalize_batch(converter, inputs.iter().map(AsRef::as_ref), policy);
+
+    // accepted outputs are in input order, at the positions no rejection takes
+    let mut accepted = normalized.accepted.into_iter();
+    let mut rejected = normalized.rejected.into_iter().peekable();
+    let mut totals = Totals::default();
+    let entries = inputs.iter().enumerate()
+        .map(|(index, input)| {
+            let input = input.as_ref();
+            totals.total += 1;
+            match rejected.next_if(|rejection| rejection.index == index) {
+                Some(rejection) => {
+                    totals.rejected += 1;
+                    let (reason, detail) = match rejection.reason {
+                        RejectReason::Invalid               => ("invalid", converter.parse(input).err().map(|err| err.to_string())),
+                        RejectReason::Legacy                => ("legacy", None),
+                        RejectReason::WrongNetwork(network) => ("wrong_network", Some(network_name(network).to_string())),
+                        RejectReason::WrongType(addr_type)  => ("wrong_type", Some(type_detail(addr_type))),
+                        RejectReason::Unencodable           => ("unencodable", None),
+                        RejectReason::Duplicate(first)      => ("duplicate", Some(first.to_string())),
+                    };
+                    EntryRecord { index, input: input.to_string(), output: None, reason: Some(reason), detail }
+                },
+                None => {
+                    totals.accepted += 1;
+                    EntryRecord { index, input: input.to_string(), output: accepted.next(), reason: None, detail: None }
+                },
+            }
+        })
+        .collect();
+
+    Report {
+        version: env!("CARGO_PKG_VERSION"),
+        registry: registry(converter),
+        policy: PolicyRecord {
+            format: format_name(&policy.format).to_string(),
+            network: policy.network.map(network_name),
+            addr_type: policy.addr_type.map(type_name),
+            type_bits: policy.addr_type.map(AddressType::type_bits),
+            accept_legacy: policy.accept_legacy,
+        },
+        entries,
+        totals,
+    }
+}
+
+/// Write a report as JSON.
+/// # Arguments
+/// * `writer` - JSON output.
+/// * `report` - Report.
+pub fn write_json<W: Write>(writer: W, report: &Report) -> serde_json::Result<()> {
+    serde_json::to_writer(writer, report)
+}
+
+/// Convert addresses and write one JSON object per line (JSON Lines), as they are converted.
+/// Invalid addresses get a line with the error rather than stopping the batch.
+/// # Arguments
+/// * `writer` - JSON Lines output.
+/// * `converter` - Converter providing the prefix configuration.
+/// * `addrs` - Addresses in any format.
+/// * `format` - Format of the outputs.
+/// # Returns
+/// * Number of lines written.
+/// # Example
+/// ```
+/// # use bch_addr::{Converter, AddressFormat};
+/// use bch_addr::report;
+///
+/// let mut output = Vec::new();
+/// let addrs = vec!["1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "nope"];
+/// report::write_json_lines(&mut output, &Converter::new(), addrs, &AddressFormat::CashAddr).unwrap();
+/// let lines: Vec<serde_json::Value> = output.split(|&c| c == b'\n')
+///     .filter(|line| !line.is_empty())
+///     .map(|line| serde_json::from_slice(line).unwrap())
+///     .collect();
+/// assert_eq!(lines[0]["output"], "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+/// assert_eq!(lines[0]["format"], "legacy");
+/// assert_eq!(lines[1]["error"], "invalid address: nope");
+/// ```
+pub fn write_json_lines<W, I, T>(mut writer: W, converter: &Converter, addrs: I, format: &AddressFormat) -> serde_json::Result<usize>
+    where W: Write, I: IntoIterator<Item = T>, T: AsRef<str>
+{
+    let mut parsed = ParsedAddress::default();
+    let mut lines = 0;
+    for input in addrs {
+        let record = line_record(converter, input.as_ref(), format, &mut parsed);
+        serde_json::to_writer(&mut writer, &record)?;
+        writer.write_all(b"\n").map_err(serde_json::Error::io)?;
+        lines += 1;
+    }
+    Ok(lines)
+}
+
+fn line_record(converter: &Converter, input: &str, format: &AddressFormat, parsed: &mut ParsedAddress) -> LineRecord {
+    let mut record = LineRecord { input: input.to_string(), output: None, format: None, network: None, error: None };
+    if let Err(err) = converter.parse_into(input, parsed) {
+        record.error = Some(err.to_string());
+        return record;
+    }
+
+    record.format = Some(format_name(&parsed.format).to_string());
+    record.network = Some(network_name(parsed.network));
+    match converter.encode(format, parsed.network, parsed.addr_type, &parsed.hash) {
+        Ok(output) => record.output = Some(output),
+        Err(err)   => record.error = Some(err.to_string()),
+    }
+    record
+}
+
+fn registry(converter: &Converter) -> Registry {
+    let prefixes = converter.cash_converter.prefixes()
+        .map(|(prefix, format, network)| PrefixRecord {
+            prefix: prefix.to_string(),
+            format: format_name(format).to_string(),
+            network: network_name(network),
+        })
+        .collect();
+    Registry { prefixes }
+}
+
+fn format_name(format: &AddressFormat) -> &str {
+    match format {
+        AddressFormat::Legacy      => "legacy",
+        AddressFormat::CashAddr    => "cashaddr",
+        AddressFormat::Other(name) => name,
+    }
+}
+
+fn network_name(network: Network) -> &'static str {
+    match network {
+        Network::Mainnet => "mainnet",
+        Network::Testnet => "testnet",
+        Network::Regtest => "regtest",
+    }
+}
+
+fn type_name(addr_type: AddressType) -> &'static str {
+    match addr_type {
+        AddressType::P2PKH    => "p2pkh",
+        AddressType::P2SH     => "p2sh",
+        AddressType::Other(_) => "other",
+    }
+}
+
+fn type_detail(addr_type: AddressType) -> String {
+    match addr_type {
+        AddressType::Other(bits) => format!("{}({})", type_name(addr_type), bits),
+        _                        => type_name(addr_type).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_test() {
+        let converter = Converter::new().add_prefixes(&[("SimpleLedger", Network::Mainnet)], "SLPAddr");
+        let policy = Policy {
+            format: AddressFormat::Other("SLPAddr".to_string()),
+            addr_type: Some(AddressType::P2PKH),
+            ..Policy::default()
+        };
+        let report = generate(&converter, vec![
+            "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR",
+            "bitcoincash:bad",
+            "3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9",
+            "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk",
+            "mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi",
+        ], &policy);
+
+        assert_eq!(report.totals, Totals { total: 5, accepted: 1, rejected: 4 });
+        assert_eq!(report.registry.prefixes.last(), Some(&PrefixRecord {
+            prefix: "simpleledger".to_string(),
+            format: "SLPAddr".to_string(),
+            network: "mainnet",
+        }));
+        assert_eq!(report.policy.addr_type, Some("p2pkh"));
+        assert_eq!(report.policy.type_bits, Some(0));
+        assert_eq!(type_detail(AddressType::Other(2)), "other(2)");
+
+        let decisions: Vec<_> = report.entries.iter()
+            .map(|entry| (entry.index, entry.output.as_deref(), entry.reason, entry.detail.as_deref()))
+            .collect();
+        assert_eq!(decisions, [
+            (0, Some("simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg"), None, None),
+            (1, None, Some("invalid"), Some("invalid address: bitcoincash:bad")),
+            (2, None, Some("wrong_type"), Some("p2sh")),
+            (3, None, Some("duplicate"), Some("0")),
+            (4, None, Some("unencodable"), None),
+        ]);
+
+        let mut output = Vec::new();
+        write_json(&mut output, &report).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(json["policy"]["type"], "p2pkh");
+        assert_eq!(json["policy"]["network"], serde_json::Value::Null);
+        assert_eq!(json["entries"][2]["reason"], "wrong_type");
+        assert_eq!(json["totals"]["rejected"], 4);
+
+        let empty = generate(&converter, Vec::<String>::new(), &Policy::default());
+        assert!(empty.entries.is_empty());
+        assert_eq!(empty.totals, Totals::default());
+    }
+
+    #[test]
+    fn json_lines_test() {
+        let converter = Converter::new().add_prefixes(&[("SimpleLedger", Network::Mainnet)], "SLPAddr");
+        let mut output = Vec::new();
+        let lines = write_json_lines(&mut output, &converter, vec![
+            "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg",
+            "bitcoincash:bad",
+            "mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi",
+        ], &AddressFormat::Other("SLPAddr".to_string())).unwrap();
+        assert_eq!(lines, 3);
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines, [
+            r#"{"input":"simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg","output":"simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg","format":"SLPAddr","network":"mainnet","error":null}"#,
+            r#"{"input":"bitcoincash:bad","output":null,"format":null,"network":null,"error":"invalid address: bitcoincash:bad"}"#,
+            r#"{"input":"mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi","output":null,"format":"legacy","network":"testnet","error":"unknow cash prefix: Other(\"SLPAddr\"), Testnet"}"#,
+        ]);
+        assert!(output.ends_with('\n'));
+
+        let mut empty = Vec::new();
+        assert_eq!(write_json_lines(&mut empty, &converter, Vec::<String>::new(), &AddressFormat::CashAddr).unwrap(), 0);
+        assert!(empty.is_empty());
+    }
+}
@@ -0,0 +1,247 @@
+use std::collections::VecDeque;
+use std::io::{self, Read};
+use std::ops::Range;
+use std::str;
+
+use super::{Converter, ParsedAddress};
+
+/// Address found in text by `Converter::scan_text`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddressMatch<'t> {
+    /// Byte range of the address in the text.
+    pub range: Range<usize>,
+    /// Address as written.
+    pub text: &'t str,
+    /// Parsed address.
+    pub parsed: ParsedAddress,
+}
+
+/// Address found in a stream by `ScanReader`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StreamMatch {
+    /// Byte offset of the address in the stream.
+    pub offset: u64,
+    /// Address as written.
+    pub address: String,
+    /// Parsed address.
+    pub parsed: ParsedAddress,
+}
+
+fn is_candidate_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b':'
+}
+
+/// Call `f` with the start, text and parsed form of each address in `bytes`.
+/// Candidates are runs of alphanumerics and `:`; when a run is not an address as a whole,
+/// the part after its last `:` is tried so that `addr:1B9U...` still yields the address.
+/// With `headless`, the first run is the tail of a longer one and only its part after a `:` is tried.
+fn scan_bytes<F>(converter: &Converter, bytes: &[u8], mut headless: bool, mut f: F)
+    where F: FnMut(usize, &str, &ParsedAddress)
+{
+    let mut parsed = ParsedAddress::default();
+
+    let mut start = None;
+    for (pos, &b) in bytes.iter().chain(Some(&b' ')).enumerate() {
+        if is_candidate_byte(b) {
+            start.get_or_insert(pos);
+            continue;
+        }
+
+        if let Some(start) = start.take() {
+            let token = str::from_utf8(&bytes[start..pos]).expect("candidates are ASCII");
+            let offset = if !headless && converter.parse_into(token, &mut parsed).is_ok() {
+                Some(0)
+            } else {
+                token.rfind(':')
+                    .map(|sep| sep + 1)
+                    .filter(|&offset| converter.parse_into(&token[offset..], &mut parsed).is_ok())
+            };
+
+            if let Some(offset) = offset {
+                f(start + offset, &token[offset..], &parsed);
+            }
+        }
+        headless = false;
+    }
+}
+
+pub(crate) fn scan_text<'t>(converter: &Converter, text: &'t str) -> Vec<AddressMatch<'t>> {
+    let mut found = Vec::new();
+    scan_bytes(converter, text.as_bytes(), false, |start, addr, parsed| {
+        let range = start..start + addr.len();
+        found.push(AddressMatch {
+            text: &text[range.clone()],
+            range,
+            parsed: parsed.clone(),
+        });
+    });
+    found
+}
+
+const CHUNK_LEN: usize = 8192;
+
+/// Candidate runs longer than this are cut back to their last `:`; no address is this long.
+const MAX_RUN_LEN: usize = 1024;
+
+/// Iterator over the addresses in a stream, returned by `Converter::scan_reader`.
+///
+/// Reads in chunks and only keeps the unfinished candidate at the end of a chunk,
+/// so memory use does not depend on the stream length. Finds the same addresses as `Converter::scan_text`.
+#[derive(Debug)]
+pub struct ScanReader<'c, R> {
+    converter: &'c Converter,
+    reader: R,
+    /// Bytes not scanned yet, starting at stream offset `offset`.
+    pending: Vec<u8>,
+    offset: u64,
+    /// `pending` starts in the middle of a candidate run that was too long to keep.
+    headless: bool,
+    found: VecDeque<StreamMatch>,
+    eof: bool,
+}
+
+impl<'c, R: Read> ScanReader<'c, R> {
+    pub(crate) fn new(converter: &'c Converter, reader: R) -> ScanReader<'c, R> {
+        ScanReader {
+            converter,
+            reader,
+            pending: Vec::new(),
+            offset: 0,
+            headless: false,
+            found: VecDeque::new(),
+            eof: false,
+        }
+    }
+
+    /// Read one chunk and scan everything up to the last byte that ends a candidate.
+    fn fill(&mut self) -> io::Result<()> {
+        let len = self.pending.len();
+        self.pending.resize(len + CHUNK_LEN, 0);
+        let read = match self.reader.read(&mut self.pending[len..]) {
+            Ok(read) => read,
+            Err(err) => {
+                self.pending.truncate(len);
+                return if err.kind() == io::ErrorKind::Interrupted { Ok(()) } else { Err(err) };
+            },
+        };
+        self.pending.truncate(len + read);
+
+        let end = if read == 0 {
+            self.eof = true;
+            self.pending.len()
+        } else {
+            self.pending.iter().rposition(|&b| !is_candidate_byte(b)).map_or(0, |pos| pos + 1)
+        };
+        self.scan(end);
+
+        if self.pending.len() > MAX_RUN_LEN {
+            let cut = self.pending.iter().rposition(|&b| b == b':').unwrap_or(self.pending.len());
+            self.pending.drain(..cut);
+            self.offset += cut as u64;
+            self.headless = true;
+        }
+        Ok(())
+    }
+
+    fn scan(&mut self, end: usize) {
+        if end == 0 {
+            return;
+        }
+
+        let (offset, found) = (self.offset, &mut self.found);
+        scan_bytes(self.converter, &self.pending[..end], self.headless, |start, addr, parsed| {
+            found.push_back(StreamMatch {
+                offset: offset + start as u64,
+                address: addr.to_string(),
+                parsed: parsed.clone(),
+            });
+        });
+
+        self.pending.drain(..end);
+        self.offset += end as u64;
+        self.headless = false;
+    }
+}
+
+impl<R: Read> Iterator for ScanReader<'_, R> {
+    type Item = io::Result<StreamMatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(found) = self.found.pop_front() {
+                return Some(Ok(found));
+            }
+            if self.eof {
+                return None;
+            }
+            if let Err(err) = self.fill() {
+                return Some(Err(err));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    use crate::{AddressFormat, Network};
+
+    const TEXT: &str = "send to 1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR, not 1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzS\n\
+                        uri: bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk?amount=1\n\
+                        “ünïcode” addr:3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9";
+
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    #[test]
+    fn scan_text_test() {
+        let converter = Converter::new();
+
+        let found = scan_text(&converter, TEXT);
+        let texts: Vec<_> = found.iter().map(|m| m.text).collect();
+        assert_eq!(texts, [
+            "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR",
+            "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk",
+            "3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9",
+        ]);
+        for m in &found {
+            assert_eq!(&TEXT[m.range.clone()], m.text);
+            assert_eq!(m.parsed.network, Network::Mainnet);
+        }
+        assert_eq!(found[0].range, 8..42);
+        assert_eq!(found[1].parsed.format, AddressFormat::CashAddr);
+
+        assert!(scan_text(&converter, "").is_empty());
+    }
+
+    /// Reader returning at most `.1` bytes per call.
+    struct Trickle<'a>(&'a [u8], usize);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = self.0.len().min(self.1).min(buf.len());
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn scan_reader_test() {
+        let converter = Converter::new();
+        let expected: Vec<_> = scan_text(&converter, TEXT).into_iter()
+            .map(|m| StreamMatch { offset: m.range.start as u64, address: m.text.to_string(), parsed: m.parsed })
+            .collect();
+
+        for &chunk in &[1, 2, 3, 7, 41, 4096] {
+            let found: Vec<_> = ScanReader::new(&converter, Trickle(TEXT.as_bytes(), chunk)).collect::<io::Result<_>>().unwrap();
+            assert_eq!(found, expected, "chunk {}", chunk);
+        }
+
+        let long = "a".repeat(3 * CHUNK_LEN);
+        for text in &[format!("{}1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", long), format!("{}:1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR x", long)] {
+            let found: Vec<_> = ScanReader::new(&converter, text.as_bytes()).map(|m| m.unwrap().offset).collect();
+            let expected: Vec<_> = scan_text(&converter, text).into_iter().map(|m| m.range.start as u64).collect();
+            assert_eq!(found, expected);
+        }
+    }
+}
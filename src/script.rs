@@ -0,0 +1,202 @@
+//! Output scripts (scriptPubKey): building them for addresses and classifying them.
+
+use super::AddressType;
+
+const OP_1: u8 = 0x51;
+const OP_16: u8 = 0x60;
+const OP_RETURN: u8 = 0x6a;
+const OP_DUP: u8 = 0x76;
+const OP_EQUAL: u8 = 0x87;
+const OP_EQUALVERIFY: u8 = 0x88;
+const OP_HASH160: u8 = 0xa9;
+const OP_HASH256: u8 = 0xaa;
+const OP_CHECKSIG: u8 = 0xac;
+const OP_CHECKMULTISIG: u8 = 0xae;
+
+/// Standard template of an output script, with the keys or hashes it pays to.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ScriptClass {
+    /// Pay to public key hash.
+    /// # Arguments
+    /// * 20-byte hash.
+    P2PKH(Vec<u8>),
+    /// Pay to script hash.
+    /// # Arguments
+    /// * 20-byte hash, or 32 bytes for P2SH32.
+    P2SH(Vec<u8>),
+    /// Pay to a public key, without an address form.
+    /// # Arguments
+    /// * Public key, compressed (33 bytes) or not (65 bytes).
+    P2PK(Vec<u8>),
+    /// Bare multisig, without an address form.
+    Multisig {
+        /// Number of signatures required.
+        required: u8,
+        /// Public keys.
+        pubkeys: Vec<Vec<u8>>,
+    },
+    /// Unspendable `OP_RETURN` output.
+    /// # Arguments
+    /// * Script following `OP_RETURN`.
+    NullData(Vec<u8>),
+    /// Any other script.
+    Unknown,
+}
+
+impl ScriptClass {
+    /// Classify an output script.
+    /// # Arguments
+    /// * `script` - Serialized output script.
+    /// # Returns
+    /// * Template of the script.
+    /// # Example
+    /// ```
+    /// use bch_addr::script::ScriptClass;
+    ///
+    /// let script = [&[0xa9, 0x14][..], &[0xab; 20], &[0x87]].concat();
+    /// assert_eq!(ScriptClass::classify(&script), ScriptClass::P2SH(vec![0xab; 20]));
+    /// assert_eq!(ScriptClass::classify(&[0x6a, 0x01, 0x2a]), ScriptClass::NullData(vec![0x01, 0x2a]));
+    /// assert_eq!(ScriptClass::classify(&[0x51]), ScriptClass::Unknown);
+    /// ```
+    pub fn classify(script: &[u8]) -> ScriptClass {
+        match address_hash(script) {
+            Some((AddressType::P2PKH, hash))        => return ScriptClass::P2PKH(hash.to_vec()),
+            Some((AddressType::P2SH, hash))         => return ScriptClass::P2SH(hash.to_vec()),
+            Some((AddressType::Other(_), _)) | None => {},
+        }
+
+        match script {
+            [OP_RETURN, data @ ..] => ScriptClass::NullData(data.to_vec()),
+            [len, pubkey @ .., OP_CHECKSIG] if usize::from(*len) == pubkey.len() && is_pubkey(pubkey) => {
+                ScriptClass::P2PK(pubkey.to_vec())
+            },
+            [m @ OP_1..=OP_16, keys @ .., n @ OP_1..=OP_16, OP_CHECKMULTISIG] => {
+                multisig(*m - OP_1 + 1, keys, *n - OP_1 + 1).unwrap_or(ScriptClass::Unknown)
+            },
+            _ => ScriptClass::Unknown,
+        }
+    }
+
+    /// Type and hash of the address paying to this script, if it has one.
+    /// # Returns
+    /// * Address type and hash, `None` for the templates without an address form.
+    pub fn address(&self) -> Option<(AddressType, &[u8])> {
+        match self {
+            ScriptClass::P2PKH(hash) => Some((AddressType::P2PKH, hash)),
+            ScriptClass::P2SH(hash)  => Some((AddressType::P2SH, hash)),
+            _                        => None,
+        }
+    }
+}
+
+/// Type and hash of the address paying to `script`, without copying the hash.
+pub(crate) fn address_hash(script: &[u8]) -> Option<(AddressType, &[u8])> {
+    match script {
+        [OP_DUP, OP_HASH160, 20, hash @ .., OP_EQUALVERIFY, OP_CHECKSIG] if hash.len() == 20 => Some((AddressType::P2PKH, hash)),
+        [OP_HASH160, 20, hash @ .., OP_EQUAL] if hash.len() == 20 => Some((AddressType::P2SH, hash)),
+        [OP_HASH256, 32, hash @ .., OP_EQUAL] if hash.len() == 32 => Some((AddressType::P2SH, hash)),
+        _ => None,
+    }
+}
+
+/// Build the standard output script paying to an address: P2PKH, P2SH or P2SH32.
+/// # Arguments
+/// * `addr_type` - Address type.
+/// * `hash` - hashed publickey or script.
+/// # Returns
+/// * Serialized script, `None` for hashes no standard script pays to
+///   (other than 20 bytes, or 32 bytes for P2SH).
+/// # Example
+/// ```
+/// use bch_addr::AddressType;
+/// use bch_addr::script::{script_pub_key, ScriptClass};
+///
+/// let script = script_pub_key(AddressType::P2PKH, &[0xab; 20]).unwrap();
+/// assert_eq!(ScriptClass::classify(&script), ScriptClass::P2PKH(vec![0xab; 20]));
+/// assert_eq!(script_pub_key(AddressType::P2PKH, &[0xab; 32]), None);
+/// ```
+pub fn script_pub_key(addr_type: AddressType, hash: &[u8]) -> Option<Vec<u8>> {
+    let (head, tail) = template(addr_type, hash.len())?;
+    Some([head, hash, tail].concat())
+}
+
+/// Length of `script_pub_key(addr_type, hash)` for a hash of `hash_len` bytes.
+pub(crate) fn script_pub_key_len(addr_type: AddressType, hash_len: usize) -> Option<usize> {
+    let (head, tail) = template(addr_type, hash_len)?;
+    Some(head.len() + hash_len + tail.len())
+}
+
+/// Opcodes before and after the hash in the standard script paying to an address.
+fn template(addr_type: AddressType, hash_len: usize) -> Option<(&'static [u8], &'static [u8])> {
+    match (addr_type, hash_len) {
+        (AddressType::P2PKH, 20) => Some((&[OP_DUP, OP_HASH160, 20], &[OP_EQUALVERIFY, OP_CHECKSIG])),
+        (AddressType::P2SH, 20)  => Some((&[OP_HASH160, 20], &[OP_EQUAL])),
+        (AddressType::P2SH, 32)  => Some((&[OP_HASH256, 32], &[OP_EQUAL])),
+        _                        => None,
+    }
+}
+
+/// `true` if `key` has the length its header byte calls for.
+fn is_pubkey(key: &[u8]) -> bool {
+    match key.first() {
+        Some(0x02) | Some(0x03)              => key.len() == 33,
+        Some(0x04) | Some(0x06) | Some(0x07) => key.len() == 65,
+        _                                    => false,
+    }
+}
+
+/// `m`-of-`n` multisig of the public keys pushed in `keys`.
+fn multisig(m: u8, mut keys: &[u8], n: u8) -> Option<ScriptClass> {
+    let mut pubkeys = Vec::new();
+    while let [len, rest @ ..] = keys {
+        let len = usize::from(*len);
+        if rest.len() < len || !is_pubkey(&rest[..len]) {
+            return None;
+        }
+        pubkeys.push(rest[..len].to_vec());
+        keys = &rest[len..];
+    }
+
+    if m > n || pubkeys.len() != usize::from(n) {
+        return None;
+    }
+    Some(ScriptClass::Multisig { required: m, pubkeys })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_test() {
+        let compressed = [&[0x02][..], &[0x11; 32]].concat();
+        let uncompressed = [&[0x04][..], &[0x22; 64]].concat();
+
+        let p2pkh = script_pub_key(AddressType::P2PKH, &[0xab; 20]).unwrap();
+        assert_eq!(ScriptClass::classify(&p2pkh).address(), Some((AddressType::P2PKH, &[0xab; 20][..])));
+        let p2sh32 = script_pub_key(AddressType::P2SH, &[0xab; 32]).unwrap();
+        assert_eq!(ScriptClass::classify(&p2sh32), ScriptClass::P2SH(vec![0xab; 32]));
+        assert_eq!(ScriptClass::classify(&p2pkh[..p2pkh.len() - 1]), ScriptClass::Unknown);
+
+        let p2pk = [&[33][..], &compressed, &[OP_CHECKSIG]].concat();
+        assert_eq!(ScriptClass::classify(&p2pk), ScriptClass::P2PK(compressed.clone()));
+        let p2pk = [&[65][..], &uncompressed, &[OP_CHECKSIG]].concat();
+        assert_eq!(ScriptClass::classify(&p2pk).address(), None);
+        assert_eq!(ScriptClass::classify(&[&[33][..], &uncompressed[..33], &[OP_CHECKSIG]].concat()), ScriptClass::Unknown);
+
+        let multisig = [&[OP_1 + 1, 33][..], &compressed, &[65], &uncompressed, &[33], &compressed, &[OP_1 + 2, OP_CHECKMULTISIG]].concat();
+        assert_eq!(ScriptClass::classify(&multisig), ScriptClass::Multisig {
+            required: 2,
+            pubkeys: vec![compressed.clone(), uncompressed.clone(), compressed.clone()],
+        });
+        let wrong_n = [&[OP_1, 33][..], &compressed, &[OP_1 + 1, OP_CHECKMULTISIG]].concat();
+        assert_eq!(ScriptClass::classify(&wrong_n), ScriptClass::Unknown);
+        let m_above_n = [&[OP_1 + 1, 33][..], &compressed, &[OP_1, OP_CHECKMULTISIG]].concat();
+        assert_eq!(ScriptClass::classify(&m_above_n), ScriptClass::Unknown);
+        let truncated = [&[OP_1, 33][..], &compressed[..20], &[OP_1, OP_CHECKMULTISIG]].concat();
+        assert_eq!(ScriptClass::classify(&truncated), ScriptClass::Unknown);
+
+        assert_eq!(ScriptClass::classify(&[OP_RETURN]), ScriptClass::NullData(vec![]));
+        assert_eq!(ScriptClass::classify(&[]), ScriptClass::Unknown);
+    }
+}
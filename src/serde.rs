@@ -0,0 +1,242 @@
+//! Helpers for `#[serde(with = "...")]` on address fields, choosing the serialized format per field.
+//!
+//! Every module accepts an address in any built-in format when deserializing and rejects invalid ones.
+//! They differ in what the field holds and what is written back:
+//! * `as_cash_addr` - lowercase cash_addr with prefix.
+//! * `as_legacy` - legacy.
+//! * `any_format` - the address as given, only validated.
+//!
+//! `strict` instead only deserializes addresses already in canonical form, lowercase cash_addr with prefix,
+//! for APIs that have completed their migration away from legacy addresses.
+//!
+//! Fields can be `String` or anything else `From<String> + AsRef<str>`.
+//! Enabled by the `serde` feature.
+//! # Example
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Payout {
+//!     #[serde(with = "bch_addr::serde::as_cash_addr")]
+//!     to: String,
+//!     #[serde(with = "bch_addr::serde::as_legacy")]
+//!     refund: String,
+//! }
+//!
+//! let payout: Payout = serde_json::from_str(r#"{
+//!     "to": "QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK",
+//!     "refund": "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk"
+//! }"#).unwrap();
+//! assert_eq!(payout.to, "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+//! assert_eq!(payout.refund, "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR");
+//!
+//! assert!(serde_json::from_str::<Payout>(r#"{"to": "bitcoincash:bad", "refund": ""}"#).is_err());
+//! ```
+
+use std::fmt::Display;
+use std::result;
+
+use ::serde::{de, ser, Deserialize, Deserializer, Serializer};
+
+use super::{Converter, AddressFormat, DbAddress};
+use super::error::Result;
+
+fn serialize_with<T, S, F, E>(addr: &T, serializer: S, convert: F) -> result::Result<S::Ok, S::Error>
+    where T: AsRef<str> + ?Sized, S: Serializer, F: FnOnce(&str) -> result::Result<String, E>, E: Display
+{
+    let addr = convert(addr.as_ref()).map_err(ser::Error::custom)?;
+    serializer.serialize_str(&addr)
+}
+
+fn deserialize_with<'de, T, D, F, E>(deserializer: D, convert: F) -> result::Result<T, D::Error>
+    where T: From<String>, D: Deserializer<'de>, F: FnOnce(&str) -> result::Result<String, E>, E: Display
+{
+    let addr = String::deserialize(deserializer)?;
+    convert(&addr).map(T::from).map_err(de::Error::custom)
+}
+
+fn cash_addr(addr: &str) -> Result<String> {
+    DbAddress::new(addr).map(DbAddress::into_string)
+}
+
+fn legacy(addr: &str) -> Result<String> {
+    Converter::new().to_legacy_addr(addr)
+}
+
+fn validated(addr: &str) -> Result<String> {
+    Converter::new().parse(addr).map(|_| addr.to_string())
+}
+
+/// `addr` if it is already a lowercase cash_addr address with prefix, otherwise the reason it is not.
+fn canonical(addr: &str) -> result::Result<String, String> {
+    let (format, ..) = Converter::new().parse(addr).map_err(|err| err.to_string())?;
+    if format == AddressFormat::Legacy {
+        Err(format!("legacy address {} is not accepted, use cash_addr format", addr))
+    } else if !addr.contains(':') {
+        Err(format!("cash_addr address {} has no prefix", addr))
+    } else if addr.bytes().any(|c| c.is_ascii_uppercase()) {
+        Err(format!("cash_addr address {} is not lowercase", addr))
+    } else {
+        Ok(addr.to_string())
+    }
+}
+
+/// Normalize to a lowercase cash_addr address with prefix, on both deserialization and serialization.
+pub mod as_cash_addr {
+    use std::result;
+
+    use ::serde::{Deserializer, Serializer};
+
+    /// Serialize `addr` as a lowercase cash_addr address with prefix.
+    pub fn serialize<T, S>(addr: &T, serializer: S) -> result::Result<S::Ok, S::Error>
+        where T: AsRef<str> + ?Sized, S: Serializer
+    {
+        super::serialize_with(addr, serializer, super::cash_addr)
+    }
+
+    /// Deserialize an address in any format as a lowercase cash_addr address with prefix.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> result::Result<T, D::Error>
+        where T: From<String>, D: Deserializer<'de>
+    {
+        super::deserialize_with(deserializer, super::cash_addr)
+    }
+}
+
+/// Normalize to legacy format, on both deserialization and serialization.
+pub mod as_legacy {
+    use std::result;
+
+    use ::serde::{Deserializer, Serializer};
+
+    /// Serialize `addr` as a legacy address.
+    pub fn serialize<T, S>(addr: &T, serializer: S) -> result::Result<S::Ok, S::Error>
+        where T: AsRef<str> + ?Sized, S: Serializer
+    {
+        super::serialize_with(addr, serializer, super::legacy)
+    }
+
+    /// Deserialize an address in any format as a legacy address.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> result::Result<T, D::Error>
+        where T: From<String>, D: Deserializer<'de>
+    {
+        super::deserialize_with(deserializer, super::legacy)
+    }
+}
+
+/// Keep the address as given, rejecting invalid ones.
+pub mod any_format {
+    use std::result;
+
+    use ::serde::{Deserializer, Serializer};
+
+    /// Serialize `addr` unchanged, failing if it is not a valid address.
+    pub fn serialize<T, S>(addr: &T, serializer: S) -> result::Result<S::Ok, S::Error>
+        where T: AsRef<str> + ?Sized, S: Serializer
+    {
+        super::serialize_with(addr, serializer, super::validated)
+    }
+
+    /// Deserialize a valid address in any format, unchanged.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> result::Result<T, D::Error>
+        where T: From<String>, D: Deserializer<'de>
+    {
+        super::deserialize_with(deserializer, super::validated)
+    }
+}
+
+/// Only accept lowercase cash_addr addresses with prefix; serialize like `as_cash_addr`.
+/// # Example
+/// ```
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Payout {
+///     #[serde(with = "bch_addr::serde::strict")]
+///     to: String,
+/// }
+///
+/// assert!(serde_json::from_str::<Payout>(r#"{"to": "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk"}"#).is_ok());
+///
+/// let err = serde_json::from_str::<Payout>(r#"{"to": "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR"}"#).unwrap_err();
+/// assert!(err.to_string().starts_with("legacy address 1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR is not accepted"));
+/// ```
+pub mod strict {
+    use std::result;
+
+    use ::serde::{Deserializer, Serializer};
+
+    /// Serialize `addr` as a lowercase cash_addr address with prefix.
+    pub fn serialize<T, S>(addr: &T, serializer: S) -> result::Result<S::Ok, S::Error>
+        where T: AsRef<str> + ?Sized, S: Serializer
+    {
+        super::serialize_with(addr, serializer, super::cash_addr)
+    }
+
+    /// Deserialize a lowercase cash_addr address with prefix, rejecting legacy, prefix-less and upper case addresses.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> result::Result<T, D::Error>
+        where T: From<String>, D: Deserializer<'de>
+    {
+        super::deserialize_with(deserializer, super::canonical)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Record {
+        #[serde(with = "crate::serde::as_cash_addr")]
+        cash: String,
+        #[serde(with = "crate::serde::as_legacy")]
+        legacy: String,
+        #[serde(with = "crate::serde::any_format")]
+        any: String,
+    }
+
+    #[test]
+    fn serde_test() {
+        let json = r#"{"cash":"1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR","legacy":"qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk","any":"QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK"}"#;
+        let record: Record = serde_json::from_str(json).unwrap();
+        assert_eq!(record.cash, "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+        assert_eq!(record.legacy, "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR");
+        assert_eq!(record.any, "QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK");
+
+        let record = Record {
+            cash: "bchtest:PPM2QSZNHKS23Z7629MMS6S4CWEF74VCWVHANQGJXU".to_string(),
+            legacy: "bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu".to_string(),
+            any: "2N44ThNe8NXHyv4bsX8AoVCXquBRW94Ls7W".to_string(),
+        };
+        assert_eq!(serde_json::to_string(&record).unwrap(), concat!(
+            r#"{"cash":"bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu","#,
+            r#""legacy":"2N44ThNe8NXHyv4bsX8AoVCXquBRW94Ls7W","#,
+            r#""any":"2N44ThNe8NXHyv4bsX8AoVCXquBRW94Ls7W"}"#,
+        ));
+
+        let bad = json.replace("QPH5", "QPH6");
+        assert!(serde_json::from_str::<Record>(&bad).is_err());
+        let record = Record { any: "bitcoincash:bad".to_string(), ..record };
+        assert!(serde_json::to_string(&record).is_err());
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Strict {
+        #[serde(with = "crate::serde::strict")]
+        addr: String,
+    }
+
+    #[test]
+    fn strict_test() {
+        let parse = |addr: &str| serde_json::from_value::<Strict>(serde_json::json!({ "addr": addr })).map_err(|err| err.to_string());
+
+        let addr = "bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu";
+        assert_eq!(parse(addr).unwrap().addr, addr);
+        assert_eq!(parse("2N44ThNe8NXHyv4bsX8AoVCXquBRW94Ls7W").unwrap_err(),
+            "legacy address 2N44ThNe8NXHyv4bsX8AoVCXquBRW94Ls7W is not accepted, use cash_addr format");
+        assert_eq!(parse("ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu").unwrap_err(),
+            "cash_addr address ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu has no prefix");
+        assert_eq!(parse("BCHTEST:PPM2QSZNHKS23Z7629MMS6S4CWEF74VCWVHANQGJXU").unwrap_err(),
+            "cash_addr address BCHTEST:PPM2QSZNHKS23Z7629MMS6S4CWEF74VCWVHANQGJXU is not lowercase");
+        assert_eq!(parse("bchtest:bad").unwrap_err(), "invalid address: bchtest:bad");
+    }
+}
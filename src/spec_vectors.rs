@@ -0,0 +1,343 @@
+//! Test vectors shared with other cash_addr implementations.
+//!
+//! Enabled by the `spec-vectors` feature. Holds the test vectors of the
+//! [cashaddr spec](https://github.com/bitcoincashorg/bitcoincash.org/blob/master/spec/cashaddr.md)
+//! and the address table of [bchaddrjs](https://github.com/bitcoincashjs/bchaddrjs/blob/master/test/bchaddr.js),
+//! so bindings and other implementations can check themselves against the corpus this crate passes.
+//! # Example
+//! ```
+//! use bch_addr::{Converter, spec_vectors};
+//!
+//! let converter = Converter::new();
+//! for vector in spec_vectors::iter().filter(|vector| vector.network.is_some()) {
+//!     let (_, network, addr_type, hash) = converter.parse(vector.address).unwrap();
+//!     assert_eq!(Some(network), vector.network);
+//!     assert_eq!(addr_type, vector.addr_type());
+//!     assert_eq!(hash, vector.hash());
+//! }
+//! ```
+
+use super::{AddressType, Network};
+
+/// Corpus a vector comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Source {
+    /// Test vectors of the cashaddr spec.
+    CashAddrSpec,
+    /// Address table of bchaddrjs.
+    Bchaddrjs,
+}
+
+/// Address and the data it encodes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Vector {
+    /// Corpus the vector comes from.
+    pub source: Source,
+    /// Encoded address. cash_addr addresses include the prefix.
+    pub address: &'static str,
+    /// Network, `None` for the spec's prefixes that belong to no network (`pref`, `prefix`).
+    pub network: Option<Network>,
+    /// Type field of the cash_addr version byte: 0 for P2PKH, 1 for P2SH.
+    /// The spec also has vectors with the unassigned type 15, decoded as `AddressType::Other(15)`.
+    /// Legacy vectors use the same numbering.
+    pub type_bits: u8,
+    /// hashed publickey, as upper case hex.
+    pub hash_hex: &'static str,
+}
+
+impl Vector {
+    /// Address type of the type field.
+    pub fn addr_type(&self) -> AddressType {
+        AddressType::from_type_bits(self.type_bits)
+    }
+
+    /// hashed publickey.
+    pub fn hash(&self) -> Vec<u8> {
+        (0..self.hash_hex.len()).step_by(2)
+            .map(|i| u8::from_str_radix(&self.hash_hex[i..i + 2], 16).expect("vectors hold valid hex"))
+            .collect()
+    }
+
+    /// `true` for legacy addresses.
+    pub fn is_legacy(&self) -> bool {
+        !self.address.contains(':')
+    }
+}
+
+/// All vectors, spec vectors first.
+pub fn iter() -> impl Iterator<Item = &'static Vector> {
+    VECTORS.iter()
+}
+
+const fn spec(address: &'static str, network: Option<Network>, type_bits: u8, hash_hex: &'static str) -> Vector {
+    Vector { source: Source::CashAddrSpec, address, network, type_bits, hash_hex }
+}
+
+const fn bchaddrjs(address: &'static str, network: Network, type_bits: u8, hash_hex: &'static str) -> Vector {
+    Vector { source: Source::Bchaddrjs, address, network: Some(network), type_bits, hash_hex }
+}
+
+static VECTORS: [Vector; 232] = [
+    spec("bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2", Some(Network::Mainnet), 0, "F5BF48B397DAE70BE82B3CCA4793F8EB2B6CDAC9"),
+    spec("bchtest:pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t", Some(Network::Testnet), 1, "F5BF48B397DAE70BE82B3CCA4793F8EB2B6CDAC9"),
+    spec("pref:pr6m7j9njldwwzlg9v7v53unlr4jkmx6ey65nvtks5", None, 1, "F5BF48B397DAE70BE82B3CCA4793F8EB2B6CDAC9"),
+    spec("prefix:0r6m7j9njldwwzlg9v7v53unlr4jkmx6ey3qnjwsrf", None, 15, "F5BF48B397DAE70BE82B3CCA4793F8EB2B6CDAC9"),
+    spec("bitcoincash:q9adhakpwzztepkpwp5z0dq62m6u5v5xtyj7j3h2ws4mr9g0", Some(Network::Mainnet), 0, "7ADBF6C17084BC86C1706827B41A56F5CA32865925E946EA"),
+    spec("bchtest:p9adhakpwzztepkpwp5z0dq62m6u5v5xtyj7j3h2u94tsynr", Some(Network::Testnet), 1, "7ADBF6C17084BC86C1706827B41A56F5CA32865925E946EA"),
+    spec("pref:p9adhakpwzztepkpwp5z0dq62m6u5v5xtyj7j3h2khlwwk5v", None, 1, "7ADBF6C17084BC86C1706827B41A56F5CA32865925E946EA"),
+    spec("prefix:09adhakpwzztepkpwp5z0dq62m6u5v5xtyj7j3h2p29kc2lp", None, 15, "7ADBF6C17084BC86C1706827B41A56F5CA32865925E946EA"),
+    spec("bitcoincash:qgagf7w02x4wnz3mkwnchut2vxphjzccwxgjvvjmlsxqwkcw59jxxuz", Some(Network::Mainnet), 0, "3A84F9CF51AAE98A3BB3A78BF16A6183790B18719126325BFC0C075B"),
+    spec("bchtest:pgagf7w02x4wnz3mkwnchut2vxphjzccwxgjvvjmlsxqwkcvs7md7wt", Some(Network::Testnet), 1, "3A84F9CF51AAE98A3BB3A78BF16A6183790B18719126325BFC0C075B"),
+    spec("pref:pgagf7w02x4wnz3mkwnchut2vxphjzccwxgjvvjmlsxqwkcrsr6gzkn", None, 1, "3A84F9CF51AAE98A3BB3A78BF16A6183790B18719126325BFC0C075B"),
+    spec("prefix:0gagf7w02x4wnz3mkwnchut2vxphjzccwxgjvvjmlsxqwkc5djw8s9g", None, 15, "3A84F9CF51AAE98A3BB3A78BF16A6183790B18719126325BFC0C075B"),
+    spec("bitcoincash:qvch8mmxy0rtfrlarg7ucrxxfzds5pamg73h7370aa87d80gyhqxq5nlegake", Some(Network::Mainnet), 0, "3173EF6623C6B48FFD1A3DCC0CC6489B0A07BB47A37F47CFEF4FE69DE825C060"),
+    spec("bchtest:pvch8mmxy0rtfrlarg7ucrxxfzds5pamg73h7370aa87d80gyhqxq7fqng6m6", Some(Network::Testnet), 1, "3173EF6623C6B48FFD1A3DCC0CC6489B0A07BB47A37F47CFEF4FE69DE825C060"),
+    spec("pref:pvch8mmxy0rtfrlarg7ucrxxfzds5pamg73h7370aa87d80gyhqxq4k9m7qf9", None, 1, "3173EF6623C6B48FFD1A3DCC0CC6489B0A07BB47A37F47CFEF4FE69DE825C060"),
+    spec("prefix:0vch8mmxy0rtfrlarg7ucrxxfzds5pamg73h7370aa87d80gyhqxqsh6jgp6w", None, 15, "3173EF6623C6B48FFD1A3DCC0CC6489B0A07BB47A37F47CFEF4FE69DE825C060"),
+    spec("bitcoincash:qnq8zwpj8cq05n7pytfmskuk9r4gzzel8qtsvwz79zdskftrzxtar994cgutavfklv39gr3uvz", Some(Network::Mainnet), 0, "C07138323E00FA4FC122D3B85B9628EA810B3F381706385E289B0B25631197D194B5C238BEB136FB"),
+    spec("bchtest:pnq8zwpj8cq05n7pytfmskuk9r4gzzel8qtsvwz79zdskftrzxtar994cgutavfklvmgm6ynej", Some(Network::Testnet), 1, "C07138323E00FA4FC122D3B85B9628EA810B3F381706385E289B0B25631197D194B5C238BEB136FB"),
+    spec("pref:pnq8zwpj8cq05n7pytfmskuk9r4gzzel8qtsvwz79zdskftrzxtar994cgutavfklv0vx5z0w3", None, 1, "C07138323E00FA4FC122D3B85B9628EA810B3F381706385E289B0B25631197D194B5C238BEB136FB"),
+    spec("prefix:0nq8zwpj8cq05n7pytfmskuk9r4gzzel8qtsvwz79zdskftrzxtar994cgutavfklvwsvctzqy", None, 15, "C07138323E00FA4FC122D3B85B9628EA810B3F381706385E289B0B25631197D194B5C238BEB136FB"),
+    spec("bitcoincash:qh3krj5607v3qlqh5c3wq3lrw3wnuxw0sp8dv0zugrrt5a3kj6ucysfz8kxwv2k53krr7n933jfsunqex2w82sl", Some(Network::Mainnet), 0, "E361CA9A7F99107C17A622E047E3745D3E19CF804ED63C5C40C6BA763696B98241223D8CE62AD48D863F4CB18C930E4C"),
+    spec("bchtest:ph3krj5607v3qlqh5c3wq3lrw3wnuxw0sp8dv0zugrrt5a3kj6ucysfz8kxwv2k53krr7n933jfsunqnzf7mt6x", Some(Network::Testnet), 1, "E361CA9A7F99107C17A622E047E3745D3E19CF804ED63C5C40C6BA763696B98241223D8CE62AD48D863F4CB18C930E4C"),
+    spec("pref:ph3krj5607v3qlqh5c3wq3lrw3wnuxw0sp8dv0zugrrt5a3kj6ucysfz8kxwv2k53krr7n933jfsunqjntdfcwg", None, 1, "E361CA9A7F99107C17A622E047E3745D3E19CF804ED63C5C40C6BA763696B98241223D8CE62AD48D863F4CB18C930E4C"),
+    spec("prefix:0h3krj5607v3qlqh5c3wq3lrw3wnuxw0sp8dv0zugrrt5a3kj6ucysfz8kxwv2k53krr7n933jfsunqakcssnmn", None, 15, "E361CA9A7F99107C17A622E047E3745D3E19CF804ED63C5C40C6BA763696B98241223D8CE62AD48D863F4CB18C930E4C"),
+    spec("bitcoincash:qmvl5lzvdm6km38lgga64ek5jhdl7e3aqd9895wu04fvhlnare5937w4ywkq57juxsrhvw8ym5d8qx7sz7zz0zvcypqscw8jd03f", Some(Network::Mainnet), 0, "D9FA7C4C6EF56DC4FF423BAAE6D495DBFF663D034A72D1DC7D52CBFE7D1E6858F9D523AC0A7A5C34077638E4DD1A701BD017842789982041"),
+    spec("bchtest:pmvl5lzvdm6km38lgga64ek5jhdl7e3aqd9895wu04fvhlnare5937w4ywkq57juxsrhvw8ym5d8qx7sz7zz0zvcypqs6kgdsg2g", Some(Network::Testnet), 1, "D9FA7C4C6EF56DC4FF423BAAE6D495DBFF663D034A72D1DC7D52CBFE7D1E6858F9D523AC0A7A5C34077638E4DD1A701BD017842789982041"),
+    spec("pref:pmvl5lzvdm6km38lgga64ek5jhdl7e3aqd9895wu04fvhlnare5937w4ywkq57juxsrhvw8ym5d8qx7sz7zz0zvcypqsammyqffl", None, 1, "D9FA7C4C6EF56DC4FF423BAAE6D495DBFF663D034A72D1DC7D52CBFE7D1E6858F9D523AC0A7A5C34077638E4DD1A701BD017842789982041"),
+    spec("prefix:0mvl5lzvdm6km38lgga64ek5jhdl7e3aqd9895wu04fvhlnare5937w4ywkq57juxsrhvw8ym5d8qx7sz7zz0zvcypqsgjrqpnw8", None, 15, "D9FA7C4C6EF56DC4FF423BAAE6D495DBFF663D034A72D1DC7D52CBFE7D1E6858F9D523AC0A7A5C34077638E4DD1A701BD017842789982041"),
+    spec("bitcoincash:qlg0x333p4238k0qrc5ej7rzfw5g8e4a4r6vvzyrcy8j3s5k0en7calvclhw46hudk5flttj6ydvjc0pv3nchp52amk97tqa5zygg96mtky5sv5w", Some(Network::Mainnet), 0, "D0F346310D5513D9E01E299978624BA883E6BDA8F4C60883C10F28C2967E67EC77ECC7EEEAEAFC6DA89FAD72D11AC961E164678B868AEEEC5F2C1DA08884175B"),
+    spec("bchtest:plg0x333p4238k0qrc5ej7rzfw5g8e4a4r6vvzyrcy8j3s5k0en7calvclhw46hudk5flttj6ydvjc0pv3nchp52amk97tqa5zygg96mc773cwez", Some(Network::Testnet), 1, "D0F346310D5513D9E01E299978624BA883E6BDA8F4C60883C10F28C2967E67EC77ECC7EEEAEAFC6DA89FAD72D11AC961E164678B868AEEEC5F2C1DA08884175B"),
+    spec("pref:plg0x333p4238k0qrc5ej7rzfw5g8e4a4r6vvzyrcy8j3s5k0en7calvclhw46hudk5flttj6ydvjc0pv3nchp52amk97tqa5zygg96mg7pj3lh8", None, 1, "D0F346310D5513D9E01E299978624BA883E6BDA8F4C60883C10F28C2967E67EC77ECC7EEEAEAFC6DA89FAD72D11AC961E164678B868AEEEC5F2C1DA08884175B"),
+    spec("prefix:0lg0x333p4238k0qrc5ej7rzfw5g8e4a4r6vvzyrcy8j3s5k0en7calvclhw46hudk5flttj6ydvjc0pv3nchp52amk97tqa5zygg96ms92w6845", None, 15, "D0F346310D5513D9E01E299978624BA883E6BDA8F4C60883C10F28C2967E67EC77ECC7EEEAEAFC6DA89FAD72D11AC961E164678B868AEEEC5F2C1DA08884175B"),
+    bchaddrjs("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", Network::Mainnet, 0, "6F4B705E3E0407BF3159E9C4050DF1B791D2C3F6"),
+    bchaddrjs("185K5yAfcrARrHjNVt4iAUHtkYqcogF4km", Network::Mainnet, 0, "4D99A438497D4419B2D80A1B44DBD5E5B4CB43CD"),
+    bchaddrjs("1EUrmffDt4SQQkGVfmDTyFcp57PuByeadW", Network::Mainnet, 0, "93DE6983014EF04D680D33EFEB014C13E938E7C5"),
+    bchaddrjs("1H6YWsFBxvDx6Ce9dyUFZvjG29npxQpBpR", Network::Mainnet, 0, "B08E665249BED3EBD8B545C6E0BDD10FB4E92B40"),
+    bchaddrjs("15z9kQvBaZmTGRTRbP3K1VBM3BQvRsj4U4", Network::Mainnet, 0, "36AF840D4CA083DAD7759FD8E62820490759BA2F"),
+    bchaddrjs("1P238gziZdeS5Wj9nqLhQHSBK2Lz6zPSke", Network::Mainnet, 0, "F184F6A0DC4C9C0D7DF1D9E6F3D494BAE73C9F9C"),
+    bchaddrjs("13WamBttqMB9AHNovKBCeLFGC5sbN4iZkh", Network::Mainnet, 0, "1B887F583F8B9810C4EE362F234C589D2939629C"),
+    bchaddrjs("17Sa1fdVXh2NVgcn5xoWzTLGNivg9gUDQ7", Network::Mainnet, 0, "46A6B5D938273B9EEA7EF73A8903BF47B23D9C25"),
+    bchaddrjs("1tQ2P2q5cVERY8AkGD4K8RGc6NmZQVTKN", Network::Mainnet, 0, "09B86EEF9DD37816D475EF3939262779B62CDB3A"),
+    bchaddrjs("1FJSGaq7Wip2ADSJboxMXniPhnYM8ym5Ri", Network::Mainnet, 0, "9CDDECF1048DE8F94E32202EF2DD9FA294CCF85D"),
+    bchaddrjs("1GxjvJnjF6t29gDnX4jF3u25u5JRqANYPV", Network::Mainnet, 0, "AF149779A0A909A9C7CF3A1520830EAC3F213AD9"),
+    bchaddrjs("1N7gqB2GtgJG8ap3uwRoKyrcrrSTa4qfXu", Network::Mainnet, 0, "E79E62D8368B8F8473ACE633A9693C09800E11EE"),
+    bchaddrjs("1JG6fXqEiu9H2fktGxqpFfGGLdy6ie7QgY", Network::Mainnet, 0, "BD551B297F90571C1456CCF06B65B85F4BCC5BD5"),
+    bchaddrjs("14ipzRgYAbSZUnmeRNhhrPMQ8XQrzGg4wo", Network::Mainnet, 0, "28D17DED198EC7FFA421397A7781619303BC8DDC"),
+    bchaddrjs("185FScTRCtVXRoy5gSDbuLnnQaQWqCK4A1", Network::Mainnet, 0, "4D96997C47AC17374B4268C33CE897779FFA1F6C"),
+    bchaddrjs("1NPRQpCNaeVvZLYw6Z3Y1XkKxLt9BrFTn5", Network::Mainnet, 0, "EA982B675CB6EA546AE837D6A102E750B5DB9241"),
+    bchaddrjs("1Pa8bRApFwCZ8rkgCJh9mfUmj4XJMUYdom", Network::Mainnet, 0, "F796D526FDEC8E90B333BC29977CCBA965D8929F"),
+    bchaddrjs("13HmTnwyKacGJCt2WseTReCeEAtG5ZAyci", Network::Mainnet, 0, "191C059BE56EF603BAEFC8FE0F38F59CBE5F3529"),
+    bchaddrjs("1Mdob5JY1yuwoj6y76Vf3AQpoqUH5Aft8z", Network::Mainnet, 0, "E258612BCE794FD3D1F8B23C5EFA762B8058D95F"),
+    bchaddrjs("1D8zGeRj3Vkns6VwKxwNoW2mDsxF25w2Zy", Network::Mainnet, 0, "85243A3E63BF03BE982A5DA415802703D6C17A72"),
+    bchaddrjs("3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9", Network::Mainnet, 1, "6F4B705E3E0407BF3159E9C4050DF1B791D2C3F6"),
+    bchaddrjs("38mL1Wf7AkUowTRocyjJb6epu58LSafEYf", Network::Mainnet, 1, "4D99A438497D4419B2D80A1B44DBD5E5B4CB43CD"),
+    bchaddrjs("3FAshD9fRxknVuxvnrt4PsykDdgckmK7xD", Network::Mainnet, 1, "93DE6983014EF04D680D33EFEB014C13E938E7C5"),
+    bchaddrjs("3HnZSQjdWpYLBNLam58qzZ6CAg5YXBddBW", Network::Mainnet, 1, "B08E665249BED3EBD8B545C6E0BDD10FB4E92B40"),
+    bchaddrjs("36gAfxQd8U5qMb9riUhuS7YHBhhdvjr8u1", Network::Mainnet, 1, "36AF840D4CA083DAD7759FD8E62820490759BA2F"),
+    bchaddrjs("3Pi44EVA7XxpAgRauw1Hpuo7TYdhd7WMon", Network::Mainnet, 1, "F184F6A0DC4C9C0D7DF1D9E6F3D494BAE73C9F9C"),
+    bchaddrjs("34CbgjPLPFVXFT5F3Qqo4xcCLcAJwvkM85", Network::Mainnet, 1, "1B887F583F8B9810C4EE362F234C589D2939629C"),
+    bchaddrjs("388awD7w5bLkarKDD4U7R5hCXFDPmHuWW7", Network::Mainnet, 1, "46A6B5D938273B9EEA7EF73A8903BF47B23D9C25"),
+    bchaddrjs("32aQwvXGdWocWhpbsMsejknCkcfVB4ivTM", Network::Mainnet, 1, "09B86EEF9DD37816D475EF3939262779B62CDB3A"),
+    bchaddrjs("3FzTC8KZ4d8QFP8jiucwxR5KrJq4bcevn7", Network::Mainnet, 1, "9CDDECF1048DE8F94E32202EF2DD9FA294CCF85D"),
+    bchaddrjs("3HekqrHAo1CQEqvDeAPqUXP23bb9Sf9WoA", Network::Mainnet, 1, "AF149779A0A909A9C7CF3A1520830EAC3F213AD9"),
+    bchaddrjs("3NohkiWiSaceDkWV336PkcDZ1NjBBWBewT", Network::Mainnet, 1, "E79E62D8368B8F8473ACE633A9693C09800E11EE"),
+    bchaddrjs("3Jx7b5KgGoTf7qTKQ4WQgHdCVAFpCKiqsB", Network::Mainnet, 1, "BD551B297F90571C1456CCF06B65B85F4BCC5BD5"),
+    bchaddrjs("35QquyAyiVkwZxU5YUNJH1iLH3haZ5TEfC", Network::Mainnet, 1, "28D17DED198EC7FFA421397A7781619303BC8DDC"),
+    bchaddrjs("38mGN9wrknouWyfWoXtCKy9iZ6hEMRGsyp", Network::Mainnet, 1, "4D96997C47AC17374B4268C33CE897779FFA1F6C"),
+    bchaddrjs("3P5SLMgp8YpJeWFNDei8SA7G6sArkNKQKL", Network::Mainnet, 1, "EA982B675CB6EA546AE837D6A102E750B5DB9241"),
+    bchaddrjs("3QG9WxfFoqWwE2T7KQMkCHqhsap1waSfDu", Network::Mainnet, 1, "F796D526FDEC8E90B333BC29977CCBA965D8929F"),
+    bchaddrjs("33ynPLSQsUvePNaTdyK3rGZaNhAyfeAmbT", Network::Mainnet, 1, "191C059BE56EF603BAEFC8FE0F38F59CBE5F3529"),
+    bchaddrjs("3NKpWcnyZtEKttoQECAFTnmkxMkzgbT4WX", Network::Mainnet, 1, "E258612BCE794FD3D1F8B23C5EFA762B8058D95F"),
+    bchaddrjs("3Dq1CBvAbQ5AxGCNT4byE8PhNQExZcR6Q2", Network::Mainnet, 1, "85243A3E63BF03BE982A5DA415802703D6C17A72"),
+    bchaddrjs("mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi", Network::Testnet, 0, "6F4B705E3E0407BF3159E9C4050DF1B791D2C3F6"),
+    bchaddrjs("mnbGP2FeRsbgdQCzDT35zPWDcYSKm4wrcg", Network::Testnet, 0, "4D99A438497D4419B2D80A1B44DBD5E5B4CB43CD"),
+    bchaddrjs("mtzp4ikCh5sfBrk7PLBqoAq8w6zc48PsGn", Network::Testnet, 0, "93DE6983014EF04D680D33EFEB014C13E938E7C5"),
+    bchaddrjs("mwcVovLAmwfCsK7mMYSdPqwat9PXqcMiFt", Network::Testnet, 0, "B08E665249BED3EBD8B545C6E0BDD10FB4E92B40"),
+    bchaddrjs("mkW73U1APbCi3Xw3Jx1gqQPfuB1dHFDiEU", Network::Testnet, 0, "36AF840D4CA083DAD7759FD8E62820490759BA2F"),
+    bchaddrjs("n3XzRk5hNf5grdCmWQK5ECeWB1wgzzYzZd", Network::Testnet, 0, "F184F6A0DC4C9C0D7DF1D9E6F3D494BAE73C9F9C"),
+    bchaddrjs("mi2Y4EyseNcPwPrRdt9aUFTb45UJHNgtbL", Network::Testnet, 0, "1B887F583F8B9810C4EE362F234C589D2939629C"),
+    bchaddrjs("mmxXJiiULiTdGo6PoXmtpNYbEiXP2v746S", Network::Testnet, 0, "46A6B5D938273B9EEA7EF73A8903BF47B23D9C25"),
+    bchaddrjs("mgQMKS7otdvVCebnTqBS93dbU5yUZPsANB", Network::Testnet, 0, "09B86EEF9DD37816D475EF3939262779B62CDB3A"),
+    bchaddrjs("mupPZdv6KkFGwKuvKNvjMhviZn93yznq73", Network::Testnet, 0, "9CDDECF1048DE8F94E32202EF2DD9FA294CCF85D"),
+    bchaddrjs("mwUhDMsi48KGvnhQEdhcspEQm4u8o754bx", Network::Testnet, 0, "AF149779A0A909A9C7CF3A1520830EAC3F213AD9"),
+    bchaddrjs("n2de8E7FhhjWuhHfdWQB9u4wir3AXqspCt", Network::Testnet, 0, "E79E62D8368B8F8473ACE633A9693C09800E11EE"),
+    bchaddrjs("mxn3xavDXvaXonEVzXpC5aUbCdZoaTEB2g", Network::Testnet, 0, "BD551B297F90571C1456CCF06B65B85F4BCC5BD5"),
+    bchaddrjs("mjEnHUmWycspFuFG8wg5gJZizX1ZtEF1XN", Network::Testnet, 0, "28D17DED198EC7FFA421397A7781619303BC8DDC"),
+    bchaddrjs("mnbCjfYQ1uvnCvShQ1ByjG17Ga1Dk3RTXN", Network::Testnet, 0, "4D96997C47AC17374B4268C33CE897779FFA1F6C"),
+    bchaddrjs("n2uNhsHMPfwBLT2Yp81uqSxepLUr6zCnCz", Network::Testnet, 0, "EA982B675CB6EA546AE837D6A102E750B5DB9241"),
+    bchaddrjs("n465tUFo4xdouyEHusfXbah6b481K5Nivk", Network::Testnet, 0, "F796D526FDEC8E90B333BC29977CCBA965D8929F"),
+    bchaddrjs("mhoikr2x8c3X5KMeEScqFZQy6AUy4GeR4M", Network::Testnet, 0, "191C059BE56EF603BAEFC8FE0F38F59CBE5F3529"),
+    bchaddrjs("n29kt8PWq1MCaqaapfU2s5d9fq4yytS1xJ", Network::Testnet, 0, "E258612BCE794FD3D1F8B23C5EFA762B8058D95F"),
+    bchaddrjs("msewZhWhrXC3eCyZ3XukdRF65sYwtbmARy", Network::Testnet, 0, "85243A3E63BF03BE982A5DA415802703D6C17A72"),
+    bchaddrjs("2N3PhNAc8v7eRB65UQAcqCLERStuD93JXLD", Network::Testnet, 1, "6F4B705E3E0407BF3159E9C4050DF1B791D2C3F6"),
+    bchaddrjs("2MzKY5Fb8nCzA9F4MJ7MBD3e67RLWFE1ciP", Network::Testnet, 1, "4D99A438497D4419B2D80A1B44DBD5E5B4CB43CD"),
+    bchaddrjs("2N6j5kx5h3RG8hhbUTzVw1py1RytnZNYoXo", Network::Testnet, 1, "93DE6983014EF04D680D33EFEB014C13E938E7C5"),
+    bchaddrjs("2N9LmW9ff8H3gP9y8SCkicW5TP2HiFpeK4z", Network::Testnet, 1, "B08E665249BED3EBD8B545C6E0BDD10FB4E92B40"),
+    bchaddrjs("2MxENjhLejvbBZNnQPcKn44XYQ3uoiBT3fF", Network::Testnet, 1, "36AF840D4CA083DAD7759FD8E62820490759BA2F"),
+    bchaddrjs("2NFGG7yRBizUANU48b4dASrnNftqsNwzSM1", Network::Testnet, 1, "F184F6A0DC4C9C0D7DF1D9E6F3D494BAE73C9F9C"),
+    bchaddrjs("2MukokUKMzhzsTEhniYTfgubTYxNUi6PtTX", Network::Testnet, 1, "1B887F583F8B9810C4EE362F234C589D2939629C"),
+    bchaddrjs("2Mygnzx3xh3r6ndwktC5z32gTjbRZXkJpFr", Network::Testnet, 1, "46A6B5D938273B9EEA7EF73A8903BF47B23D9C25"),
+    bchaddrjs("2Mt8d1fTJEyJxiVT9YVVXMhmTxxsexLdJiE", Network::Testnet, 1, "09B86EEF9DD37816D475EF3939262779B62CDB3A"),
+    bchaddrjs("2N7YfFsFag5dkTAmHQ3EpaN4b4f3EPkwQkk", Network::Testnet, 1, "9CDDECF1048DE8F94E32202EF2DD9FA294CCF85D"),
+    bchaddrjs("2N9CxubDCQThkSdYmKJ1i6UNHFwoKBxp2Hj", Network::Testnet, 1, "AF149779A0A909A9C7CF3A1520830EAC3F213AD9"),
+    bchaddrjs("2NEMupTSk437zRY92iAiGNZCpDiwLvwnZEL", Network::Testnet, 1, "E79E62D8368B8F8473ACE633A9693C09800E11EE"),
+    bchaddrjs("2NAWKepFhtFy1Kd5s5C8HJEcThWTyzKiNGA", Network::Testnet, 1, "BD551B297F90571C1456CCF06B65B85F4BCC5BD5"),
+    bchaddrjs("2Mvy3yi71KxGHmk6dDbzAtxhbVPukK6MD5u", Network::Testnet, 1, "28D17DED198EC7FFA421397A7781619303BC8DDC"),
+    bchaddrjs("2MzKURtstNFKFimJ4UfW4wv8ymSuQCcZPN2", Network::Testnet, 1, "4D96997C47AC17374B4268C33CE897779FFA1F6C"),
+    bchaddrjs("2NEdeQ6cqk1KerHsutnL1476XKDP2agcCh5", Network::Testnet, 1, "EA982B675CB6EA546AE837D6A102E750B5DB9241"),
+    bchaddrjs("2NFpMahbHRJ2HRp5ezXycpEpy5w2BmnVM9W", Network::Testnet, 1, "F796D526FDEC8E90B333BC29977CCBA965D8929F"),
+    bchaddrjs("2MuXzT5NSUwRzbAD1K6vvUDYqb3P9RUvPgK", Network::Testnet, 1, "191C059BE56EF603BAEFC8FE0F38F59CBE5F3529"),
+    bchaddrjs("2NDt2aMj1BLjg6gRwuKn85jm2AhyAV8e2VF", Network::Testnet, 1, "E258612BCE794FD3D1F8B23C5EFA762B8058D95F"),
+    bchaddrjs("2N5PDFvrCCraXA3pv8CDqr5NxakT8KJb3Gg", Network::Testnet, 1, "85243A3E63BF03BE982A5DA415802703D6C17A72"),
+    bchaddrjs("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", Network::Mainnet, 0, "6F4B705E3E0407BF3159E9C4050DF1B791D2C3F6"),
+    bchaddrjs("bitcoincash:qpxenfpcf975gxdjmq9pk3xm6hjmfj6re56t60smsm", Network::Mainnet, 0, "4D99A438497D4419B2D80A1B44DBD5E5B4CB43CD"),
+    bchaddrjs("bitcoincash:qzfau6vrq980qntgp5e7l6cpfsf7jw88c5u7y85qx6", Network::Mainnet, 0, "93DE6983014EF04D680D33EFEB014C13E938E7C5"),
+    bchaddrjs("bitcoincash:qzcguejjfxld867ck4zudc9a6y8mf6ftgqqrxzfmlh", Network::Mainnet, 0, "B08E665249BED3EBD8B545C6E0BDD10FB4E92B40"),
+    bchaddrjs("bitcoincash:qqm2lpqdfjsg8kkhwk0a3e3gypyswkd69urny99j70", Network::Mainnet, 0, "36AF840D4CA083DAD7759FD8E62820490759BA2F"),
+    bchaddrjs("bitcoincash:qrccfa4qm3xfcrta78v7du75jjaww0ylnss5nxsy9s", Network::Mainnet, 0, "F184F6A0DC4C9C0D7DF1D9E6F3D494BAE73C9F9C"),
+    bchaddrjs("bitcoincash:qqdcsl6c879esyxyacmz7g6vtzwjjwtznsv65x6znz", Network::Mainnet, 0, "1B887F583F8B9810C4EE362F234C589D2939629C"),
+    bchaddrjs("bitcoincash:qpr2ddwe8qnnh8h20mmn4zgrharmy0vuy5y4gr8gl2", Network::Mainnet, 0, "46A6B5D938273B9EEA7EF73A8903BF47B23D9C25"),
+    bchaddrjs("bitcoincash:qqymsmh0nhfhs9k5whhnjwfxyaumvtxm8g2z0s4f9y", Network::Mainnet, 0, "09B86EEF9DD37816D475EF3939262779B62CDB3A"),
+    bchaddrjs("bitcoincash:qzwdmm83qjx7372wxgszaukan73ffn8ct54v6hs3dl", Network::Mainnet, 0, "9CDDECF1048DE8F94E32202EF2DD9FA294CCF85D"),
+    bchaddrjs("bitcoincash:qzh3f9me5z5sn2w8euap2gyrp6kr7gf6my5mhjey6s", Network::Mainnet, 0, "AF149779A0A909A9C7CF3A1520830EAC3F213AD9"),
+    bchaddrjs("bitcoincash:qrneuckcx69clprn4nnr82tf8sycqrs3ac4tr8m86f", Network::Mainnet, 0, "E79E62D8368B8F8473ACE633A9693C09800E11EE"),
+    bchaddrjs("bitcoincash:qz742xef07g9w8q52mx0q6m9hp05hnzm657wqd0ce2", Network::Mainnet, 0, "BD551B297F90571C1456CCF06B65B85F4BCC5BD5"),
+    bchaddrjs("bitcoincash:qq5dzl0drx8v0layyyuh5aupvxfs80ydmsp5444280", Network::Mainnet, 0, "28D17DED198EC7FFA421397A7781619303BC8DDC"),
+    bchaddrjs("bitcoincash:qpxedxtug7kpwd6tgf5vx08gjamel7sldsc40mxew8", Network::Mainnet, 0, "4D96997C47AC17374B4268C33CE897779FFA1F6C"),
+    bchaddrjs("bitcoincash:qr4fs2m8tjmw54r2aqmadggzuagttkujgyrjs5d769", Network::Mainnet, 0, "EA982B675CB6EA546AE837D6A102E750B5DB9241"),
+    bchaddrjs("bitcoincash:qrmed4fxlhkgay9nxw7zn9muew5ktkyjnuuawvycze", Network::Mainnet, 0, "F796D526FDEC8E90B333BC29977CCBA965D8929F"),
+    bchaddrjs("bitcoincash:qqv3cpvmu4h0vqa6aly0urec7kwtuhe49yz6e7922v", Network::Mainnet, 0, "191C059BE56EF603BAEFC8FE0F38F59CBE5F3529"),
+    bchaddrjs("bitcoincash:qr39scfteeu5l573lzerchh6wc4cqkxeturafzfkk9", Network::Mainnet, 0, "E258612BCE794FD3D1F8B23C5EFA762B8058D95F"),
+    bchaddrjs("bitcoincash:qzzjgw37vwls805c9fw6g9vqyupadst6wgmane0s4l", Network::Mainnet, 0, "85243A3E63BF03BE982A5DA415802703D6C17A72"),
+    bchaddrjs("bitcoincash:pph5kuz78czq00e3t85ugpgd7xmer5kr7crv8a2z4t", Network::Mainnet, 1, "6F4B705E3E0407BF3159E9C4050DF1B791D2C3F6"),
+    bchaddrjs("bitcoincash:ppxenfpcf975gxdjmq9pk3xm6hjmfj6re5dw8qhctx", Network::Mainnet, 1, "4D99A438497D4419B2D80A1B44DBD5E5B4CB43CD"),
+    bchaddrjs("bitcoincash:pzfau6vrq980qntgp5e7l6cpfsf7jw88c5tmegnra8", Network::Mainnet, 1, "93DE6983014EF04D680D33EFEB014C13E938E7C5"),
+    bchaddrjs("bitcoincash:pzcguejjfxld867ck4zudc9a6y8mf6ftgqhxmdwcy2", Network::Mainnet, 1, "B08E665249BED3EBD8B545C6E0BDD10FB4E92B40"),
+    bchaddrjs("bitcoincash:pqm2lpqdfjsg8kkhwk0a3e3gypyswkd69u5ke2z39j", Network::Mainnet, 1, "36AF840D4CA083DAD7759FD8E62820490759BA2F"),
+    bchaddrjs("bitcoincash:prccfa4qm3xfcrta78v7du75jjaww0ylns83wfh87d", Network::Mainnet, 1, "F184F6A0DC4C9C0D7DF1D9E6F3D494BAE73C9F9C"),
+    bchaddrjs("bitcoincash:pqdcsl6c879esyxyacmz7g6vtzwjjwtznsmlffapgl", Network::Mainnet, 1, "1B887F583F8B9810C4EE362F234C589D2939629C"),
+    bchaddrjs("bitcoincash:ppr2ddwe8qnnh8h20mmn4zgrharmy0vuy5ns4vqtyh", Network::Mainnet, 1, "46A6B5D938273B9EEA7EF73A8903BF47B23D9C25"),
+    bchaddrjs("bitcoincash:pqymsmh0nhfhs9k5whhnjwfxyaumvtxm8ga8jlj27e", Network::Mainnet, 1, "09B86EEF9DD37816D475EF3939262779B62CDB3A"),
+    bchaddrjs("bitcoincash:pzwdmm83qjx7372wxgszaukan73ffn8ct5zf8chjkz", Network::Mainnet, 1, "9CDDECF1048DE8F94E32202EF2DD9FA294CCF85D"),
+    bchaddrjs("bitcoincash:pzh3f9me5z5sn2w8euap2gyrp6kr7gf6myr72a78pd", Network::Mainnet, 1, "AF149779A0A909A9C7CF3A1520830EAC3F213AD9"),
+    bchaddrjs("bitcoincash:prneuckcx69clprn4nnr82tf8sycqrs3aczw7guyp5", Network::Mainnet, 1, "E79E62D8368B8F8473ACE633A9693C09800E11EE"),
+    bchaddrjs("bitcoincash:pz742xef07g9w8q52mx0q6m9hp05hnzm65ftazgmzh", Network::Mainnet, 1, "BD551B297F90571C1456CCF06B65B85F4BCC5BD5"),
+    bchaddrjs("bitcoincash:pq5dzl0drx8v0layyyuh5aupvxfs80ydmsk3g6jfuj", Network::Mainnet, 1, "28D17DED198EC7FFA421397A7781619303BC8DDC"),
+    bchaddrjs("bitcoincash:ppxedxtug7kpwd6tgf5vx08gjamel7slds0sj5p646", Network::Mainnet, 1, "4D96997C47AC17374B4268C33CE897779FFA1F6C"),
+    bchaddrjs("bitcoincash:pr4fs2m8tjmw54r2aqmadggzuagttkujgy5hdm2apc", Network::Mainnet, 1, "EA982B675CB6EA546AE837D6A102E750B5DB9241"),
+    bchaddrjs("bitcoincash:prmed4fxlhkgay9nxw7zn9muew5ktkyjnutcnrrmey", Network::Mainnet, 1, "F796D526FDEC8E90B333BC29977CCBA965D8929F"),
+    bchaddrjs("bitcoincash:pqv3cpvmu4h0vqa6aly0urec7kwtuhe49y4ly3zf33", Network::Mainnet, 1, "191C059BE56EF603BAEFC8FE0F38F59CBE5F3529"),
+    bchaddrjs("bitcoincash:pr39scfteeu5l573lzerchh6wc4cqkxetu5c5dw4dc", Network::Mainnet, 1, "E258612BCE794FD3D1F8B23C5EFA762B8058D95F"),
+    bchaddrjs("bitcoincash:pzzjgw37vwls805c9fw6g9vqyupadst6wgvcwkgnwz", Network::Mainnet, 1, "85243A3E63BF03BE982A5DA415802703D6C17A72"),
+    bchaddrjs("bchtest:qph5kuz78czq00e3t85ugpgd7xmer5kr7csm740kf2", Network::Testnet, 0, "6F4B705E3E0407BF3159E9C4050DF1B791D2C3F6"),
+    bchaddrjs("bchtest:qpxenfpcf975gxdjmq9pk3xm6hjmfj6re57e7gjvh8", Network::Testnet, 0, "4D99A438497D4419B2D80A1B44DBD5E5B4CB43CD"),
+    bchaddrjs("bchtest:qzfau6vrq980qntgp5e7l6cpfsf7jw88c5cvqqkhpx", Network::Testnet, 0, "93DE6983014EF04D680D33EFEB014C13E938E7C5"),
+    bchaddrjs("bchtest:qzcguejjfxld867ck4zudc9a6y8mf6ftgqy3z9tvct", Network::Testnet, 0, "B08E665249BED3EBD8B545C6E0BDD10FB4E92B40"),
+    bchaddrjs("bchtest:qqm2lpqdfjsg8kkhwk0a3e3gypyswkd69u8pqz89en", Network::Testnet, 0, "36AF840D4CA083DAD7759FD8E62820490759BA2F"),
+    bchaddrjs("bchtest:qrccfa4qm3xfcrta78v7du75jjaww0ylns5xhpjnzv", Network::Testnet, 0, "F184F6A0DC4C9C0D7DF1D9E6F3D494BAE73C9F9C"),
+    bchaddrjs("bchtest:qqdcsl6c879esyxyacmz7g6vtzwjjwtznsggspc457", Network::Testnet, 0, "1B887F583F8B9810C4EE362F234C589D2939629C"),
+    bchaddrjs("bchtest:qpr2ddwe8qnnh8h20mmn4zgrharmy0vuy5q8vy9lck", Network::Testnet, 0, "46A6B5D938273B9EEA7EF73A8903BF47B23D9C25"),
+    bchaddrjs("bchtest:qqymsmh0nhfhs9k5whhnjwfxyaumvtxm8gwsthh7zc", Network::Testnet, 0, "09B86EEF9DD37816D475EF3939262779B62CDB3A"),
+    bchaddrjs("bchtest:qzwdmm83qjx7372wxgszaukan73ffn8ct5377sjx2r", Network::Testnet, 0, "9CDDECF1048DE8F94E32202EF2DD9FA294CCF85D"),
+    bchaddrjs("bchtest:qzh3f9me5z5sn2w8euap2gyrp6kr7gf6mysfn4mnav", Network::Testnet, 0, "AF149779A0A909A9C7CF3A1520830EAC3F213AD9"),
+    bchaddrjs("bchtest:qrneuckcx69clprn4nnr82tf8sycqrs3ac3e8qesa4", Network::Testnet, 0, "E79E62D8368B8F8473ACE633A9693C09800E11EE"),
+    bchaddrjs("bchtest:qz742xef07g9w8q52mx0q6m9hp05hnzm656uy2d07k", Network::Testnet, 0, "BD551B297F90571C1456CCF06B65B85F4BCC5BD5"),
+    bchaddrjs("bchtest:qq5dzl0drx8v0layyyuh5aupvxfs80ydms9x3jhaqn", Network::Testnet, 0, "28D17DED198EC7FFA421397A7781619303BC8DDC"),
+    bchaddrjs("bchtest:qpxedxtug7kpwd6tgf5vx08gjamel7sldsu8tuywfm", Network::Testnet, 0, "4D96997C47AC17374B4268C33CE897779FFA1F6C"),
+    bchaddrjs("bchtest:qr4fs2m8tjmw54r2aqmadggzuagttkujgy8q5n0fae", Network::Testnet, 0, "EA982B675CB6EA546AE837D6A102E750B5DB9241"),
+    bchaddrjs("bchtest:qrmed4fxlhkgay9nxw7zn9muew5ktkyjnuc02tx099", Network::Testnet, 0, "F796D526FDEC8E90B333BC29977CCBA965D8929F"),
+    bchaddrjs("bchtest:qqv3cpvmu4h0vqa6aly0urec7kwtuhe49yxgae8ads", Network::Testnet, 0, "191C059BE56EF603BAEFC8FE0F38F59CBE5F3529"),
+    bchaddrjs("bchtest:qr39scfteeu5l573lzerchh6wc4cqkxetu80d9tp3e", Network::Testnet, 0, "E258612BCE794FD3D1F8B23C5EFA762B8058D95F"),
+    bchaddrjs("bchtest:qzzjgw37vwls805c9fw6g9vqyupadst6wgl0h7d8jr", Network::Testnet, 0, "85243A3E63BF03BE982A5DA415802703D6C17A72"),
+    bchaddrjs("bchtest:pph5kuz78czq00e3t85ugpgd7xmer5kr7c87r6g4jh", Network::Testnet, 1, "6F4B705E3E0407BF3159E9C4050DF1B791D2C3F6"),
+    bchaddrjs("bchtest:ppxenfpcf975gxdjmq9pk3xm6hjmfj6re5fur840v6", Network::Testnet, 1, "4D99A438497D4419B2D80A1B44DBD5E5B4CB43CD"),
+    bchaddrjs("bchtest:pzfau6vrq980qntgp5e7l6cpfsf7jw88c50fa0356m", Network::Testnet, 1, "93DE6983014EF04D680D33EFEB014C13E938E7C5"),
+    bchaddrjs("bchtest:pzcguejjfxld867ck4zudc9a6y8mf6ftgqn5l2v0rk", Network::Testnet, 1, "B08E665249BED3EBD8B545C6E0BDD10FB4E92B40"),
+    bchaddrjs("bchtest:pqm2lpqdfjsg8kkhwk0a3e3gypyswkd69usyadqxzw", Network::Testnet, 1, "36AF840D4CA083DAD7759FD8E62820490759BA2F"),
+    bchaddrjs("bchtest:prccfa4qm3xfcrta78v7du75jjaww0ylnsrr2w4se3", Network::Testnet, 1, "F184F6A0DC4C9C0D7DF1D9E6F3D494BAE73C9F9C"),
+    bchaddrjs("bchtest:pqdcsl6c879esyxyacmz7g6vtzwjjwtznslddwlk0r", Network::Testnet, 1, "1B887F583F8B9810C4EE362F234C589D2939629C"),
+    bchaddrjs("bchtest:ppr2ddwe8qnnh8h20mmn4zgrharmy0vuy5hz3tzurt", Network::Testnet, 1, "46A6B5D938273B9EEA7EF73A8903BF47B23D9C25"),
+    bchaddrjs("bchtest:pqymsmh0nhfhs9k5whhnjwfxyaumvtxm8ge4kcsae9", Network::Testnet, 1, "09B86EEF9DD37816D475EF3939262779B62CDB3A"),
+    bchaddrjs("bchtest:pzwdmm83qjx7372wxgszaukan73ffn8ct5xmrl4937", Network::Testnet, 1, "9CDDECF1048DE8F94E32202EF2DD9FA294CCF85D"),
+    bchaddrjs("bchtest:pzh3f9me5z5sn2w8euap2gyrp6kr7gf6my8vw6usx3", Network::Testnet, 1, "AF149779A0A909A9C7CF3A1520830EAC3F213AD9"),
+    bchaddrjs("bchtest:prneuckcx69clprn4nnr82tf8sycqrs3acxu607nxg", Network::Testnet, 1, "E79E62D8368B8F8473ACE633A9693C09800E11EE"),
+    bchaddrjs("bchtest:pz742xef07g9w8q52mx0q6m9hp05hnzm65dee92v9t", Network::Testnet, 1, "BD551B297F90571C1456CCF06B65B85F4BCC5BD5"),
+    bchaddrjs("bchtest:pq5dzl0drx8v0layyyuh5aupvxfs80ydmsjrvas7mw", Network::Testnet, 1, "28D17DED198EC7FFA421397A7781619303BC8DDC"),
+    bchaddrjs("bchtest:ppxedxtug7kpwd6tgf5vx08gjamel7sldstzknrdjx", Network::Testnet, 1, "4D96997C47AC17374B4268C33CE897779FFA1F6C"),
+    bchaddrjs("bchtest:pr4fs2m8tjmw54r2aqmadggzuagttkujgys9fug2xy", Network::Testnet, 1, "EA982B675CB6EA546AE837D6A102E750B5DB9241"),
+    bchaddrjs("bchtest:prmed4fxlhkgay9nxw7zn9muew5ktkyjnu02hypv7c", Network::Testnet, 1, "F796D526FDEC8E90B333BC29977CCBA965D8929F"),
+    bchaddrjs("bchtest:pqv3cpvmu4h0vqa6aly0urec7kwtuhe49y3dqkq7kd", Network::Testnet, 1, "191C059BE56EF603BAEFC8FE0F38F59CBE5F3529"),
+    bchaddrjs("bchtest:pr39scfteeu5l573lzerchh6wc4cqkxetus2s2vz2y", Network::Testnet, 1, "E258612BCE794FD3D1F8B23C5EFA762B8058D95F"),
+    bchaddrjs("bchtest:pzzjgw37vwls805c9fw6g9vqyupadst6wgg2232yf7", Network::Testnet, 1, "85243A3E63BF03BE982A5DA415802703D6C17A72"),
+    bchaddrjs("bchreg:qph5kuz78czq00e3t85ugpgd7xmer5kr7c28g5v92v", Network::Regtest, 0, "6F4B705E3E0407BF3159E9C4050DF1B791D2C3F6"),
+    bchaddrjs("bchreg:qpxenfpcf975gxdjmq9pk3xm6hjmfj6re5y9gf3l5p", Network::Regtest, 0, "4D99A438497D4419B2D80A1B44DBD5E5B4CB43CD"),
+    bchaddrjs("bchreg:qzfau6vrq980qntgp5e7l6cpfsf7jw88c5zskp4yzq", Network::Regtest, 0, "93DE6983014EF04D680D33EFEB014C13E938E7C5"),
+    bchaddrjs("bchreg:qzcguejjfxld867ck4zudc9a6y8mf6ftgq7d5yglmd", Network::Regtest, 0, "B08E665249BED3EBD8B545C6E0BDD10FB4E92B40"),
+    bchaddrjs("bchreg:qqm2lpqdfjsg8kkhwk0a3e3gypyswkd69uaakryk64", Network::Regtest, 0, "36AF840D4CA083DAD7759FD8E62820490759BA2F"),
+    bchaddrjs("bchreg:qrccfa4qm3xfcrta78v7du75jjaww0ylnsw6pq3qp2", Network::Regtest, 0, "F184F6A0DC4C9C0D7DF1D9E6F3D494BAE73C9F9C"),
+    bchaddrjs("bchreg:qqdcsl6c879esyxyacmz7g6vtzwjjwtznsj5xqmxhc", Network::Regtest, 0, "1B887F583F8B9810C4EE362F234C589D2939629C"),
+    bchaddrjs("bchreg:qpr2ddwe8qnnh8h20mmn4zgrharmy0vuy56m69xvms", Network::Regtest, 0, "46A6B5D938273B9EEA7EF73A8903BF47B23D9C25"),
+    bchaddrjs("bchreg:qqymsmh0nhfhs9k5whhnjwfxyaumvtxm8g5vak5dp7", Network::Regtest, 0, "09B86EEF9DD37816D475EF3939262779B62CDB3A"),
+    bchaddrjs("bchreg:qzwdmm83qjx7372wxgszaukan73ffn8ct5tzg334f9", Network::Regtest, 0, "9CDDECF1048DE8F94E32202EF2DD9FA294CCF85D"),
+    bchaddrjs("bchreg:qzh3f9me5z5sn2w8euap2gyrp6kr7gf6my2495cq72", Network::Regtest, 0, "AF149779A0A909A9C7CF3A1520830EAC3F213AD9"),
+    bchaddrjs("bchreg:qrneuckcx69clprn4nnr82tf8sycqrs3act93p6r7n", Network::Regtest, 0, "E79E62D8368B8F8473ACE633A9693C09800E11EE"),
+    bchaddrjs("bchreg:qz742xef07g9w8q52mx0q6m9hp05hnzm65qqjtwuas", Network::Regtest, 0, "BD551B297F90571C1456CCF06B65B85F4BCC5BD5"),
+    bchaddrjs("bchreg:qq5dzl0drx8v0layyyuh5aupvxfs80ydmsl68n5wr4", Network::Regtest, 0, "28D17DED198EC7FFA421397A7781619303BC8DDC"),
+    bchaddrjs("bchreg:qpxedxtug7kpwd6tgf5vx08gjamel7sldsxmaa8a2a", Network::Regtest, 0, "4D96997C47AC17374B4268C33CE897779FFA1F6C"),
+    bchaddrjs("bchreg:qr4fs2m8tjmw54r2aqmadggzuagttkujgyauzjv67l", Network::Regtest, 0, "EA982B675CB6EA546AE837D6A102E750B5DB9241"),
+    bchaddrjs("bchreg:qrmed4fxlhkgay9nxw7zn9muew5ktkyjnuznu29uxr", Network::Regtest, 0, "F796D526FDEC8E90B333BC29977CCBA965D8929F"),
+    bchaddrjs("bchreg:qqv3cpvmu4h0vqa6aly0urec7kwtuhe49yu5tcywwk", Network::Regtest, 0, "191C059BE56EF603BAEFC8FE0F38F59CBE5F3529"),
+    bchaddrjs("bchreg:qr39scfteeu5l573lzerchh6wc4cqkxetuanmygjjl", Network::Regtest, 0, "E258612BCE794FD3D1F8B23C5EFA762B8058D95F"),
+    bchaddrjs("bchreg:qzzjgw37vwls805c9fw6g9vqyupadst6wg9nplw539", Network::Regtest, 0, "85243A3E63BF03BE982A5DA415802703D6C17A72"),
+    bchaddrjs("bchreg:pph5kuz78czq00e3t85ugpgd7xmer5kr7caz4mtx33", Network::Regtest, 1, "6F4B705E3E0407BF3159E9C4050DF1B791D2C3F6"),
+    bchaddrjs("bchreg:ppxenfpcf975gxdjmq9pk3xm6hjmfj6re5nq4xku0u", Network::Regtest, 1, "4D99A438497D4419B2D80A1B44DBD5E5B4CB43CD"),
+    bchaddrjs("bchreg:pzfau6vrq980qntgp5e7l6cpfsf7jw88c544twj8ea", Network::Regtest, 1, "93DE6983014EF04D680D33EFEB014C13E938E7C5"),
+    bchaddrjs("bchreg:pzcguejjfxld867ck4zudc9a6y8mf6ftgqfgft0uqs", Network::Regtest, 1, "B08E665249BED3EBD8B545C6E0BDD10FB4E92B40"),
+    bchaddrjs("bchreg:pqm2lpqdfjsg8kkhwk0a3e3gypyswkd69u2ctvr4pg", Network::Regtest, 1, "36AF840D4CA083DAD7759FD8E62820490759BA2F"),
+    bchaddrjs("bchreg:prccfa4qm3xfcrta78v7du75jjaww0ylnselu0kr6h", Network::Regtest, 1, "F184F6A0DC4C9C0D7DF1D9E6F3D494BAE73C9F9C"),
+    bchaddrjs("bchreg:pqdcsl6c879esyxyacmz7g6vtzwjjwtzns93m0u9v9", Network::Regtest, 1, "1B887F583F8B9810C4EE362F234C589D2939629C"),
+    bchaddrjs("bchreg:ppr2ddwe8qnnh8h20mmn4zgrharmy0vuy5d782p0qd", Network::Regtest, 1, "46A6B5D938273B9EEA7EF73A8903BF47B23D9C25"),
+    bchaddrjs("bchreg:pqymsmh0nhfhs9k5whhnjwfxyaumvtxm8grfqenw6r", Network::Regtest, 1, "09B86EEF9DD37816D475EF3939262779B62CDB3A"),
+    bchaddrjs("bchreg:pzwdmm83qjx7372wxgszaukan73ffn8ct5u847kkjc", Network::Regtest, 1, "9CDDECF1048DE8F94E32202EF2DD9FA294CCF85D"),
+    bchaddrjs("bchreg:pzh3f9me5z5sn2w8euap2gyrp6kr7gf6myascmlr9h", Network::Regtest, 1, "AF149779A0A909A9C7CF3A1520830EAC3F213AD9"),
+    bchaddrjs("bchreg:prneuckcx69clprn4nnr82tf8sycqrs3acuqvwaq9w", Network::Regtest, 1, "E79E62D8368B8F8473ACE633A9693C09800E11EE"),
+    bchaddrjs("bchreg:pz742xef07g9w8q52mx0q6m9hp05hnzm65h90yflxd", Network::Regtest, 1, "BD551B297F90571C1456CCF06B65B85F4BCC5BD5"),
+    bchaddrjs("bchreg:pq5dzl0drx8v0layyyuh5aupvxfs80ydmsgl6undcg", Network::Regtest, 1, "28D17DED198EC7FFA421397A7781619303BC8DDC"),
+    bchaddrjs("bchreg:ppxedxtug7kpwd6tgf5vx08gjamel7slds37qjq73q", Network::Regtest, 1, "4D96997C47AC17374B4268C33CE897779FFA1F6C"),
+    bchaddrjs("bchreg:pr4fs2m8tjmw54r2aqmadggzuagttkujgy2elate9z", Network::Regtest, 1, "EA982B675CB6EA546AE837D6A102E750B5DB9241"),
+    bchaddrjs("bchreg:prmed4fxlhkgay9nxw7zn9muew5ktkyjnu4kp9zla7", Network::Regtest, 1, "F796D526FDEC8E90B333BC29977CCBA965D8929F"),
+    bchaddrjs("bchreg:pqv3cpvmu4h0vqa6aly0urec7kwtuhe49yt3khrd4t", Network::Regtest, 1, "191C059BE56EF603BAEFC8FE0F38F59CBE5F3529"),
+    bchaddrjs("bchreg:pr39scfteeu5l573lzerchh6wc4cqkxetu2kxt03fz", Network::Regtest, 1, "E258612BCE794FD3D1F8B23C5EFA762B8058D95F"),
+    bchaddrjs("bchreg:pzzjgw37vwls805c9fw6g9vqyupadst6wgjkusfh2c", Network::Regtest, 1, "85243A3E63BF03BE982A5DA415802703D6C17A72"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Converter, cash_addr};
+
+    #[test]
+    fn vectors_test() {
+        let converter = Converter::new();
+        assert_eq!(iter().filter(|vector| vector.source == Source::CashAddrSpec).count(), 32);
+
+        for vector in iter() {
+            let addr_type = vector.addr_type();
+
+            if !vector.is_legacy() {
+                let (prefix, decoded_type, hash) = cash_addr::decode(vector.address).unwrap();
+                assert_eq!(Some(prefix.as_str()), vector.address.split(':').next());
+                assert_eq!(decoded_type, addr_type);
+                assert_eq!(hash, vector.hash());
+            }
+
+            if let Some(network) = vector.network {
+                let (_, parsed_network, parsed_type, hash) = converter.parse(vector.address).unwrap();
+                assert_eq!(parsed_network, network);
+                assert_eq!(parsed_type, addr_type);
+                assert_eq!(hash, vector.hash());
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! Conversion of address streams, for async pipelines such as ETL jobs.
+//!
+//! `AddressStreamExt` adds combinators to any `futures::Stream` of addresses:
+//! `convert_addrs` converts each address on the polling task,
+//! and `convert_addrs_blocking` converts chunks of addresses on tokio's blocking threads, several at a time,
+//! keeping the async workers free on large streams.
+//! Both yield the results in input order. Enabled by the `async` feature.
+//! # Example
+//! ```
+//! # use bch_addr::{Converter, AddressFormat};
+//! use futures::{executor, stream, StreamExt};
+//! use bch_addr::stream::AddressStreamExt;
+//!
+//! let converter = Converter::new();
+//! let addrs = stream::iter(vec!["1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR".to_string(), "nope".to_string()]);
+//! let converted: Vec<_> = executor::block_on(addrs.convert_addrs(&converter, AddressFormat::CashAddr, None).collect());
+//! assert_eq!(converted[0].as_ref().unwrap(), "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+//! assert!(converted[1].is_err());
+//! ```
+
+use std::panic;
+use std::sync::Arc;
+
+use futures::{stream, Stream, StreamExt};
+
+use super::{Converter, ParsedAddress, AddressFormat, Network};
+use super::error::Result;
+
+/// Options of `AddressStreamExt::convert_addrs_blocking`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BlockingOptions {
+    /// Target format. Defaults to cash_addr.
+    pub format: AddressFormat,
+    /// Target network, `None` (the default) to keep the network of each address.
+    pub network: Option<Network>,
+    /// Number of addresses converted by each blocking task. Defaults to 1024.
+    pub chunk_size: usize,
+    /// Number of blocking tasks running at a time. Defaults to 4.
+    pub concurrency: usize,
+}
+
+impl Default for BlockingOptions {
+    fn default() -> Self {
+        BlockingOptions {
+            format: AddressFormat::CashAddr,
+            network: None,
+            chunk_size: 1024,
+            concurrency: 4,
+        }
+    }
+}
+
+/// Conversion combinators for streams of addresses.
+/// Converted addresses are lowercase and prefixed, even when already in the target format.
+pub trait AddressStreamExt: Stream<Item = String> + Sized {
+    /// Convert each address as the stream is polled.
+    /// # Arguments
+    /// * `converter` - Converter providing the prefix configuration.
+    /// * `format` - Target format.
+    /// * `network` - Target network, `None` to keep the network of each address.
+    /// # Returns
+    /// * Stream of converted addresses or errors, in input order.
+    fn convert_addrs<'c>(self, converter: &'c Converter, format: AddressFormat, network: Option<Network>) -> impl Stream<Item = Result<String>> + 'c
+        where Self: 'c
+    {
+        let mut parsed = ParsedAddress::default();
+        self.map(move |addr| convert(converter, &addr, &format, network, &mut parsed))
+    }
+
+    /// Convert chunks of addresses with `tokio::task::spawn_blocking`, running up to `options.concurrency` at a time.
+    /// Must be polled within a tokio runtime. A panic of a conversion task is resumed on the polling task.
+    /// # Arguments
+    /// * `converter` - Converter providing the prefix configuration, shared with the blocking tasks.
+    /// * `options` - Target and parallelism.
+    /// # Returns
+    /// * Stream of converted addresses or errors, in input order.
+    fn convert_addrs_blocking(self, converter: Arc<Converter>, options: BlockingOptions) -> impl Stream<Item = Result<String>> {
+        let BlockingOptions { format, network, chunk_size, concurrency } = options;
+        let format = Arc::new(format);
+        self.chunks(chunk_size.max(1))
+            .map(move |chunk| {
+                let converter = Arc::clone(&converter);
+                let format = Arc::clone(&format);
+                tokio::task::spawn_blocking(move || {
+                    let mut parsed = ParsedAddress::default();
+                    chunk.iter()
+                        .map(|addr| convert(&converter, addr, &format, network, &mut parsed))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .buffered(concurrency.max(1))
+            .flat_map(|converted| match converted {
+                Ok(converted) => stream::iter(converted),
+                Err(err)      => match err.try_into_panic() {
+                    Ok(payload) => panic::resume_unwind(payload),
+                    Err(err)    => panic!("conversion task failed: {}", err),
+                },
+            })
+    }
+}
+
+impl<S: Stream<Item = String>> AddressStreamExt for S {}
+
+fn convert(converter: &Converter, addr: &str, format: &AddressFormat, network: Option<Network>, parsed: &mut ParsedAddress) -> Result<String> {
+    converter.parse_into(addr, parsed)?;
+    converter.encode(format, network.unwrap_or(parsed.network), parsed.addr_type, &parsed.hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addrs() -> Vec<String> {
+        vec![
+            "QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK".to_string(),
+            "bitcoincash:bad".to_string(),
+            "mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi".to_string(),
+            "bchtest:ppm2qsznhks23z7629mms6s4cwef74vcwvhanqgjxu".to_string(),
+            "3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9".to_string(),
+        ]
+    }
+
+    #[tokio::test]
+    async fn convert_addrs_test() {
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+        let converted: Vec<_> = stream::iter(addrs())
+            .convert_addrs(&converter, AddressFormat::Legacy, None)
+            .map(|addr| addr.ok())
+            .collect().await;
+        assert_eq!(converted, [
+            Some("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR".to_string()),
+            None,
+            Some("mqfRfwGeZnFwfFE7KWJjyg6Yx212iGi6Fi".to_string()),
+            Some("2N44ThNe8NXHyv4bsX8AoVCXquBRW94Ls7W".to_string()),
+            Some("3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9".to_string()),
+        ]);
+
+        let converter = Arc::new(converter);
+        let options = BlockingOptions {
+            format: AddressFormat::Other("SLPAddr".to_string()),
+            network: Some(Network::Mainnet),
+            chunk_size: 2,
+            concurrency: 2,
+        };
+        let blocking: Vec<_> = stream::iter(addrs())
+            .convert_addrs_blocking(Arc::clone(&converter), options.clone())
+            .collect().await;
+        let expected: Vec<_> = stream::iter(addrs())
+            .convert_addrs(&converter, options.format, options.network)
+            .collect().await;
+        assert_eq!(blocking.len(), expected.len());
+        for (blocking, expected) in blocking.iter().zip(&expected) {
+            assert_eq!(blocking.as_ref().ok(), expected.as_ref().ok());
+        }
+        assert_eq!(blocking[0].as_ref().unwrap(), "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg");
+
+        let empty: Vec<_> = stream::iter(Vec::new())
+            .convert_addrs_blocking(converter, BlockingOptions::default())
+            .collect().await;
+        assert!(empty.is_empty());
+    }
+}
@@ -0,0 +1,243 @@
+//! Address generators for tests.
+//!
+//! Enabled by the `test-util` feature. The random generators take any `rand_core::RngCore`,
+//! and the seeded ones derive the same address from the same seed in every build, for snapshot fixtures.
+//! `assert_equivalent!` compares addresses across formats.
+
+use rand_core::RngCore;
+
+use super::{Converter, ParsedAddress, AddressFormat, AddressType, Network};
+use super::error::Result;
+use super::hash::{self, HASH160_LEN};
+
+/// Generate a valid address with a random 20-byte hash.
+/// # Arguments
+/// * `network` - Address network.
+/// * `addr_type` - Address type.
+/// * `format` - `AddressFormat::Legacy` or `AddressFormat::CashAddr`.
+/// * `rng` - Random number generator.
+/// # Returns
+/// * Address.
+/// # Panics
+/// * If `format` is `AddressFormat::Other`; use `random_addr_with` and a converter that knows its prefixes.
+/// * If `addr_type` is `AddressType::Other`, which `Converter::new()` does not accept;
+///   use `random_addr_with` and a converter with its type bits registered.
+/// * If `format` is compiled out: `AddressFormat::Legacy` without the `legacy` feature,
+///   `AddressFormat::CashAddr` without the `cashaddr` feature.
+/// # Example
+/// ```
+/// # use bch_addr::{Converter, AddressFormat, AddressType, Network};
+/// use bch_addr::test_util::random_addr;
+/// use rand::{rngs::SmallRng, SeedableRng};
+///
+/// let mut rng = SmallRng::seed_from_u64(1);
+/// let addr = random_addr(Network::Testnet, AddressType::P2SH, &AddressFormat::CashAddr, &mut rng);
+/// assert!(addr.starts_with("bchtest:p"));
+/// assert!(Converter::new().is_p2sh_addr(&addr));
+/// ```
+pub fn random_addr<R: RngCore + ?Sized>(network: Network, addr_type: AddressType, format: &AddressFormat, rng: &mut R) -> String {
+    random_addr_with(&Converter::new(), network, addr_type, format, rng)
+        .expect("format and type are supported by the default converter")
+}
+
+/// Generate a valid address with a random 20-byte hash, in any format registered in `converter`.
+/// # Arguments
+/// * `converter` - Converter providing the prefix configuration.
+/// * `network` - Address network.
+/// * `addr_type` - Address type.
+/// * `format` - Address format.
+/// * `rng` - Random number generator.
+/// # Returns
+/// * Address.
+/// # Example
+/// ```
+/// # use bch_addr::{Converter, AddressFormat, AddressType, Network};
+/// use bch_addr::test_util::random_addr_with;
+/// use rand::{rngs::SmallRng, SeedableRng};
+///
+/// let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+/// let format = AddressFormat::Other("SLPAddr".to_string());
+/// let addr = random_addr_with(&converter, Network::Mainnet, AddressType::P2PKH, &format, &mut SmallRng::seed_from_u64(1)).unwrap();
+/// assert!(addr.starts_with("simpleledger:q"));
+/// ```
+pub fn random_addr_with<R>(converter: &Converter, network: Network, addr_type: AddressType, format: &AddressFormat, rng: &mut R) -> Result<String>
+    where R: RngCore + ?Sized
+{
+    let mut hash = [0; HASH160_LEN];
+    rng.fill_bytes(&mut hash);
+    converter.encode(format, network, addr_type, &hash)
+}
+
+/// Derive a valid address from `seed`. The hash is `hash160(seed)`, so a seed gives equivalent addresses
+/// in every format, network and type, and the same ones in every release.
+/// # Arguments
+/// * `seed` - Seed, e.g. the name of a fixture.
+/// * `network` - Address network.
+/// * `addr_type` - Address type.
+/// * `format` - `AddressFormat::Legacy` or `AddressFormat::CashAddr`.
+/// # Returns
+/// * Address.
+/// # Panics
+/// * If `format` is `AddressFormat::Other`; use `addr_from_seed_with` and a converter that knows its prefixes.
+/// * If `addr_type` is `AddressType::Other`, which `Converter::new()` does not accept;
+///   use `addr_from_seed_with` and a converter with its type bits registered.
+/// * If `format` is compiled out: `AddressFormat::Legacy` without the `legacy` feature,
+///   `AddressFormat::CashAddr` without the `cashaddr` feature.
+/// # Example
+/// ```
+/// # use bch_addr::{AddressFormat, AddressType, Network};
+/// use bch_addr::test_util::addr_from_seed;
+///
+/// let alice = addr_from_seed(b"alice", Network::Mainnet, AddressType::P2PKH, &AddressFormat::CashAddr);
+/// assert_eq!(alice, addr_from_seed(b"alice", Network::Mainnet, AddressType::P2PKH, &AddressFormat::CashAddr));
+/// assert_ne!(alice, addr_from_seed(b"bob", Network::Mainnet, AddressType::P2PKH, &AddressFormat::CashAddr));
+/// ```
+pub fn addr_from_seed(seed: &[u8], network: Network, addr_type: AddressType, format: &AddressFormat) -> String {
+    addr_from_seed_with(&Converter::new(), seed, network, addr_type, format)
+        .expect("format and type are supported by the default converter")
+}
+
+/// Derive a valid address from `seed`, in any format registered in `converter`.
+/// # Arguments
+/// * `converter` - Converter providing the prefix configuration.
+/// * `seed` - Seed.
+/// * `network` - Address network.
+/// * `addr_type` - Address type.
+/// * `format` - Address format.
+/// # Returns
+/// * Address.
+pub fn addr_from_seed_with(converter: &Converter, seed: &[u8], network: Network, addr_type: AddressType, format: &AddressFormat) -> Result<String> {
+    converter.encode(format, network, addr_type, &hash::hash160(seed))
+}
+
+/// Assert that two addresses, in any format, have the same network, type and hash.
+/// On failure the panic message shows what each address decodes to and which parts differ.
+/// Uses `Converter::new()`; see `test_util::assert_equivalent_with` for other prefixes.
+/// # Example
+/// ```
+/// use bch_addr::assert_equivalent;
+///
+/// assert_equivalent!("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk");
+/// ```
+/// ```should_panic
+/// # use bch_addr::assert_equivalent;
+/// // differs in: network
+/// assert_equivalent!("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "bchtest:qph5kuz78czq00e3t85ugpgd7xmer5kr7csm740kf2");
+/// ```
+#[macro_export]
+macro_rules! assert_equivalent {
+    ($a:expr, $b:expr $(,)?) => {
+        $crate::test_util::assert_equivalent_with(&$crate::Converter::new(), &$a, &$b)
+    };
+}
+
+/// `assert_equivalent!` with the prefixes registered in `converter`.
+/// # Arguments
+/// * `converter` - Converter providing the prefix configuration.
+/// * `a` - Address in any format.
+/// * `b` - Address in any format.
+/// # Panics
+/// * If either address is invalid, or they differ in network, type or hash.
+#[track_caller]
+pub fn assert_equivalent_with(converter: &Converter, a: &str, b: &str) {
+    let parse = |addr| {
+        let mut parsed = ParsedAddress::default();
+        converter.parse_into(addr, &mut parsed).map(|()| parsed)
+    };
+    let (parsed_a, parsed_b) = match (parse(a), parse(b)) {
+        (Ok(parsed_a), Ok(parsed_b)) => (parsed_a, parsed_b),
+        (parsed_a, parsed_b)         => panic!(
+            "assertion failed: addresses are not equivalent\n  left: {}\n right: {}",
+            describe(a, parsed_a.as_ref().ok()),
+            describe(b, parsed_b.as_ref().ok()),
+        ),
+    };
+
+    let diff = [
+        ("network", parsed_a.network != parsed_b.network),
+        ("type", parsed_a.addr_type != parsed_b.addr_type),
+        ("hash", parsed_a.hash != parsed_b.hash),
+    ];
+    let differs: Vec<_> = diff.iter().filter(|(_, differs)| *differs).map(|(name, _)| *name).collect();
+    if !differs.is_empty() {
+        panic!(
+            "assertion failed: addresses are not equivalent\n  left: {}\n right: {}\ndiffers in: {}",
+            describe(a, Some(&parsed_a)),
+            describe(b, Some(&parsed_b)),
+            differs.join(", "),
+        );
+    }
+}
+
+fn describe(addr: &str, parsed: Option<&ParsedAddress>) -> String {
+    match parsed {
+        Some(parsed) => {
+            format!("{} ({:?}, {:?}, {:?}, {})", addr, parsed.format, parsed.network, parsed.addr_type, parsed.hash_hex())
+        },
+        None => format!("{} (invalid)", addr),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn random_addr_test() {
+        let converter = Converter::new();
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        for &network in &[Network::Mainnet, Network::Testnet, Network::Regtest] {
+            for &addr_type in &[AddressType::P2PKH, AddressType::P2SH] {
+                let addr = random_addr(network, addr_type, &AddressFormat::CashAddr, &mut rng);
+                assert_eq!(converter.detect_addr_network(&addr).unwrap(), network);
+                assert_eq!(converter.detect_addr_type(&addr).unwrap(), addr_type);
+
+                let legacy = random_addr(network, addr_type, &AddressFormat::Legacy, &mut rng);
+                assert!(converter.is_legacy_addr(&legacy));
+                assert_eq!(converter.detect_addr_type(&legacy).unwrap(), addr_type);
+                assert_ne!(converter.to_cash_addr(&legacy).unwrap(), addr);
+            }
+        }
+
+        let format = AddressFormat::Other("SLPAddr".to_string());
+        assert!(random_addr_with(&converter, Network::Mainnet, AddressType::P2PKH, &format, &mut rng).is_err());
+    }
+
+    #[test]
+    fn addr_from_seed_test() {
+        assert_eq!(
+            addr_from_seed(b"alice", Network::Mainnet, AddressType::P2PKH, &AddressFormat::CashAddr),
+            "bitcoincash:qpysn9jhu8mtcj4gvatmz8czuh90yy2t7yvdsnrqtm",
+        );
+        assert_eq!(
+            addr_from_seed(b"alice", Network::Mainnet, AddressType::P2PKH, &AddressFormat::Legacy),
+            "17fBp95y8fRSTqQpL4nv7G4cH6G5WHn817",
+        );
+        assert_eq!(
+            addr_from_seed(b"alice", Network::Testnet, AddressType::P2SH, &AddressFormat::CashAddr),
+            "bchtest:ppysn9jhu8mtcj4gvatmz8czuh90yy2t7yl6fmx5h6",
+        );
+    }
+
+    #[test]
+    fn assert_equivalent_test() {
+        let legacy = addr_from_seed(b"alice", Network::Mainnet, AddressType::P2PKH, &AddressFormat::Legacy);
+        assert_equivalent!(legacy, "QPYSN9JHU8MTCJ4GVATMZ8CZUH90YY2T7YVDSNRQTM");
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+        assert_equivalent_with(&converter, "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg");
+
+        let message = |a: &'static str, b: &'static str| {
+            let err = std::panic::catch_unwind(|| assert_equivalent!(a, b)).unwrap_err();
+            err.downcast::<String>().unwrap()
+        };
+        assert_eq!(*message("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "bitcoincash:pph5kuz78czq00e3t85ugpgd7xmer5kr7crv8a2z4t"), "\
+            assertion failed: addresses are not equivalent\n  \
+            left: 1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR (Legacy, Mainnet, P2PKH, 6f4b705e3e0407bf3159e9c4050df1b791d2c3f6)\n \
+            right: bitcoincash:pph5kuz78czq00e3t85ugpgd7xmer5kr7crv8a2z4t (CashAddr, Mainnet, P2SH, 6f4b705e3e0407bf3159e9c4050df1b791d2c3f6)\n\
+            differs in: type");
+        assert!(message("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "nope").ends_with("right: nope (invalid)"));
+    }
+}
@@ -0,0 +1,67 @@
+//! Instrumentation hooks reported through `tracing`.
+//! Without the `tracing` feature every hook compiles to nothing.
+#![cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+
+use super::{AddressFormat, Network};
+use super::error::Error;
+
+/// Decoder an address was tried with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decoder {
+    Legacy,
+    CashAddr,
+}
+
+impl Decoder {
+    #[cfg(feature = "tracing")]
+    fn name(self) -> &'static str {
+        match self {
+            Decoder::Legacy   => "legacy",
+            Decoder::CashAddr => "cash_addr",
+        }
+    }
+}
+
+/// Span covering the decoding of one address; exited when dropped.
+pub struct ParseSpan {
+    #[cfg(feature = "tracing")]
+    _span: tracing::span::EnteredSpan,
+}
+
+/// Enter the span of a parse attempt.
+pub fn parse_span(addr: &str) -> ParseSpan {
+    ParseSpan {
+        #[cfg(feature = "tracing")]
+        _span: tracing::trace_span!("parse", addr).entered(),
+    }
+}
+
+/// `decoder` accepted the address.
+pub fn decoder_matched(decoder: Decoder) {
+    #[cfg(feature = "tracing")]
+    tracing::trace!(decoder = decoder.name(), "decoder matched");
+}
+
+/// `decoder` rejected the address; the next one may still accept it.
+pub fn decoder_failed(decoder: Decoder, err: &Error) {
+    #[cfg(feature = "tracing")]
+    tracing::trace!(decoder = decoder.name(), error = %err, "decoder failed");
+}
+
+/// The checksum of a prefix-less address was checked against `prefix`.
+pub fn prefix_trial(prefix: &str, matched: bool) {
+    #[cfg(feature = "tracing")]
+    tracing::trace!(prefix, matched, "prefix trial");
+}
+
+/// Every decoder rejected `addr`; `err` is the reason given by the last one.
+pub fn rejected(addr: &str, err: &Error) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(addr, error = %err, "address rejected");
+}
+
+/// No prefix is registered for the target of a conversion.
+pub fn unknown_format(format: &AddressFormat, network: Network) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(?format, ?network, "no prefix registered for the target format");
+}
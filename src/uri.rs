@@ -0,0 +1,40 @@
+use super::error::{Error, Result};
+
+/// Maximum number of decimal places of a BCH amount (satoshis).
+const MAX_DECIMALS: usize = 8;
+
+/// Append `?amount=` to `cash_addr` after checking `amount` is a plain decimal BCH amount.
+pub fn build(cash_addr: String, amount: Option<&str>) -> Result<String> {
+    let amount = match amount {
+        Some(amount) => amount,
+        None         => return Ok(cash_addr),
+    };
+
+    let (int, frac) = match amount.find('.') {
+        Some(dot) => (&amount[..dot], &amount[dot + 1..]),
+        None      => (amount, ""),
+    };
+    let is_digits = |s: &str| s.bytes().all(|c| c.is_ascii_digit());
+    if int.is_empty() || !is_digits(int) || !is_digits(frac) || frac.len() > MAX_DECIMALS || amount.ends_with('.') {
+        return Err(Error::InvalidAmount(amount.to_string()));
+    }
+
+    Ok(format!("{}?amount={}", cash_addr, amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_test() {
+        let addr = "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk";
+
+        assert_eq!(build(addr.to_string(), None).unwrap(), addr);
+        assert_eq!(build(addr.to_string(), Some("0.1")).unwrap(), format!("{}?amount=0.1", addr));
+        assert_eq!(build(addr.to_string(), Some("21000000")).unwrap(), format!("{}?amount=21000000", addr));
+        for amount in &["", ".1", "1.", "1.123456789", "-1", "1e3", "1.2.3", "１"] {
+            assert!(build(addr.to_string(), Some(amount)).is_err(), "{}", amount);
+        }
+    }
+}
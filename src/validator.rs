@@ -0,0 +1,350 @@
+use super::Converter;
+use super::cash_addr::{self, decode_char};
+use super::legacy_converter;
+
+/// Payload length of legacy addresses (version byte, 20-byte hash and checksum),
+/// a lower bound of their length since each byte takes at least one character.
+const MIN_LEGACY_LEN: usize = 25;
+
+/// State of the input of an `IncrementalValidator`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ValidationState {
+    /// The input can still become a valid address.
+    Incomplete,
+    /// No address starts like the input; more characters will not help.
+    /// # Arguments
+    /// * Position of the first offending character, in characters.
+    InvalidCharAt(usize),
+    /// The input is a valid address.
+    CompleteValid,
+    /// The input has the length of a complete address but its checksum does not match.
+    CompleteInvalid,
+}
+
+/// Validator of an address typed character by character, e.g. in a wallet form field.
+///
+/// Each character is checked against what the characters before it allow (alphabet, case,
+/// registered prefixes and type fields, type and length given by the version byte) without going over the input again,
+/// and the checksum is only verified once the input has the length of a complete address.
+/// # Example
+/// ```
+/// # #[cfg(feature = "cashaddr")]
+/// # {
+/// # use bch_addr::{Converter, IncrementalValidator, ValidationState};
+/// let converter = Converter::new();
+/// let mut validator = IncrementalValidator::new(&converter);
+///
+/// assert_eq!(validator.push_str("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdp"), ValidationState::Incomplete);
+/// assert_eq!(validator.push_str("wk"), ValidationState::CompleteValid);
+/// assert_eq!(validator.pop(), ValidationState::Incomplete);
+/// assert_eq!(validator.push('x'), ValidationState::CompleteInvalid);
+/// assert_eq!(validator.push('q'), ValidationState::InvalidCharAt(54));
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct IncrementalValidator<'a> {
+    converter: &'a Converter,
+    input: String,
+    /// The input can still be a legacy address.
+    legacy: bool,
+    /// The input can still be a cash_addr address without prefix.
+    bare_cash: bool,
+    /// The input can still be the beginning of a registered prefix or alias.
+    prefix: bool,
+    /// Position of the separator once a registered prefix is complete.
+    separator: Option<usize>,
+    /// Case of the letters so far, for cash_addr.
+    lowercase: Option<bool>,
+    state: ValidationState,
+}
+
+impl<'a> IncrementalValidator<'a> {
+    /// Construct a validator with empty input.
+    /// # Arguments
+    /// * `converter` - Converter providing the prefix configuration.
+    /// # Returns
+    /// * Validator.
+    pub fn new(converter: &'a Converter) -> IncrementalValidator<'a> {
+        IncrementalValidator {
+            converter,
+            input: String::new(),
+            legacy: true,
+            bare_cash: true,
+            prefix: true,
+            separator: None,
+            lowercase: None,
+            state: ValidationState::Incomplete,
+        }
+    }
+
+    /// Append a character.
+    /// # Arguments
+    /// * `c` - Character.
+    /// # Returns
+    /// * State of the input.
+    pub fn push(&mut self, c: char) -> ValidationState {
+        let pos = self.input.len();
+        self.input.push(c);
+        if let ValidationState::InvalidCharAt(_) = self.state {
+            return self.state;
+        }
+        // every character so far is ASCII, so `pos` counts characters
+        if !c.is_ascii() {
+            return self.invalid(pos);
+        }
+
+        let b = c as u8;
+        let case_ok = !b.is_ascii_alphabetic() || self.lowercase.is_none_or(|lower| lower == b.is_ascii_lowercase());
+        if case_ok && b.is_ascii_alphabetic() {
+            self.lowercase = Some(b.is_ascii_lowercase());
+        }
+
+        match self.separator {
+            Some(sep) => {
+                let cash_converter = &self.converter.cash_converter;
+                let accepts_type = |bits| cash_converter.prefix_details(&self.input[..sep])
+                    .is_some_and(|(format, _)| cash_converter.accepts_type_bits(Some(format), bits));
+                if !(case_ok && is_cash_body_start(&self.input[sep + 1..], accepts_type)) {
+                    return self.invalid(pos);
+                }
+            },
+            None if b == b':' => {
+                if !(self.prefix && self.converter.cash_converter.prefix_details(&self.input[..pos]).is_some()) {
+                    return self.invalid(pos);
+                }
+                self.separator = Some(pos);
+                self.legacy = false;
+                self.bare_cash = false;
+            },
+            None => {
+                let input = self.input.as_bytes();
+                self.legacy = self.legacy && is_legacy_start(input);
+                let cash_converter = &self.converter.cash_converter;
+                self.bare_cash = self.bare_cash && case_ok
+                    && is_cash_body_start(&self.input, |bits| cash_converter.accepts_type_bits(None, bits));
+                let mut prefixes = cash_converter.prefixes().map(|(prefix, _, _)| prefix)
+                    .chain(cash_converter.aliases().map(|(alias, _)| alias));
+                self.prefix = self.prefix && case_ok
+                    && prefixes.any(|prefix| prefix.len() > pos && prefix.as_bytes()[..=pos].eq_ignore_ascii_case(input));
+                if !(self.legacy || self.bare_cash || self.prefix) {
+                    return self.invalid(pos);
+                }
+            },
+        }
+
+        self.state = self.completion();
+        self.state
+    }
+
+    /// Append characters.
+    /// # Arguments
+    /// * `chunk` - Characters, e.g. pasted text.
+    /// # Returns
+    /// * State of the input.
+    pub fn push_str(&mut self, chunk: &str) -> ValidationState {
+        for c in chunk.chars() {
+            self.push(c);
+        }
+        self.state
+    }
+
+    /// Remove the last character, e.g. on backspace. The remaining input is validated again.
+    /// # Returns
+    /// * State of the input.
+    pub fn pop(&mut self) -> ValidationState {
+        let mut input = std::mem::take(&mut self.input);
+        input.pop();
+        self.clear();
+        self.push_str(&input)
+    }
+
+    /// Remove all characters.
+    pub fn clear(&mut self) {
+        *self = IncrementalValidator::new(self.converter);
+    }
+
+    /// State of the input.
+    pub fn state(&self) -> ValidationState {
+        self.state
+    }
+
+    /// Input so far.
+    pub fn as_str(&self) -> &str {
+        &self.input
+    }
+
+    fn invalid(&mut self, pos: usize) -> ValidationState {
+        self.state = ValidationState::InvalidCharAt(pos);
+        self.state
+    }
+
+    /// State of input whose characters are all acceptable.
+    fn completion(&self) -> ValidationState {
+        let cash_converter = &self.converter.cash_converter;
+        if let Some(sep) = self.separator {
+            return match cash_body_len(&self.input[sep + 1..]) {
+                Some(len) if len == self.input.len() - sep - 1 => match cash_converter.classify(&self.input) {
+                    Ok(_)  => ValidationState::CompleteValid,
+                    Err(_) => ValidationState::CompleteInvalid,
+                },
+                _ => ValidationState::Incomplete,
+            };
+        }
+
+        // the prefix being typed can always grow
+        let mut growable = self.prefix;
+        if self.bare_cash {
+            match cash_body_len(&self.input) {
+                Some(len) if len == self.input.len() => {
+                    if cash_converter.classify(&self.input).is_ok() {
+                        return ValidationState::CompleteValid;
+                    }
+                },
+                _ => growable = true,
+            }
+        }
+        if self.legacy {
+            if self.input.len() >= MIN_LEGACY_LEN && legacy_converter::classify(&self.input).is_ok() {
+                return ValidationState::CompleteValid;
+            }
+            growable |= self.input.len() < max_legacy_len(self.input.as_bytes()[0]);
+        }
+
+        if growable {
+            ValidationState::Incomplete
+        } else {
+            ValidationState::CompleteInvalid
+        }
+    }
+}
+
+/// Longest legacy address starting with `first`.
+fn max_legacy_len(first: u8) -> usize {
+    // testnet P2SH version byte 0xc4 makes the payload one character longer
+    if first == b'2' { 35 } else { 34 }
+}
+
+/// The last character of `input` can follow the ones before it in a legacy address.
+fn is_legacy_start(input: &[u8]) -> bool {
+    match input {
+        [first] => matches!(first, b'1' | b'3' | b'm' | b'n' | b'2'),
+        [first, .., last] => {
+            last.is_ascii_alphanumeric() && !b"0OIl".contains(last) && input.len() <= max_legacy_len(*first)
+        },
+        [] => true,
+    }
+}
+
+/// The last character of `body` can follow the ones before it in the part of a cash_addr address
+/// after the separator: the first one gives the type, which `accepts_type` checks against the registered
+/// type fields, and the first two the hash length.
+fn is_cash_body_start(body: &str, accepts_type: impl FnOnce(u8) -> bool) -> bool {
+    let bytes = body.as_bytes();
+    match bytes.last().and_then(|&c| decode_char(c)) {
+        None                            => false,
+        Some(value) if bytes.len() == 1 => accepts_type(value),
+        Some(_)                         => cash_body_len(body).is_none_or(|len| bytes.len() <= len),
+    }
+}
+
+/// Length of the part after the separator given by the version byte in its first two characters.
+fn cash_body_len(body: &str) -> Option<usize> {
+    match body.as_bytes() {
+        [first, second, ..] => {
+            let version_byte = (decode_char(*first)? << 3) | (decode_char(*second)? >> 2);
+            Some(cash_addr::body_len(cash_addr::hash_size(version_byte)))
+        },
+        _ => None,
+    }
+}
+
+#[cfg(all(test, feature = "cashaddr"))]
+mod tests {
+    use super::*;
+    use crate::Network;
+
+    fn states(validator: &mut IncrementalValidator<'_>, input: &str) -> Vec<ValidationState> {
+        input.chars().map(|c| validator.push(c)).collect()
+    }
+
+    #[cfg(feature = "legacy")]
+    #[test]
+    fn incremental_validator_test() {
+        use ValidationState::*;
+
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+        let mut validator = IncrementalValidator::new(&converter);
+
+        for addr in &[
+            "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR",
+            "2N44ThNe8NXHyv4bsX8AoVCXquBRW94Ls7W",
+            "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk",
+            "SIMPLELEDGER:QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7CCJ3FCPSG",
+            "qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk",
+            "pph5kuz78czq00e3t85ugpgd7xmer5kr7crv8a2z4t",
+        ] {
+            validator.clear();
+            let states = states(&mut validator, addr);
+            assert!(states[..addr.len() - 1].iter().all(|state| *state == Incomplete), "{}", addr);
+            assert_eq!(states[addr.len() - 1], CompleteValid, "{}", addr);
+        }
+
+        // 32-byte hash: the version byte announces a longer body
+        let p2sh32 = converter.encode(&crate::AddressFormat::CashAddr, Network::Mainnet, crate::AddressType::P2SH, &[0xab; 32]).unwrap();
+        validator.clear();
+        assert_eq!(validator.push_str(&p2sh32[..54]), Incomplete);
+        assert_eq!(validator.push_str(&p2sh32[54..]), CompleteValid);
+
+        validator.clear();
+        assert_eq!(validator.push_str("qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwq"), CompleteInvalid);
+        assert_eq!(validator.push('q'), InvalidCharAt(42));
+        assert_eq!(validator.push('q'), InvalidCharAt(42));
+        assert_eq!(validator.pop(), InvalidCharAt(42));
+        assert_eq!(validator.pop(), CompleteInvalid);
+        assert_eq!(validator.as_str().len(), 42);
+
+        validator.clear();
+        assert_eq!(validator.push_str("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzq"), CompleteInvalid);
+
+        for (input, pos) in &[
+            ("x", 0),
+            ("bitcoincash:x", 12),
+            ("bitcoincash:qPh", 13),
+            ("bitcoincash:zq", 12),
+            ("bitcoin:", 7),
+            ("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwkq", 54),
+            ("1B9UNtBf0", 8),
+            ("qph5é", 4),
+        ] {
+            validator.clear();
+            assert_eq!(validator.push_str(input), InvalidCharAt(*pos), "{}", input);
+        }
+    }
+
+    #[test]
+    fn registered_type_bits_test() {
+        use ValidationState::*;
+
+        let other = "bitcoincash:zp24242424242424242424242424242425fpph3eds";
+        let converter = Converter::new()
+            .add_type_bits(&crate::AddressFormat::CashAddr, &[2])
+            .add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+        let mut validator = IncrementalValidator::new(&converter);
+        for addr in &[other, &other[12..]] {
+            validator.clear();
+            let states = states(&mut validator, addr);
+            assert!(states[..addr.len() - 1].iter().all(|state| *state == Incomplete), "{}", addr);
+            assert_eq!(states[addr.len() - 1], CompleteValid, "{}", addr);
+        }
+
+        // registered for cash_addr only, and 3 for none
+        for (input, pos) in &[("simpleledger:z", 13), ("bitcoincash:r", 12), ("r", 0)] {
+            validator.clear();
+            assert_eq!(validator.push_str(input), InvalidCharAt(*pos), "{}", input);
+        }
+
+        let default = Converter::new();
+        let mut validator = IncrementalValidator::new(&default);
+        assert_eq!(validator.push_str(other), InvalidCharAt(12));
+    }
+}
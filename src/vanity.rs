@@ -0,0 +1,102 @@
+//! Helpers for vanity address search.
+//!
+//! A pattern is matched against the cash_addr body, the part after the prefix, including its first character:
+//! `q` for P2PKH and `p` for P2SH addresses of 20-byte hashes.
+
+use super::AddressType;
+use super::cash_addr;
+
+/// Characters of the body encoding the version byte and hash of a 20-byte hash address; the rest is checksum.
+const DATA_LEN: usize = 34;
+
+/// Check whether a cash_addr address starts with `pattern`, ignoring case.
+/// The address is not validated, so candidates can be checked before the expensive parts of a search.
+/// # Arguments
+/// * `addr` - cash_addr address, with or without prefix.
+/// * `pattern` - Pattern, starting with the `q` or `p` of the body.
+/// # Returns
+/// * `true` if the body starts with `pattern`.
+/// # Example
+/// ```
+/// use bch_addr::vanity::matches_vanity;
+///
+/// assert!(matches_vanity("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk", "qph5k"));
+/// assert!(matches_vanity("QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK", "qph5k"));
+/// assert!(!matches_vanity("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR", "1B9"));
+/// ```
+pub fn matches_vanity(addr: &str, pattern: &str) -> bool {
+    let body = match addr.rfind(':') {
+        Some(sep) => &addr[sep + 1..],
+        None      => addr,
+    };
+    body.get(..pattern.len()).is_some_and(|head| head.eq_ignore_ascii_case(pattern)) && in_charset(pattern)
+}
+
+/// `pattern` only uses cash_addr characters.
+fn in_charset(pattern: &str) -> bool {
+    pattern.bytes().all(|c| cash_addr::decode_char(c).is_some())
+}
+
+/// Check whether some address of a 20-byte hash and `addr_type` starts with `pattern`.
+/// Besides the character set, the first character is fixed by the address type,
+/// the second one only carries 2 bits of the hash (`q`, `p`, `z` or `r`),
+/// and the 34th only 3 (the last two bits are padding). Longer patterns would reach into the checksum.
+/// # Arguments
+/// * `pattern` - Pattern, starting with the `q` or `p` of the body. Case is ignored.
+/// * `addr_type` - Address type.
+/// # Returns
+/// * `true` if `pattern` can be found.
+/// # Example
+/// ```
+/// use bch_addr::AddressType;
+/// use bch_addr::vanity::is_vanity_encodable;
+///
+/// assert!(is_vanity_encodable("qr2x", AddressType::P2PKH));
+/// assert!(!is_vanity_encodable("qx", AddressType::P2PKH));   // 'x' needs a non-zero hash size
+/// assert!(!is_vanity_encodable("qpb", AddressType::P2PKH));  // 'b' is not in the alphabet
+/// assert!(!is_vanity_encodable("qp", AddressType::P2SH));
+/// ```
+pub fn is_vanity_encodable(pattern: &str, addr_type: AddressType) -> bool {
+    if pattern.len() > DATA_LEN {
+        return false;
+    }
+
+    // version byte: type bits, 3 size bits (0 for 20 bytes); 5 bits per character
+    let first = addr_type.type_bits();
+    pattern.bytes().enumerate().all(|(i, c)| {
+        match (i, cash_addr::decode_char(c)) {
+            (_, None)                         => false,
+            (0, Some(v))                      => v == first,
+            (1, Some(v))                      => v < 4,
+            (i, Some(v)) if i == DATA_LEN - 1 => v & 3 == 0,
+            (_, Some(_))                      => true,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Converter, AddressFormat, Network};
+
+    #[test]
+    fn vanity_test() {
+        let converter = Converter::new();
+        for &(addr_type, hash) in &[(AddressType::P2PKH, [0x00; 20]), (AddressType::P2SH, [0xff; 20]), (AddressType::P2PKH, [0x5a; 20])] {
+            let addr = converter.encode(&AddressFormat::CashAddr, Network::Testnet, addr_type, &hash).unwrap();
+            let body = &addr["bchtest:".len()..];
+
+            for len in 0..=DATA_LEN {
+                assert!(is_vanity_encodable(&body[..len], addr_type), "{}", &body[..len]);
+                assert!(matches_vanity(&addr, &body[..len]));
+            }
+            assert!(!is_vanity_encodable(&body[..DATA_LEN + 1], addr_type));
+        }
+
+        assert!(!is_vanity_encodable("q", AddressType::P2SH));
+        assert!(!is_vanity_encodable(&format!("q{}", "l".repeat(33)), AddressType::P2PKH));
+        assert!(!matches_vanity("bchtest:qb", "qb"));
+        assert!(!matches_vanity("bchtest:q", "qq"));
+        assert!(!matches_vanity("bchtest:qé", "q\u{e9}"));
+    }
+}
@@ -0,0 +1,258 @@
+//! JavaScript bindings built with `wasm-bindgen`.
+//!
+//! Enabled by the `wasm` feature. Addresses go in and out as strings, and the
+//! enums are passed by the names bchaddrjs uses (`"legacy"`, `"cashaddr"`,
+//! `"mainnet"`, `"p2pkh"`, ...), typed as string unions in the generated
+//! TypeScript definitions. Failures are thrown as JS `Error`s.
+
+use std::mem;
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use super::{Converter, AddressFormat, Network, AddressType, Error};
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &str = r#"
+/** Bitcoin network. */
+export type Network = "mainnet" | "testnet" | "regtest";
+
+/** Address type. `"other"` for type fields registered with `Converter.addTypeBits`, see `detectAddressTypeBits`. */
+export type AddressType = "p2pkh" | "p2sh" | "other";
+
+/** `"legacy"`, `"cashaddr"` or the name of a format registered with `Converter.addPrefix`. */
+export type AddressFormat = "legacy" | "cashaddr" | (string & {});
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    /// `Network` name on the JS side.
+    #[wasm_bindgen(typescript_type = "Network")]
+    pub type JsNetwork;
+
+    /// `AddressType` name on the JS side.
+    #[wasm_bindgen(typescript_type = "AddressType")]
+    pub type JsAddressType;
+
+    /// `AddressFormat` name on the JS side.
+    #[wasm_bindgen(typescript_type = "AddressFormat")]
+    pub type JsAddressFormat;
+}
+
+/// `Converter` exported to JavaScript as `Converter`.
+#[wasm_bindgen(js_name = Converter)]
+#[derive(Debug, Default)]
+pub struct WasmConverter {
+    converter: Converter,
+}
+
+#[wasm_bindgen(js_class = Converter)]
+impl WasmConverter {
+    /// Construct a converter with the built-in prefixes.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmConverter {
+        WasmConverter::default()
+    }
+
+    /// Register `prefix` for `network` under the user-defined format `format_name`.
+    #[wasm_bindgen(js_name = addPrefix)]
+    pub fn add_prefix(&mut self, prefix: &str, network: &JsNetwork, format_name: &str) -> Result<(), JsError> {
+        let network = read_network(network)?;
+        let converter = mem::take(&mut self.converter);
+        self.converter = converter.add_prefixes(&[(prefix, network)], format_name);
+        Ok(())
+    }
+
+    /// Accept the unassigned type fields `type_bits` (2 to 15) in addresses of `format`, detected as `"other"`.
+    #[wasm_bindgen(js_name = addTypeBits)]
+    pub fn add_type_bits(&mut self, format: &JsAddressFormat, type_bits: &[u8]) -> Result<(), JsError> {
+        let format = read_format(format)?;
+        let converter = mem::take(&mut self.converter);
+        self.converter = converter.add_type_bits(&format, type_bits);
+        Ok(())
+    }
+
+    /// Convert to cash_addr format, or to `format`/`network` when given.
+    #[wasm_bindgen(js_name = toCashAddress)]
+    pub fn to_cash_addr(&self, addr: &str, format: Option<JsAddressFormat>, network: Option<JsNetwork>) -> Result<String, JsError> {
+        let format = format.as_ref().map(read_format).transpose()?;
+        let network = network.as_ref().map(read_network).transpose()?;
+        self.converter.to_cash_addr_with_options(addr, format, network).map_err(js_error)
+    }
+
+    /// Convert to legacy format.
+    #[wasm_bindgen(js_name = toLegacyAddress)]
+    pub fn to_legacy_addr(&self, addr: &str) -> Result<String, JsError> {
+        self.converter.to_legacy_addr(addr).map_err(js_error)
+    }
+
+    /// `"legacy"`, `"cashaddr"` or the name of a user-defined format.
+    #[wasm_bindgen(js_name = detectAddressFormat)]
+    pub fn detect_addr_format(&self, addr: &str) -> Result<JsAddressFormat, JsError> {
+        self.converter.detect_addr_format(addr).map(|format| to_js(format_name(&format))).map_err(js_error)
+    }
+
+    /// `"mainnet"`, `"testnet"` or `"regtest"`.
+    #[wasm_bindgen(js_name = detectAddressNetwork)]
+    pub fn detect_addr_network(&self, addr: &str) -> Result<JsNetwork, JsError> {
+        self.converter.detect_addr_network(addr).map(|network| to_js(network_name(network))).map_err(js_error)
+    }
+
+    /// `"p2pkh"`, `"p2sh"` or `"other"`.
+    #[wasm_bindgen(js_name = detectAddressType)]
+    pub fn detect_addr_type(&self, addr: &str) -> Result<JsAddressType, JsError> {
+        self.converter.detect_addr_type(addr).map(|addr_type| to_js(type_name(addr_type))).map_err(js_error)
+    }
+
+    /// Type field of the address: 0 for P2PKH, 1 for P2SH, the registered one for `"other"`.
+    #[wasm_bindgen(js_name = detectAddressTypeBits)]
+    pub fn detect_addr_type_bits(&self, addr: &str) -> Result<u8, JsError> {
+        self.converter.detect_addr_type(addr).map(AddressType::type_bits).map_err(js_error)
+    }
+
+    /// `true` if `addr` is valid in any registered format.
+    #[wasm_bindgen(js_name = isValidAddress)]
+    pub fn is_valid_addr(&self, addr: &str) -> bool {
+        self.converter.parse(addr).is_ok()
+    }
+
+    /// `true` if `addr` is a legacy address.
+    #[wasm_bindgen(js_name = isLegacyAddress)]
+    pub fn is_legacy_addr(&self, addr: &str) -> bool {
+        self.converter.is_legacy_addr(addr)
+    }
+
+    /// `true` if `addr` is a cash_addr (or user-defined format) address.
+    #[wasm_bindgen(js_name = isCashAddress)]
+    pub fn is_cash_addr(&self, addr: &str) -> bool {
+        self.converter.is_cash_addr(addr)
+    }
+
+    /// `true` if `addr` is a mainnet address.
+    #[wasm_bindgen(js_name = isMainnetAddress)]
+    pub fn is_mainnet_addr(&self, addr: &str) -> bool {
+        self.converter.is_mainnet_addr(addr)
+    }
+
+    /// `true` if `addr` is a testnet address.
+    #[wasm_bindgen(js_name = isTestnetAddress)]
+    pub fn is_testnet_addr(&self, addr: &str) -> bool {
+        self.converter.is_testnet_addr(addr)
+    }
+
+    /// `true` if `addr` is a regtest address.
+    #[wasm_bindgen(js_name = isRegtestAddress)]
+    pub fn is_regtest_addr(&self, addr: &str) -> bool {
+        self.converter.is_regtest_addr(addr)
+    }
+
+    /// `true` if `addr` is a P2PKH address.
+    #[wasm_bindgen(js_name = isP2PKHAddress)]
+    pub fn is_p2pkh_addr(&self, addr: &str) -> bool {
+        self.converter.is_p2pkh_addr(addr)
+    }
+
+    /// `true` if `addr` is a P2SH address.
+    #[wasm_bindgen(js_name = isP2SHAddress)]
+    pub fn is_p2sh_addr(&self, addr: &str) -> bool {
+        self.converter.is_p2sh_addr(addr)
+    }
+}
+
+/// Convert to cash_addr format with the built-in prefixes.
+#[wasm_bindgen(js_name = toCashAddress)]
+pub fn to_cash_addr(addr: &str) -> Result<String, JsError> {
+    Converter::new().to_cash_addr(addr).map_err(js_error)
+}
+
+/// Convert to legacy format with the built-in prefixes.
+#[wasm_bindgen(js_name = toLegacyAddress)]
+pub fn to_legacy_addr(addr: &str) -> Result<String, JsError> {
+    Converter::new().to_legacy_addr(addr).map_err(js_error)
+}
+
+/// `true` if `addr` is a valid legacy or cash_addr address.
+#[wasm_bindgen(js_name = isValidAddress)]
+pub fn is_valid_addr(addr: &str) -> bool {
+    Converter::new().parse(addr).is_ok()
+}
+
+fn js_error(err: Error) -> JsError {
+    JsError::new(&err.to_string())
+}
+
+fn js_string(value: &JsValue) -> Result<String, JsError> {
+    value.as_string().ok_or_else(|| JsError::new("Expected a string"))
+}
+
+fn read_network(network: &JsNetwork) -> Result<Network, JsError> {
+    parse_network(&js_string(network)?)
+}
+
+fn read_format(format: &JsAddressFormat) -> Result<AddressFormat, JsError> {
+    Ok(parse_format(&js_string(format)?))
+}
+
+fn to_js<T: JsCast>(name: &str) -> T {
+    JsValue::from_str(name).unchecked_into()
+}
+
+fn parse_network(network: &str) -> Result<Network, JsError> {
+    match network {
+        "mainnet" => Ok(Network::Mainnet),
+        "testnet" => Ok(Network::Testnet),
+        "regtest" => Ok(Network::Regtest),
+        _         => Err(JsError::new(&format!("Unknown network: {}", network))),
+    }
+}
+
+fn parse_format(format: &str) -> AddressFormat {
+    match format {
+        "legacy"   => AddressFormat::Legacy,
+        "cashaddr" => AddressFormat::CashAddr,
+        other      => AddressFormat::Other(other.to_string()),
+    }
+}
+
+fn network_name(network: Network) -> &'static str {
+    match network {
+        Network::Mainnet => "mainnet",
+        Network::Testnet => "testnet",
+        Network::Regtest => "regtest",
+    }
+}
+
+fn format_name(format: &AddressFormat) -> &str {
+    match format {
+        AddressFormat::Legacy      => "legacy",
+        AddressFormat::CashAddr    => "cashaddr",
+        AddressFormat::Other(name) => name,
+    }
+}
+
+fn type_name(addr_type: AddressType) -> &'static str {
+    match addr_type {
+        AddressType::P2PKH    => "p2pkh",
+        AddressType::P2SH     => "p2sh",
+        AddressType::Other(_) => "other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_test() {
+        for format in &[AddressFormat::Legacy, AddressFormat::CashAddr, AddressFormat::Other("SLPAddr".to_string())] {
+            assert_eq!(parse_format(format_name(format)), *format);
+        }
+        assert_eq!(network_name(Network::Regtest), "regtest");
+        assert_eq!(type_name(AddressType::P2SH), "p2sh");
+        assert_eq!(type_name(AddressType::Other(2)), "other");
+
+        let converter = WasmConverter::new();
+        assert!(converter.is_valid_addr("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR"));
+        assert!(!converter.is_valid_addr("bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwl"));
+    }
+}
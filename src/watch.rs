@@ -0,0 +1,263 @@
+use std::collections::HashSet;
+
+use super::{Converter, AddressType};
+use super::error::{Error, Result};
+use super::script;
+
+/// Address or output script checked against a `WatchList`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WatchTarget<'t> {
+    /// Address in any format.
+    Address(&'t str),
+    /// Serialized output script (scriptPubKey).
+    Script(&'t [u8]),
+}
+
+impl<'t> From<&'t str> for WatchTarget<'t> {
+    fn from(addr: &'t str) -> WatchTarget<'t> {
+        WatchTarget::Address(addr)
+    }
+}
+
+impl<'t> From<&'t [u8]> for WatchTarget<'t> {
+    fn from(script: &'t [u8]) -> WatchTarget<'t> {
+        WatchTarget::Script(script)
+    }
+}
+
+/// Tracked addresses, matched against addresses in any format and against output scripts,
+/// e.g. to screen the outputs of mempool transactions.
+///
+/// Addresses are stored by type and hash. The network is left out since output scripts do not have one;
+/// a monitor only sees outputs of its own network.
+///
+/// For very large lists, `with_bloom` adds a Bloom filter in front of the exact lookup
+/// so that most untracked outputs are rejected without touching the set.
+/// # Example
+/// ```
+/// # #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+/// # {
+/// # use bch_addr::{Converter, WatchList, AddressType};
+/// use bch_addr::script::script_pub_key;
+///
+/// let converter = Converter::new();
+/// let mut watched = WatchList::new(&converter).with_bloom(1_000_000, 0.001);
+/// watched.insert("bitcoincash:pph5kuz78czq00e3t85ugpgd7xmer5kr7crv8a2z4t").unwrap();
+///
+/// assert!(watched.matches("3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9"));
+/// let (_, _, addr_type, hash) = converter.parse("3BqVJRg7Jf94yJSvj2zxaPFAEYh3MAyyw9").unwrap();
+/// assert!(watched.matches(&script_pub_key(addr_type, &hash).unwrap()[..]));
+/// assert!(!watched.matches("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR"));
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct WatchList<'a> {
+    converter: &'a Converter,
+    p2pkh: HashSet<Box<[u8]>>,
+    p2sh: HashSet<Box<[u8]>>,
+    bloom: Option<Bloom>,
+}
+
+impl<'a> WatchList<'a> {
+    /// Construct an empty list without a Bloom filter.
+    /// # Arguments
+    /// * `converter` - Converter providing the prefix configuration.
+    /// # Returns
+    /// * Watch list.
+    pub fn new(converter: &'a Converter) -> WatchList<'a> {
+        WatchList {
+            converter,
+            p2pkh: HashSet::new(),
+            p2sh: HashSet::new(),
+            bloom: None,
+        }
+    }
+
+    /// Put a Bloom filter in front of the exact lookup; addresses already in the list are added to it.
+    /// Matches stay exact, the filter only speeds up rejecting untracked outputs.
+    /// # Arguments
+    /// * `expected_len` - Number of addresses the list is expected to hold.
+    /// * `false_positive_rate` - Share of untracked outputs passing the filter at `expected_len` addresses, e.g. `0.001`.
+    /// # Returns
+    /// * Watch list.
+    pub fn with_bloom(mut self, expected_len: usize, false_positive_rate: f64) -> WatchList<'a> {
+        let mut bloom = Bloom::new(expected_len, false_positive_rate);
+        for hash in &self.p2pkh {
+            bloom.insert(AddressType::P2PKH, hash);
+        }
+        for hash in &self.p2sh {
+            bloom.insert(AddressType::P2SH, hash);
+        }
+        self.bloom = Some(bloom);
+        self
+    }
+
+    /// Addresses of `addr_type`, `None` for types without an output script, which are never tracked.
+    fn set(&self, addr_type: AddressType) -> Option<&HashSet<Box<[u8]>>> {
+        match addr_type {
+            AddressType::P2PKH    => Some(&self.p2pkh),
+            AddressType::P2SH     => Some(&self.p2sh),
+            AddressType::Other(_) => None,
+        }
+    }
+
+    /// Add an address.
+    /// # Arguments
+    /// * `addr` - Address in any format.
+    /// # Returns
+    /// * `true` if the address was not tracked yet.
+    ///   `Error::UnsupportedAddressType` for `AddressType::Other`, as no output pays to it.
+    pub fn insert(&mut self, addr: &str) -> Result<bool> {
+        let (_, _, addr_type, hash) = self.converter.parse(addr)?;
+        let set = match addr_type {
+            AddressType::P2PKH    => &mut self.p2pkh,
+            AddressType::P2SH     => &mut self.p2sh,
+            AddressType::Other(_) => return Err(Error::UnsupportedAddressType(addr_type)),
+        };
+        if let Some(bloom) = &mut self.bloom {
+            bloom.insert(addr_type, &hash);
+        }
+        Ok(set.insert(hash.into_boxed_slice()))
+    }
+
+    /// Check an address or output script against the list.
+    /// # Arguments
+    /// * `target` - Address in any format (`&str`) or output script (`&[u8]`).
+    /// # Returns
+    /// * `true` if it pays to a tracked address, `false` if not, invalid, or a script without an address form.
+    pub fn matches<'t, T: Into<WatchTarget<'t>>>(&self, target: T) -> bool {
+        match target.into() {
+            WatchTarget::Address(addr) => match self.converter.parse(addr) {
+                Ok((_, _, addr_type, hash)) => self.contains(addr_type, &hash),
+                Err(_)                      => false,
+            },
+            WatchTarget::Script(script) => match script::address_hash(script) {
+                Some((addr_type, hash)) => self.contains(addr_type, hash),
+                None                    => false,
+            },
+        }
+    }
+
+    fn contains(&self, addr_type: AddressType, hash: &[u8]) -> bool {
+        if let Some(bloom) = &self.bloom {
+            if !bloom.contains(addr_type, hash) {
+                return false;
+            }
+        }
+        self.set(addr_type).is_some_and(|set| set.contains(hash))
+    }
+
+    /// Number of tracked addresses.
+    pub fn len(&self) -> usize {
+        self.p2pkh.len() + self.p2sh.len()
+    }
+
+    /// `true` if no address is tracked.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Bloom filter over address types and hashes.
+/// Hashes are already uniformly distributed, so their bytes seed the bit positions directly.
+#[derive(Debug)]
+struct Bloom {
+    bits: Vec<u64>,
+    hash_count: u32,
+}
+
+impl Bloom {
+    fn new(expected_len: usize, false_positive_rate: f64) -> Bloom {
+        let n = expected_len.max(1) as f64;
+        let p = false_positive_rate.clamp(1e-9, 0.5);
+        let bit_len = (-n * p.ln() / (2f64.ln() * 2f64.ln())).ceil().max(64.0);
+        let hash_count = (bit_len / n * 2f64.ln()).round().clamp(1.0, 32.0) as u32;
+
+        Bloom {
+            bits: vec![0; (bit_len as usize).div_ceil(64)],
+            hash_count,
+        }
+    }
+
+    /// Bit positions of `hash`, by double hashing.
+    fn positions(&self, addr_type: AddressType, hash: &[u8]) -> impl Iterator<Item = usize> {
+        let h1 = read_u64(hash, 0) ^ u64::from(addr_type.version_bits());
+        let h2 = read_u64(hash, 8) | 1;
+        let bit_len = self.bits.len() as u64 * 64;
+        (0..u64::from(self.hash_count)).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % bit_len) as usize)
+    }
+
+    fn insert(&mut self, addr_type: AddressType, hash: &[u8]) {
+        for pos in self.positions(addr_type, hash) {
+            self.bits[pos / 64] |= 1 << (pos % 64);
+        }
+    }
+
+    fn contains(&self, addr_type: AddressType, hash: &[u8]) -> bool {
+        self.positions(addr_type, hash).all(|pos| self.bits[pos / 64] & (1 << (pos % 64)) != 0)
+    }
+}
+
+/// Little endian `u64` at `offset`, zero-padded past the end of `bytes`.
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    let mut buf = [0; 8];
+    let bytes = bytes.get(offset..).unwrap_or_default();
+    let len = bytes.len().min(8);
+    buf[..len].copy_from_slice(&bytes[..len]);
+    u64::from_le_bytes(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    #![cfg_attr(not(all(feature = "legacy", feature = "cashaddr")), allow(unused_imports))]
+
+    use super::*;
+    use crate::Network;
+
+    #[cfg(all(feature = "legacy", feature = "cashaddr"))]
+    #[test]
+    fn watch_list_test() {
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+        let p2pkh = script::script_pub_key(AddressType::P2PKH, &converter.parse("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap().3).unwrap();
+        let p2sh32 = script::script_pub_key(AddressType::P2SH, &[0xab; 32]).unwrap();
+        let p2sh32_addr = converter.encode(&crate::AddressFormat::CashAddr, Network::Mainnet, AddressType::P2SH, &[0xab; 32]).unwrap();
+
+        for bloom in &[false, true] {
+            let mut list = WatchList::new(&converter);
+            assert!(list.insert("simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg").unwrap());
+            if *bloom {
+                list = list.with_bloom(100, 0.01);
+            }
+            assert!(!list.insert("1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR").unwrap());
+            assert!(list.insert(&p2sh32_addr).unwrap());
+            assert!(list.insert("bitcoincash:bad").is_err());
+            assert_eq!(list.len(), 2);
+
+            assert!(list.matches("QPH5KUZ78CZQ00E3T85UGPGD7XMER5KR7C5F6JDPWK"));
+            assert!(list.matches("bchtest:qph5kuz78czq00e3t85ugpgd7xmer5kr7csm740kf2"));
+            assert!(list.matches(&p2pkh[..]));
+            assert!(list.matches(&p2sh32[..]));
+            assert!(!list.matches("bitcoincash:pph5kuz78czq00e3t85ugpgd7xmer5kr7crv8a2z4t"));
+            assert!(!list.matches("185K5yAfcrARrHjNVt4iAUHtkYqcogF4km"));
+            assert!(!list.matches("bitcoincash:bad"));
+            assert!(!list.matches(&p2pkh[1..]));
+            assert!(!list.matches(&[0x6a][..]));
+        }
+    }
+
+    #[cfg(feature = "hash160")]
+    #[test]
+    fn bloom_test() {
+        let mut bloom = Bloom::new(1000, 0.01);
+        for i in 0..1000u32 {
+            bloom.insert(AddressType::P2PKH, &crate::hash::hash160(&i.to_le_bytes()));
+        }
+        for i in 0..1000u32 {
+            assert!(bloom.contains(AddressType::P2PKH, &crate::hash::hash160(&i.to_le_bytes())));
+        }
+        let false_positives = (1000..11000u32)
+            .filter(|i| bloom.contains(AddressType::P2PKH, &crate::hash::hash160(&i.to_le_bytes())))
+            .count();
+        assert!(false_positives < 300, "{} false positives", false_positives);
+    }
+}
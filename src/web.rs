@@ -0,0 +1,252 @@
+//! Request extractors parsing an address and normalizing it to a `DbAddress`.
+//!
+//! With the `axum` feature they implement `FromRequestParts` for any state providing an `Arc<Converter>`,
+//! and with the `actix` feature `FromRequest`, using the `web::Data<Converter>` app data
+//! (or the built-in prefixes when none is registered).
+//! Invalid addresses are rejected with `400 Bad Request` and a JSON body:
+//! `{"error": "invalid address: bitcoincash:bad", "address": "bitcoincash:bad"}`.
+//! # Example
+//! ```
+//! # #[cfg(feature = "axum")]
+//! # {
+//! use std::sync::Arc;
+//! use axum::{routing::get, Router};
+//! use bch_addr::Converter;
+//! use bch_addr::web::AddressPath;
+//!
+//! async fn balance(AddressPath(addr): AddressPath) -> String {
+//!     format!("balance of {}", addr)
+//! }
+//!
+//! let app: Router = Router::new()
+//!     .route("/balance/{addr}", get(balance))
+//!     .with_state(Arc::new(Converter::new()));
+//! # }
+//! ```
+
+use std::fmt;
+
+use super::{Converter, DbAddress};
+
+/// Address taken from the only path parameter of the route.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddressPath(pub DbAddress);
+
+/// Address taken from the `address` query parameter.
+/// Use `Query` with `bch_addr::serde` for other parameter names.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddressQuery(pub DbAddress);
+
+/// Missing or invalid address, responded as `400 Bad Request`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddressRejection {
+    /// Address given, `None` if missing.
+    pub address: Option<String>,
+    /// Reason of the rejection.
+    pub message: String,
+}
+
+impl AddressRejection {
+    /// JSON response body.
+    pub fn body(&self) -> String {
+        serde_json::json!({
+            "error": self.message,
+            "address": self.address,
+        }).to_string()
+    }
+}
+
+impl fmt::Display for AddressRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for AddressRejection {}
+
+fn normalize(converter: &Converter, addr: Option<&str>, missing: &str) -> Result<DbAddress, AddressRejection> {
+    let addr = addr.ok_or_else(|| AddressRejection {
+        address: None,
+        message: missing.to_string(),
+    })?;
+    DbAddress::with_converter(converter, addr).map_err(|err| AddressRejection {
+        address: Some(addr.to_string()),
+        message: err.to_string(),
+    })
+}
+
+const MISSING_PATH: &str = "expected exactly one path parameter";
+const MISSING_QUERY: &str = "missing `address` query parameter";
+
+#[cfg(feature = "axum")]
+mod axum_impls {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use axum::extract::{FromRef, FromRequestParts, Query, RawPathParams};
+    use axum::http::{header, request::Parts, StatusCode};
+    use axum::response::{IntoResponse, Response};
+
+    use super::*;
+
+    impl<S> FromRequestParts<S> for AddressPath
+        where Arc<Converter>: FromRef<S>, S: Send + Sync
+    {
+        type Rejection = AddressRejection;
+
+        async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+            let params = RawPathParams::from_request_parts(parts, state).await.ok();
+            let addr = params.as_ref().and_then(|params| {
+                let mut params = params.iter();
+                match (params.next(), params.next()) {
+                    (Some((_, addr)), None) => Some(addr),
+                    _                       => None,
+                }
+            });
+            normalize(&Arc::from_ref(state), addr, MISSING_PATH).map(AddressPath)
+        }
+    }
+
+    impl<S> FromRequestParts<S> for AddressQuery
+        where Arc<Converter>: FromRef<S>, S: Send + Sync
+    {
+        type Rejection = AddressRejection;
+
+        async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+            let query = Query::<HashMap<String, String>>::try_from_uri(&parts.uri).ok();
+            let addr = query.as_ref().and_then(|query| query.get("address")).map(String::as_str);
+            normalize(&Arc::from_ref(state), addr, MISSING_QUERY).map(AddressQuery)
+        }
+    }
+
+    impl IntoResponse for AddressRejection {
+        fn into_response(self) -> Response {
+            (StatusCode::BAD_REQUEST, [(header::CONTENT_TYPE, "application/json")], self.body()).into_response()
+        }
+    }
+}
+
+#[cfg(feature = "actix")]
+mod actix_impls {
+    use std::collections::HashMap;
+    use std::future::{ready, Ready};
+
+    use actix_web::{dev::Payload, http::StatusCode, web, FromRequest, HttpRequest, HttpResponse, ResponseError};
+
+    use super::*;
+
+    fn with_converter<T, F>(req: &HttpRequest, f: F) -> T
+        where F: FnOnce(&Converter) -> T
+    {
+        match req.app_data::<web::Data<Converter>>() {
+            Some(converter) => f(converter),
+            None            => f(&Converter::new()),
+        }
+    }
+
+    impl FromRequest for AddressPath {
+        type Error = AddressRejection;
+        type Future = Ready<Result<Self, Self::Error>>;
+
+        fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+            let mut params = req.match_info().iter();
+            let addr = match (params.next(), params.next()) {
+                (Some((_, addr)), None) => Some(addr),
+                _                       => None,
+            };
+            ready(with_converter(req, |converter| normalize(converter, addr, MISSING_PATH)).map(AddressPath))
+        }
+    }
+
+    impl FromRequest for AddressQuery {
+        type Error = AddressRejection;
+        type Future = Ready<Result<Self, Self::Error>>;
+
+        fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+            let query = web::Query::<HashMap<String, String>>::from_query(req.query_string()).ok();
+            let addr = query.as_ref().and_then(|query| query.get("address")).map(String::as_str);
+            ready(with_converter(req, |converter| normalize(converter, addr, MISSING_QUERY)).map(AddressQuery))
+        }
+    }
+
+    impl ResponseError for AddressRejection {
+        fn status_code(&self) -> StatusCode {
+            StatusCode::BAD_REQUEST
+        }
+
+        fn error_response(&self) -> HttpResponse {
+            HttpResponse::BadRequest().content_type("application/json").body(self.body())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CASH_ADDR: &str = "bitcoincash:qph5kuz78czq00e3t85ugpgd7xmer5kr7c5f6jdpwk";
+    const LEGACY: &str = "1B9UNtBfkkpgt8kVbwLN9ktE62QKnMbDzR";
+
+    #[cfg(feature = "axum")]
+    #[tokio::test]
+    async fn axum_test() {
+        use std::sync::Arc;
+
+        use axum::{body::{self, Body}, http::{Request, StatusCode}, routing::get, Router};
+        use tower::ServiceExt;
+
+        use crate::Network;
+
+        let converter = Converter::new().add_prefixes(&[("simpleledger", Network::Mainnet)], "SLPAddr");
+        let app = Router::new()
+            .route("/path/{addr}", get(|AddressPath(addr): AddressPath| async move { addr.into_string() }))
+            .route("/query", get(|AddressQuery(addr): AddressQuery| async move { addr.into_string() }))
+            .with_state(Arc::new(converter));
+        let get = |uri: String| {
+            let app = app.clone();
+            async move {
+                let res = app.oneshot(Request::get(uri).body(Body::empty()).unwrap()).await.unwrap();
+                let status = res.status();
+                let body = body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+                (status, String::from_utf8(body.to_vec()).unwrap())
+            }
+        };
+
+        assert_eq!(get(format!("/path/{}", LEGACY)).await, (StatusCode::OK, CASH_ADDR.to_string()));
+        assert_eq!(get("/path/simpleledger:qph5kuz78czq00e3t85ugpgd7xmer5kr7ccj3fcpsg".to_string()).await, (StatusCode::OK, CASH_ADDR.to_string()));
+        assert_eq!(get(format!("/query?address={}", LEGACY)).await, (StatusCode::OK, CASH_ADDR.to_string()));
+
+        let (status, body) = get("/path/bitcoincash:bad".to_string()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&body).unwrap(), serde_json::json!({
+            "error": "invalid address: bitcoincash:bad",
+            "address": "bitcoincash:bad",
+        }));
+        let (status, body) = get("/query".to_string()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.contains(MISSING_QUERY));
+    }
+
+    #[cfg(feature = "actix")]
+    #[test]
+    fn actix_test() {
+        use actix_web::{dev::Payload, test::TestRequest, FromRequest, ResponseError};
+
+        let req = TestRequest::default().param("addr", LEGACY).to_http_request();
+        let addr = AddressPath::from_request(&req, &mut Payload::None).into_inner().unwrap();
+        assert_eq!(addr.0.as_str(), CASH_ADDR);
+
+        let req = TestRequest::with_uri(&format!("/?address={}", LEGACY)).to_http_request();
+        let addr = AddressQuery::from_request(&req, &mut Payload::None).into_inner().unwrap();
+        assert_eq!(addr.0.as_str(), CASH_ADDR);
+
+        let req = TestRequest::default().param("addr", "bitcoincash:bad").to_http_request();
+        let err = AddressPath::from_request(&req, &mut Payload::None).into_inner().unwrap_err();
+        assert_eq!(err.address.as_deref(), Some("bitcoincash:bad"));
+        assert_eq!(err.error_response().status(), 400);
+
+        let req = TestRequest::default().to_http_request();
+        let err = AddressQuery::from_request(&req, &mut Payload::None).into_inner().unwrap_err();
+        assert_eq!(err.message, MISSING_QUERY);
+    }
+}
@@ -0,0 +1,28 @@
+/// Word list of `Converter::checkwords`: 256 short, distinct English nouns, one per byte value.
+/// Must never change, since that would change the words of every address.
+pub(crate) const WORDS: [&str; 256] = [
+    "acid", "acorn", "actor", "adobe", "agent", "alarm", "album", "alley", "amber", "angel", "ankle",
+    "apple", "apron", "arrow", "atlas", "attic", "autumn", "bacon", "badge", "bagel", "baker", "bamboo",
+    "banjo", "barn", "basil", "beach", "beard", "beaver", "bell", "bench", "berry", "bison", "blade",
+    "blanket", "bloom", "boat", "bottle", "brick", "bridge", "bronze", "brush", "bubble", "bucket",
+    "buffalo", "cabin", "cactus", "camel", "candle", "canoe", "canyon", "carpet", "carrot", "castle",
+    "cedar", "cello", "chalk", "cherry", "chess", "cider", "circus", "citrus", "clock", "cloud", "clover",
+    "cobra", "coconut", "comet", "copper", "coral", "cotton", "coyote", "crane", "crayon", "crown",
+    "crystal", "curtain", "daisy", "dancer", "desert", "diamond", "dinner", "dolphin", "donkey", "dragon",
+    "drum", "eagle", "easel", "echo", "elbow", "ember", "engine", "falcon", "feather", "fern", "ferry",
+    "fiddle", "flame", "flute", "forest", "fossil", "fox", "galaxy", "garden", "garlic", "geyser", "ginger",
+    "giraffe", "glacier", "globe", "goose", "granite", "grape", "gravel", "guitar", "hammer", "harbor",
+    "harp", "hazel", "helmet", "heron", "honey", "horizon", "igloo", "island", "ivory", "jacket", "jaguar",
+    "jelly", "jigsaw", "jungle", "kayak", "kettle", "kitten", "koala", "ladder", "lagoon", "lantern", "lava",
+    "lemon", "lily", "lizard", "lobster", "lotus", "magnet", "mango", "maple", "marble", "meadow", "melon",
+    "meteor", "mirror", "mitten", "monkey", "moose", "mosaic", "muffin", "nectar", "needle", "nest",
+    "noodle", "nugget", "oasis", "ocean", "octopus", "olive", "onion", "orange", "orchid", "otter", "owl",
+    "paddle", "palace", "panda", "panther", "parrot", "peach", "peanut", "pebble", "pepper", "piano",
+    "pickle", "pigeon", "pillow", "pine", "pirate", "planet", "plum", "pony", "potato", "prism", "pumpkin",
+    "puzzle", "quartz", "quill", "rabbit", "radish", "rainbow", "raven", "rhino", "ribbon", "river", "robot",
+    "rocket", "rose", "ruby", "saddle", "salmon", "sandal", "satin", "scarf", "shadow", "shark", "shell",
+    "silver", "sketch", "sled", "slipper", "snail", "spider", "sponge", "spruce", "squid", "statue", "storm",
+    "sugar", "summit", "sunset", "swan", "tablet", "tango", "teapot", "temple", "thunder", "tiger", "tomato",
+    "trumpet", "tulip", "tunnel", "turtle", "valley", "velvet", "violin", "volcano", "waffle", "walnut",
+    "walrus", "whale", "whistle", "willow", "window", "wizard", "wolf", "yacht", "yarn", "yogurt", "zebra",
+];